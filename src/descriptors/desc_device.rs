@@ -1,39 +1,53 @@
+use core::fmt;
+
 use num_derive::FromPrimitive;
 
+use super::{parser::Error, PortSpeed};
+
 #[derive(Copy, Clone, Default, Debug)]
 #[repr(C, packed)]
 pub struct Device {
     pub len: u8,
     pub descriptor_type: u8,
-    pub cd_usb: u16,
+    pub cd_usb: BcdVersion,
     pub class: u8,
     pub subclass: u8,
     pub protocol: u8,
     pub max_packet_size0: u8,
     pub vendor: u16,
     pub product_id: u16,
-    pub device: u16,
+    pub device: BcdVersion,
     pub manufacture: u8,
     pub product: u8,
     pub serial_number: u8,
     pub num_configurations: u8,
 }
 impl Device {
-    pub fn max_packet_size(&self) -> u16 {
-        if let (3, _) = self.version() {
-            2_u16.pow(self.max_packet_size0.into())
+    //the encoding of bMaxPacketSize0 depends on the speed the device actually runs at, not on bcdUSB
+    pub fn max_packet_size(&self, port_speed: PortSpeed) -> Result<u16, Error> {
+        let raw = self.max_packet_size0;
+        let (size, legal): (u16, &[u16]) = match port_speed {
+            PortSpeed::LowSpeed => (raw.into(), &[8]),
+            PortSpeed::FullSpeed => (raw.into(), &[8, 16, 32, 64]),
+            PortSpeed::HighSpeed => (raw.into(), &[64]),
+            PortSpeed::SuperSpeed | PortSpeed::SuperSpeedPlus => {
+                (if raw < 16 { 1 << raw } else { 0 }, &[512])
+            }
+        };
+
+        if legal.contains(&size) {
+            Ok(size)
         } else {
-            self.max_packet_size0.into()
+            Err(Error::InvalidMaxPacketSize0(raw))
         }
     }
 
-    fn version(&self) -> (u8, u8) {
-        let cd_usb = self.cd_usb;
+    pub fn version(&self) -> BcdVersion {
+        self.cd_usb
+    }
 
-        (
-            (cd_usb >> 8).try_into().unwrap(),
-            (cd_usb & 0xff).try_into().unwrap(),
-        )
+    pub fn device_version(&self) -> BcdVersion {
+        self.device
     }
 
     pub fn is_refer_interface(&self) -> bool {
@@ -43,7 +57,72 @@ impl Device {
     }
 }
 
-#[derive(FromPrimitive, Copy, Clone, Debug)]
+//binary coded decimal release number as 0xJJMN, e.g. 0x0210 is 2.1.0
+#[derive(Copy, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct BcdVersion(u16);
+
+impl BcdVersion {
+    //two decimal digits of major and one each of minor and sub-minor, None if they do not fit
+    pub const fn new(major: u8, minor: u8, sub_minor: u8) -> Option<Self> {
+        if major > 99 || minor > 9 || sub_minor > 9 {
+            return None;
+        }
+        Some(Self(
+            ((major / 10) as u16) << 12
+                | ((major % 10) as u16) << 8
+                | (minor as u16) << 4
+                | sub_minor as u16,
+        ))
+    }
+
+    pub const fn from_raw(raw: u16) -> Self {
+        Self(raw)
+    }
+
+    pub const fn raw(&self) -> u16 {
+        self.0
+    }
+
+    pub const fn major(&self) -> u8 {
+        ((self.0 >> 12) & 0xf) as u8 * 10 + ((self.0 >> 8) & 0xf) as u8
+    }
+
+    pub const fn minor(&self) -> u8 {
+        ((self.0 >> 4) & 0xf) as u8
+    }
+
+    pub const fn sub_minor(&self) -> u8 {
+        (self.0 & 0xf) as u8
+    }
+}
+
+impl From<u16> for BcdVersion {
+    fn from(raw: u16) -> Self {
+        Self(raw)
+    }
+}
+
+impl From<BcdVersion> for u16 {
+    fn from(version: BcdVersion) -> Self {
+        version.0
+    }
+}
+
+//same layout as lsusb prints bcdUSB/bcdDevice: "2.10"
+impl fmt::Display for BcdVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:x}.{:02x}", self.0 >> 8, self.0 & 0xff)
+    }
+}
+
+impl fmt::Debug for BcdVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BcdVersion({})", self)
+    }
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
 #[repr(u8)]
 pub enum StandardUSBDeviceClassCode {
     ReferInterfaceDescriptor = 0x00,
@@ -63,6 +142,9 @@ pub enum StandardUSBDeviceClassCode {
     AudioVideoDevices = 0x10,
     BillboardDeviceClass = 0x11,
     USBTypeCBridge = 0x12,
+    BulkDisplayProtocol = 0x13,
+    MCTP = 0x14,
+    I3C = 0x3C,
     DiagnosticDevice = 0xDC,
     WirelessController = 0xE0,
     Miscellaneous = 0xEF,
@@ -70,15 +152,217 @@ pub enum StandardUSBDeviceClassCode {
     VendorSpecific = 0xFF,
 }
 
-// #[derive(Copy, Clone, Debug, FromPrimitive)]
-// #[repr(u8)]
-// pub enum StandardUSBDeviceSubClassCode {
-//     Common = 0x02,
-//     Any = 0x03,
-// }
-
-// #[derive(FromPrimitive, Copy, Clone, Debug)]
-// #[repr(u8)]
-// pub enum StandardUSBDeviceProtocol {
-//     ReferInterfaceAssociationDescriptor = 0x01,
-// }
+impl StandardUSBDeviceClassCode {
+    pub fn name(&self) -> &'static str {
+        DefinedClassCode::lookup(*self as u8, None, None)
+            .map(|code| code.name)
+            .unwrap_or("Unknown")
+    }
+
+    pub fn usage(&self) -> ClassCodeUsage {
+        DefinedClassCode::lookup(*self as u8, None, None)
+            .map(|code| code.usage)
+            .unwrap_or(ClassCodeUsage::Both)
+    }
+}
+
+//where a class code is allowed to appear
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ClassCodeUsage {
+    Device,
+    Interface,
+    Both,
+}
+
+//interface association descriptors carry interface level codes
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DescriptorLevel {
+    Device,
+    Interface,
+}
+
+impl ClassCodeUsage {
+    pub fn allows(&self, level: DescriptorLevel) -> bool {
+        matches!(
+            (self, level),
+            (ClassCodeUsage::Both, _)
+                | (ClassCodeUsage::Device, DescriptorLevel::Device)
+                | (ClassCodeUsage::Interface, DescriptorLevel::Interface)
+        )
+    }
+}
+
+//an entry of https://www.usb.org/defined-class-codes, `None` matches any value
+#[derive(Copy, Clone, Debug)]
+pub struct DefinedClassCode {
+    pub class: u8,
+    pub subclass: Option<u8>,
+    pub protocol: Option<u8>,
+    pub usage: ClassCodeUsage,
+    pub name: &'static str,
+}
+
+impl DefinedClassCode {
+    //class only entries come first, so the most specific match is the last one
+    pub fn lookup(
+        class: u8,
+        subclass: Option<u8>,
+        protocol: Option<u8>,
+    ) -> Option<&'static DefinedClassCode> {
+        DEFINED_CLASS_CODES
+            .iter()
+            .filter(|code| code.class == class)
+            .filter(|code| code.subclass.is_none() || code.subclass == subclass)
+            .filter(|code| code.protocol.is_none() || code.protocol == protocol)
+            .max_by_key(|code| (code.subclass.is_some(), code.protocol.is_some()))
+    }
+
+    pub fn class_name(class: u8) -> Option<&'static str> {
+        Self::lookup(class, None, None).map(|code| code.name)
+    }
+
+    pub fn subclass_name(class: u8, subclass: u8) -> Option<&'static str> {
+        DEFINED_CLASS_CODES
+            .iter()
+            .find(|code| {
+                code.class == class && code.subclass == Some(subclass) && code.protocol.is_none()
+            })
+            .map(|code| code.name)
+    }
+
+    pub fn protocol_name(class: u8, subclass: u8, protocol: u8) -> Option<&'static str> {
+        Self::lookup(class, Some(subclass), Some(protocol))
+            .filter(|code| code.protocol.is_some())
+            .map(|code| code.name)
+    }
+
+    pub fn is_valid_at(level: DescriptorLevel, class: u8, subclass: u8, protocol: u8) -> bool {
+        Self::lookup(class, Some(subclass), Some(protocol))
+            .map(|code| code.usage.allows(level))
+            .unwrap_or(true)
+    }
+}
+
+macro_rules! class_code {
+    ($class:expr, $usage:ident, $name:expr) => {
+        DefinedClassCode {
+            class: $class,
+            subclass: None,
+            protocol: None,
+            usage: ClassCodeUsage::$usage,
+            name: $name,
+        }
+    };
+    ($class:expr, $subclass:expr, $usage:ident, $name:expr) => {
+        DefinedClassCode {
+            class: $class,
+            subclass: Some($subclass),
+            protocol: None,
+            usage: ClassCodeUsage::$usage,
+            name: $name,
+        }
+    };
+    ($class:expr, $subclass:expr, $protocol:expr, $usage:ident, $name:expr) => {
+        DefinedClassCode {
+            class: $class,
+            subclass: Some($subclass),
+            protocol: Some($protocol),
+            usage: ClassCodeUsage::$usage,
+            name: $name,
+        }
+    };
+}
+
+#[rustfmt::skip]
+pub static DEFINED_CLASS_CODES: &[DefinedClassCode] = &[
+    class_code!(0x00, Device, "Use class information in the Interface Descriptors"),
+    class_code!(0x01, Interface, "Audio"),
+    class_code!(0x02, Both, "Communications and CDC Control"),
+    class_code!(0x03, Interface, "Human Interface Device"),
+    class_code!(0x03, 0x01, Interface, "Boot Interface Subclass"),
+    class_code!(0x03, 0x01, 0x01, Interface, "Keyboard"),
+    class_code!(0x03, 0x01, 0x02, Interface, "Mouse"),
+    class_code!(0x05, Interface, "Physical"),
+    class_code!(0x06, Interface, "Image"),
+    class_code!(0x06, 0x01, 0x01, Interface, "Still Image Capture Device"),
+    class_code!(0x07, Interface, "Printer"),
+    class_code!(0x08, Interface, "Mass Storage"),
+    //device only in the usb-if table, yet hub interfaces repeat the class (usb 2.0 11.23.1)
+    class_code!(0x09, Both, "Hub"),
+    class_code!(0x09, 0x00, 0x00, Both, "Full speed Hub"),
+    class_code!(0x09, 0x00, 0x01, Both, "Hi-speed hub with single TT"),
+    class_code!(0x09, 0x00, 0x02, Both, "Hi-speed hub with multiple TTs"),
+    class_code!(0x0A, Interface, "CDC-Data"),
+    class_code!(0x0B, Interface, "Smart Card"),
+    class_code!(0x0D, Interface, "Content Security"),
+    class_code!(0x0E, Interface, "Video"),
+    class_code!(0x0E, 0x01, Interface, "Video Control"),
+    class_code!(0x0E, 0x02, Interface, "Video Streaming"),
+    class_code!(0x0E, 0x03, Interface, "Video Interface Collection"),
+    class_code!(0x0F, Interface, "Personal Healthcare"),
+    class_code!(0x10, Interface, "Audio/Video Devices"),
+    class_code!(0x10, 0x01, 0x00, Interface, "AVControl Interface"),
+    class_code!(0x10, 0x02, 0x00, Interface, "AVData Video Streaming Interface"),
+    class_code!(0x10, 0x03, 0x00, Interface, "AVData Audio Streaming Interface"),
+    class_code!(0x11, Device, "Billboard Device"),
+    class_code!(0x11, 0x00, 0x00, Device, "Billboard Device"),
+    class_code!(0x12, Interface, "USB Type-C Bridge"),
+    class_code!(0x12, 0x00, 0x00, Interface, "USB Type-C Bridge"),
+    class_code!(0x13, Interface, "USB Bulk Display Protocol"),
+    class_code!(0x13, 0x00, 0x00, Interface, "USB Bulk Display Protocol"),
+    class_code!(0x14, Interface, "MCTP over USB Protocol Endpoint"),
+    class_code!(0x14, 0x00, 0x01, Interface, "MCTP 1.x Management-controller and Managed-Device endpoints"),
+    class_code!(0x14, 0x00, 0x02, Interface, "MCTP 2.x Management-controller and Managed-Device endpoints"),
+    class_code!(0x3C, Interface, "I3C Device"),
+    class_code!(0x3C, 0x00, 0x00, Interface, "I3C Device"),
+    class_code!(0xDC, Both, "Diagnostic Device"),
+    class_code!(0xDC, 0x01, 0x01, Both, "USB2 Compliance Device"),
+    class_code!(0xDC, 0x02, 0x00, Both, "Debug Target vendor defined"),
+    class_code!(0xDC, 0x02, 0x01, Both, "GNU Remote Debug Command Set"),
+    class_code!(0xDC, 0x03, 0x00, Both, "Undefined"),
+    class_code!(0xDC, 0x03, 0x01, Both, "Vendor defined Trace protocol on DbC"),
+    class_code!(0xDC, 0x04, 0x00, Both, "Undefined"),
+    class_code!(0xDC, 0x04, 0x01, Both, "Vendor defined Dfx protocol on DbC"),
+    class_code!(0xDC, 0x05, 0x00, Both, "Vendor defined Trace protocol over General Purpose (GP) endpoint on DvC"),
+    class_code!(0xDC, 0x05, 0x01, Both, "GNU Protocol protocol over General Purpose (GP) endpoint on DvC"),
+    class_code!(0xDC, 0x06, 0x00, Both, "Undefined"),
+    class_code!(0xDC, 0x06, 0x01, Both, "Vendor defined Dfx protocol on DvC"),
+    class_code!(0xDC, 0x07, 0x00, Both, "Undefined"),
+    class_code!(0xDC, 0x07, 0x01, Both, "Vendor defined Trace protocol on DvC"),
+    class_code!(0xDC, 0x08, 0x00, Both, "Undefined"),
+    class_code!(0xE0, Interface, "Wireless Controller"),
+    class_code!(0xE0, 0x01, 0x01, Interface, "Bluetooth Programming Interface"),
+    class_code!(0xE0, 0x01, 0x02, Interface, "UWB Radio Control Interface"),
+    class_code!(0xE0, 0x01, 0x03, Interface, "Remote NDIS"),
+    class_code!(0xE0, 0x01, 0x04, Interface, "Bluetooth AMP Controller"),
+    class_code!(0xE0, 0x02, 0x01, Interface, "Host Wire Adapter Control/Data interface"),
+    class_code!(0xE0, 0x02, 0x02, Interface, "Device Wire Adapter Control/Data interface"),
+    class_code!(0xE0, 0x02, 0x03, Interface, "Device Wire Adapter Isochronous interface"),
+    class_code!(0xEF, Both, "Miscellaneous"),
+    class_code!(0xEF, 0x01, 0x01, Both, "Active Sync device"),
+    class_code!(0xEF, 0x01, 0x02, Both, "Palm Sync"),
+    class_code!(0xEF, 0x02, 0x01, Both, "Interface Association Descriptor"),
+    class_code!(0xEF, 0x02, 0x02, Both, "Wire Adapter Multifunction Peripheral programming interface"),
+    class_code!(0xEF, 0x03, 0x01, Both, "Cable Based Association Framework"),
+    class_code!(0xEF, 0x04, 0x01, Both, "RNDIS over Ethernet"),
+    class_code!(0xEF, 0x04, 0x02, Both, "RNDIS over WiFi"),
+    class_code!(0xEF, 0x04, 0x03, Both, "RNDIS over WiMAX"),
+    class_code!(0xEF, 0x04, 0x04, Both, "RNDIS over WWAN"),
+    class_code!(0xEF, 0x04, 0x05, Both, "RNDIS for Raw IPv4"),
+    class_code!(0xEF, 0x04, 0x06, Both, "RNDIS for Raw IPv6"),
+    class_code!(0xEF, 0x04, 0x07, Both, "RNDIS for GPRS"),
+    class_code!(0xEF, 0x05, 0x00, Both, "USB3 Vision Control Interface"),
+    class_code!(0xEF, 0x05, 0x01, Both, "USB3 Vision Event Interface"),
+    class_code!(0xEF, 0x05, 0x02, Both, "USB3 Vision Streaming Interface"),
+    class_code!(0xEF, 0x06, 0x01, Both, "STEP. Stream Transport Efficient Protocol"),
+    class_code!(0xEF, 0x06, 0x02, Both, "STEP RAW. Stream Transport Efficient Protocol"),
+    class_code!(0xEF, 0x07, 0x00, Both, "Command Interface in IAD"),
+    class_code!(0xEF, 0x07, 0x01, Both, "Command Interface in Interface Descriptor"),
+    class_code!(0xEF, 0x07, 0x02, Both, "Media Interface in Interface Descriptor"),
+    class_code!(0xFE, Interface, "Application Specific"),
+    class_code!(0xFE, 0x01, 0x01, Interface, "Device Firmware Upgrade"),
+    class_code!(0xFE, 0x02, 0x00, Interface, "IRDA Bridge device"),
+    class_code!(0xFE, 0x03, 0x00, Interface, "USB Test and Measurement Device"),
+    class_code!(0xFE, 0x03, 0x01, Interface, "USB Test and Measurement Device conforming to the USBTMC USB488 Subclass"),
+    class_code!(0xFF, Both, "Vendor Specific"),
+];
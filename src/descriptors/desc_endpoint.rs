@@ -1,4 +1,5 @@
 use bit_field::BitField;
+use log::warn;
 use num_derive::FromPrimitive;
 use num_traits::FromPrimitive;
 
@@ -25,7 +26,41 @@ pub struct SuperSpeedCmp {
     pub bytes_per_interval: u16,
 }
 
+impl SuperSpeedCmp {
+    //usb 3.2 9.6.7, follows every endpoint descriptor of a superspeed device
+    pub fn from_u8_array(raw: &[u8]) -> Self {
+        Self {
+            kind: raw[1],
+            max_burst: raw[2],
+            attributes: raw[3],
+            bytes_per_interval: u16::from_le_bytes([raw[4], raw[5]]),
+        }
+    }
+}
+
 impl Endpoint {
+    //audio class endpoints append bRefresh and bSynchAddress, they are not kept
+    pub fn from_u8_array(raw: &[u8]) -> Self {
+        Self {
+            len: raw[0],
+            descriptor_type: raw[1],
+            endpoint_address: raw[2],
+            attributes: raw[3],
+            max_packet_size: u16::from_le_bytes([raw[4], raw[5]]),
+            interval: raw[6],
+            //the parser attaches the companion descriptor that follows, if any
+            ssc: None,
+        }
+    }
+
+    pub fn length(&self) -> u8 {
+        self.len
+    }
+
+    pub fn descriptor_type(&self) -> u8 {
+        self.descriptor_type
+    }
+
     pub fn endpoint_type(&self) -> EndpointType {
         EndpointType::from_u8(if self.attributes == 0 {
             4
@@ -41,15 +76,79 @@ impl Endpoint {
     }
 
     pub fn calc_actual_interval(&self, port_speed: PortSpeed) -> u8 {
-        if let PortSpeed::FullSpeed | PortSpeed::LowSpeed = port_speed {
-            if let EndpointType::IsochOut | EndpointType::IsochIn = self.endpoint_type() {
-                self.interval + 2
-            } else {
-                self.interval + 3
+        self.service_interval(port_speed).xhci_interval
+    }
+
+    //refer usb 2.0 9.6.6 and xhci 6.2.3.6
+    pub fn service_interval(&self, port_speed: PortSpeed) -> EndpointInterval {
+        let interval = self.interval;
+        match (port_speed, self.endpoint_type()) {
+            (
+                PortSpeed::FullSpeed | PortSpeed::LowSpeed,
+                EndpointType::InterruptIn | EndpointType::InterruptOut,
+            ) => {
+                //bInterval counts frames directly, xhci rounds it down to a power of two
+                let frames = self.clamp_interval(interval, 1, 255) as u32;
+                EndpointInterval::new(frames * 8, (frames * 8).ilog2().clamp(3, 10) as u8)
             }
-        } else {
-            self.interval - 1
+            (_, EndpointType::IsochIn | EndpointType::IsochOut)
+            | (
+                PortSpeed::HighSpeed | PortSpeed::SuperSpeed | PortSpeed::SuperSpeedPlus,
+                EndpointType::InterruptIn | EndpointType::InterruptOut,
+            ) => {
+                //exponential encoding, 2^(bInterval-1) frames on full speed, microframes otherwise
+                let exponent = self.clamp_interval(interval, 1, 16) - 1;
+                let (exponent, xhci_interval) = match port_speed {
+                    PortSpeed::FullSpeed | PortSpeed::LowSpeed => {
+                        //the xhci Interval field is 4 bits, bInterval 14..=16 would need more
+                        let exponent = exponent + 3;
+                        let xhci_interval = exponent.min(15);
+                        if xhci_interval != exponent {
+                            let address = self.endpoint_address;
+                            warn!(
+                                "endpoint {:#x}: xhci interval {} exceeds 15, clamped to {}",
+                                address, exponent, xhci_interval
+                            );
+                        }
+                        (exponent, xhci_interval)
+                    }
+                    _ => (exponent, exponent),
+                };
+                EndpointInterval::new(1 << exponent, xhci_interval)
+            }
+            (PortSpeed::HighSpeed, EndpointType::BulkOut | EndpointType::Control)
+                if interval != 0 =>
+            {
+                //bInterval is the maximum nak rate here, 0 means never nak. usb 2.0 9.6.6 only
+                //gives it that meaning for bulk OUT and control, bulk IN ignores it like full speed
+                EndpointInterval::new(interval as u32, interval.ilog2().min(15) as u8)
+            }
+            _ => EndpointInterval::new(0, 0),
+        }
+    }
+
+    fn clamp_interval(&self, interval: u8, min: u8, max: u8) -> u8 {
+        let clamped = interval.clamp(min, max);
+        if clamped != interval {
+            let address = self.endpoint_address;
+            warn!(
+                "endpoint {:#x}: bInterval {} out of range {}..={}, clamped to {}",
+                address, interval, min, max, clamped
+            );
+        }
+        clamped
+    }
+
+    //bytes a periodic endpoint moves per service interval: wBytesPerInterval of the superspeed
+    //companion (usb 3.2 9.6.7), otherwise packet size times transactions (usb 2.0 9.6.6)
+    pub fn max_bytes_per_interval(&self) -> u32 {
+        if let Some(ssc) = self.ssc {
+            let bytes_per_interval = ssc.bytes_per_interval;
+            return bytes_per_interval as u32;
         }
+        let max_packet_size = self.max_packet_size;
+        let transactions = max_packet_size.get_bits(11..=12) as u32 + 1;
+        max_packet_size.get_bits(0..=10) as u32 * transactions
     }
 
     pub fn max_streams(&self) -> Option<u8> {
@@ -76,7 +175,7 @@ impl Endpoint {
                     0
                 }
             })
-            .unwrap()
+            .unwrap_or(0)
     }
 
     pub fn is_superspeedplus(&self) -> bool {
@@ -103,6 +202,27 @@ impl Endpoint {
     }
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct EndpointInterval {
+    /// Service interval in 125us microframes, 0 for endpoints that are not periodic.
+    pub microframes: u32,
+    pub nanos: u64,
+    /// Value for the Interval field of the xHCI endpoint context, the period is 2^n microframes.
+    pub xhci_interval: u8,
+}
+
+impl EndpointInterval {
+    const MICROFRAME_NANOS: u64 = 125_000;
+
+    fn new(microframes: u32, xhci_interval: u8) -> Self {
+        Self {
+            microframes,
+            nanos: microframes as u64 * Self::MICROFRAME_NANOS,
+            xhci_interval,
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash, FromPrimitive)]
 pub enum EndpointType {
     /// Not Valid.
@@ -1,15 +1,188 @@
+use alloc::vec::Vec;
 use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
 
-#[derive(Copy, Clone, Debug, Default)]
-#[repr(C, packed)]
+use super::{desc_device::BcdVersion, parser::Error};
+
+#[derive(Clone, Debug, Default)]
 pub struct Hid {
     pub len: u8,
     pub descriptor_type: u8,
-    pub hid_bcd: u16,
+    pub hid_bcd: BcdVersion,
     pub country_code: u8,
     pub num_descriptions: u8,
-    pub report_descriptor_type: u8, // actually, these two entry is a variant length vector, but we only pick first entry!
-    pub report_descriptor_len: u16, //
+    //bNumDescriptors (bDescriptorType, wDescriptorLength) pairs
+    pub descriptors: Vec<HidClassDescriptor>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct HidClassDescriptor {
+    pub descriptor_type: u8,
+    pub length: u16,
+}
+
+impl HidClassDescriptor {
+    pub fn ty(&self) -> Option<HIDDescriptorTypes> {
+        HIDDescriptorTypes::from_u8(self.descriptor_type)
+    }
+}
+
+//HID 1.11 7.1.1 Get_Descriptor: wValue = type << 8 | index, wIndex = interface number
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct HidDescriptorRequest {
+    pub descriptor_type: u8,
+    pub index: u8,
+    pub length: u16,
+}
+
+impl HidDescriptorRequest {
+    pub const REQUEST_TYPE: u8 = 0x81;
+    pub const GET_DESCRIPTOR: u8 = 0x06;
+
+    pub fn value(&self) -> u16 {
+        ((self.descriptor_type as u16) << 8) | self.index as u16
+    }
+}
+
+impl Hid {
+    pub fn from_u8_array(raw: &[u8]) -> Result<Self, Error> {
+        //bLength covers the fixed 6 bytes and must fit in what was read
+        if raw.len() < 6 || (raw[0] as usize) < 6 || raw[0] as usize > raw.len() {
+            return Err(Error::ShortDescriptor(
+                HIDDescriptorTypes::Hid as u8,
+                raw.len(),
+            ));
+        }
+        let num_descriptions = raw[5];
+        Ok(Self {
+            len: raw[0],
+            descriptor_type: raw[1],
+            hid_bcd: u16::from_le_bytes([raw[2], raw[3]]).into(),
+            country_code: raw[4],
+            num_descriptions,
+            //stop at bLength rather than trusting bNumDescriptors
+            descriptors: raw[6..raw[0] as usize]
+                .chunks_exact(3)
+                .take(num_descriptions as usize)
+                .map(|entry| HidClassDescriptor {
+                    descriptor_type: entry[0],
+                    length: u16::from_le_bytes([entry[1], entry[2]]),
+                })
+                .collect(),
+        })
+    }
+
+    //what a device (or gadget) with a single report descriptor announces
+    pub fn new(hid_bcd: BcdVersion, country_code: u8, report_descriptor_len: u16) -> Self {
+        let mut hid = Self {
+            descriptor_type: HIDDescriptorTypes::Hid as u8,
+            hid_bcd,
+            country_code,
+            ..Default::default()
+        };
+        hid.set_report_descriptor_len(report_descriptor_len);
+        hid
+    }
+
+    //updates the first report descriptor entry, adding one if there is none
+    pub fn set_report_descriptor_len(&mut self, len: u16) {
+        match self
+            .descriptors
+            .iter_mut()
+            .find(|desc| desc.ty() == Some(HIDDescriptorTypes::HIDReport))
+        {
+            Some(desc) => desc.length = len,
+            None => self.descriptors.push(HidClassDescriptor {
+                descriptor_type: HIDDescriptorTypes::HIDReport as u8,
+                length: len,
+            }),
+        }
+        self.num_descriptions = self.descriptors.len() as u8;
+        self.len = 6 + 3 * self.num_descriptions;
+    }
+
+    pub fn to_u8_array(&self) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(self.len as usize);
+        raw.extend_from_slice(&[self.len, self.descriptor_type]);
+        raw.extend_from_slice(&self.hid_bcd.raw().to_le_bytes());
+        raw.extend_from_slice(&[self.country_code, self.num_descriptions]);
+        for desc in &self.descriptors {
+            raw.push(desc.descriptor_type);
+            raw.extend_from_slice(&desc.length.to_le_bytes());
+        }
+        raw
+    }
+
+    pub fn report_descriptors(&self) -> impl Iterator<Item = &HidClassDescriptor> {
+        self.descriptors
+            .iter()
+            .filter(|desc| desc.ty() == Some(HIDDescriptorTypes::HIDReport))
+    }
+
+    //length of the first report descriptor, the only one most devices have
+    pub fn report_descriptor_len(&self) -> Option<u16> {
+        self.report_descriptors().next().map(|desc| desc.length)
+    }
+
+    //descriptors of the same type are numbered in the order they are listed
+    pub fn descriptor_requests(&self) -> Vec<HidDescriptorRequest> {
+        let mut requests: Vec<HidDescriptorRequest> = Vec::new();
+        for desc in &self.descriptors {
+            let index = requests
+                .iter()
+                .filter(|request| request.descriptor_type == desc.descriptor_type)
+                .count();
+            requests.push(HidDescriptorRequest {
+                descriptor_type: desc.descriptor_type,
+                index: index as u8,
+                length: desc.length,
+            });
+        }
+        requests
+    }
+
+    //HID 1.11 6.2.1, names as listed in usb.ids
+    pub fn country_name(&self) -> Option<&'static str> {
+        const COUNTRIES: [&str; 36] = [
+            "Not supported",
+            "Arabic",
+            "Belgian",
+            "Canadian-Bilingual",
+            "Canadian-French",
+            "Czech Republic",
+            "Danish",
+            "Finnish",
+            "French",
+            "German",
+            "Greek",
+            "Hebrew",
+            "Hungary",
+            "International (ISO)",
+            "Italian",
+            "Japan (Katakana)",
+            "Korean",
+            "Latin American",
+            "Netherlands/Dutch",
+            "Norwegian",
+            "Persian (Farsi)",
+            "Poland",
+            "Portuguese",
+            "Russia",
+            "Slovakia",
+            "Spanish",
+            "Swedish",
+            "Switzerland/French",
+            "Switzerland/German",
+            "Switzerland",
+            "Taiwan",
+            "Turkish-Q",
+            "UK",
+            "US",
+            "Yugoslavia",
+            "Turkish-F",
+        ];
+        COUNTRIES.get(self.country_code as usize).copied()
+    }
 }
 
 #[derive(FromPrimitive, Copy, Clone, Debug)]
@@ -0,0 +1,240 @@
+//HID 1.11 6.2.3 physical descriptors, fetched with GET_DESCRIPTOR like the report descriptor
+use alloc::vec::Vec;
+
+use bit_field::BitField;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::{
+    desc_hid::{HIDDescriptorTypes, HidDescriptorRequest},
+    desc_report::ReportField,
+    parser::Error,
+};
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Designator {
+    None = 0x00,
+    Hand = 0x01,
+    Eyeball = 0x02,
+    Eyebrow = 0x03,
+    Eyelid = 0x04,
+    Ear = 0x05,
+    Nose = 0x06,
+    Mouth = 0x07,
+    UpperLip = 0x08,
+    LowerLip = 0x09,
+    Jaw = 0x0A,
+    Neck = 0x0B,
+    UpperArm = 0x0C,
+    Elbow = 0x0D,
+    Forearm = 0x0E,
+    Wrist = 0x0F,
+    Palm = 0x10,
+    Thumb = 0x11,
+    IndexFinger = 0x12,
+    MiddleFinger = 0x13,
+    RingFinger = 0x14,
+    LittleFinger = 0x15,
+    Head = 0x16,
+    Shoulder = 0x17,
+    Hip = 0x18,
+    Waist = 0x19,
+    Thigh = 0x1A,
+    Knee = 0x1B,
+    Calf = 0x1C,
+    Ankle = 0x1D,
+    Foot = 0x1E,
+    Heel = 0x1F,
+    BallOfFoot = 0x20,
+    BigToe = 0x21,
+    SecondToe = 0x22,
+    ThirdToe = 0x23,
+    FourthToe = 0x24,
+    LittleToe = 0x25,
+    Brow = 0x26,
+    Cheek = 0x27,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Qualifier {
+    None = 0,
+    Right = 1,
+    Left = 2,
+    Both = 3,
+    Either = 4,
+    Center = 5,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum Bias {
+    NotApplicable = 0,
+    RightHand = 1,
+    LeftHand = 2,
+    BothHands = 3,
+    EitherHand = 4,
+}
+
+//descriptor set 0: how many sets follow and how long each one is
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PhysicalDescriptorSet0 {
+    pub number: u8,
+    pub length: u16,
+}
+
+impl PhysicalDescriptorSet0 {
+    pub const LENGTH: u16 = 3;
+
+    pub fn from_u8_array(raw: &[u8]) -> Result<Self, Error> {
+        match raw {
+            [number, low, high, ..] => Ok(Self {
+                number: *number,
+                length: u16::from_le_bytes([*low, *high]),
+            }),
+            _ => Err(Error::ShortDescriptor(
+                HIDDescriptorTypes::HIDPhysical as u8,
+                raw.len(),
+            )),
+        }
+    }
+
+    //the request that fetches set 0 itself
+    pub fn request() -> HidDescriptorRequest {
+        HidDescriptorRequest {
+            descriptor_type: HIDDescriptorTypes::HIDPhysical as u8,
+            index: 0,
+            length: Self::LENGTH,
+        }
+    }
+
+    //sets are numbered from 1
+    pub fn set_requests(&self) -> impl Iterator<Item = HidDescriptorRequest> + '_ {
+        (1..=self.number).map(|index| HidDescriptorRequest {
+            descriptor_type: HIDDescriptorTypes::HIDPhysical as u8,
+            index,
+            length: self.length,
+        })
+    }
+}
+
+//one dwPhysical entry
+#[derive(Copy, Clone, Debug, Default)]
+pub struct PhysicalDescriptor {
+    pub designator: u8,
+    pub flags: u8,
+}
+
+impl PhysicalDescriptor {
+    pub fn designator(&self) -> Option<Designator> {
+        Designator::from_u8(self.designator)
+    }
+
+    pub fn qualifier(&self) -> Option<Qualifier> {
+        Qualifier::from_u8(self.flags.get_bits(5..=7))
+    }
+
+    //0 means the control is not easy to reach, 31 means very easy
+    pub fn effort(&self) -> u8 {
+        self.flags.get_bits(0..=4)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PhysicalDescriptorSet {
+    pub physical_info: u8,
+    pub descriptors: Vec<PhysicalDescriptor>,
+}
+
+impl PhysicalDescriptorSet {
+    //a trailing odd byte is not a whole entry and is dropped
+    pub fn from_u8_array(raw: &[u8]) -> Result<Self, Error> {
+        let Some((&physical_info, entries)) = raw.split_first() else {
+            return Err(Error::ShortDescriptor(
+                HIDDescriptorTypes::HIDPhysical as u8,
+                0,
+            ));
+        };
+        Ok(Self {
+            physical_info,
+            descriptors: entries
+                .chunks_exact(2)
+                .map(|entry| PhysicalDescriptor {
+                    designator: entry[0],
+                    flags: entry[1],
+                })
+                .collect(),
+        })
+    }
+
+    pub fn bias(&self) -> Option<Bias> {
+        Bias::from_u8(self.physical_info.get_bits(5..=7))
+    }
+
+    //0 is the most preferred set
+    pub fn preference(&self) -> u8 {
+        self.physical_info.get_bits(0..=4)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct PhysicalDescriptors {
+    pub set0: PhysicalDescriptorSet0,
+    pub sets: Vec<PhysicalDescriptorSet>,
+}
+
+impl PhysicalDescriptors {
+    //`sets` holds the raw sets 1..=bNumber in request order
+    pub fn new(set0: &[u8], sets: &[&[u8]]) -> Result<Self, Error> {
+        Ok(Self {
+            set0: PhysicalDescriptorSet0::from_u8_array(set0)?,
+            sets: sets
+                .iter()
+                .map(|raw| PhysicalDescriptorSet::from_u8_array(raw))
+                .collect::<Result<_, _>>()?,
+        })
+    }
+
+    //most preferred set, optionally restricted to one hand
+    pub fn preferred_set(&self, bias: Option<Bias>) -> Option<&PhysicalDescriptorSet> {
+        self.sets
+            .iter()
+            .filter(|set| bias.is_none() || set.bias() == bias)
+            .min_by_key(|set| set.preference())
+    }
+
+    //designator indices count the entries of a set from zero
+    pub fn designator(
+        &self,
+        designator_index: u32,
+        bias: Option<Bias>,
+    ) -> Option<&PhysicalDescriptor> {
+        self.preferred_set(bias)?
+            .descriptors
+            .get(designator_index as usize)
+    }
+
+    //physical descriptor of the nth element of a report field
+    pub fn for_field(
+        &self,
+        field: &ReportField,
+        element: usize,
+        bias: Option<Bias>,
+    ) -> Option<&PhysicalDescriptor> {
+        self.designator(field.designator_index(element)?, bias)
+    }
+}
+
+impl ReportField {
+    //Designator Index applies to every element, a Designator Minimum/Maximum range is spread
+    //over them like a usage range and the last designator repeats
+    pub fn designator_index(&self, element: usize) -> Option<u32> {
+        let local = &self.local_state;
+        if let Some(index) = local.designator_index {
+            return Some(index);
+        }
+        let (minimum, maximum) = (local.designator_minimum?, local.designator_maximum?);
+        Some(minimum.saturating_add(element as u32).min(maximum))
+    }
+}
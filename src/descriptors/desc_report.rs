@@ -1,4 +1,966 @@
+//HID 1.11 6.2.2 report descriptor parsing
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    vec,
+    vec::Vec,
+};
 
+use bit_field::BitField;
+use log::{trace, warn};
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::hid_usage::Usage;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReportDescriptorError {
+    UnexpectedEnd(usize),
+    ReportIdZero(usize),
+    PopWithoutPush(usize),
+    UnbalancedEndCollection(usize),
+    UnclosedCollection,
+    NestedDelimiter(usize),
+    UnbalancedDelimiter(usize),
+    ReportTooLarge(usize),
+    UnknownReportId(u8),
+    ShortReport(usize),
+    UndeclaredUsage(u32, u32),
+    ArrayOverflow(u32, u32),
+    ValueOutOfRange(usize),
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum ItemType {
+    Main = 0,
+    Global = 1,
+    Local = 2,
+    Reserved = 3,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum MainItemTag {
+    Input = 0x8,
+    Output = 0x9,
+    Collection = 0xA,
+    Feature = 0xB,
+    EndCollection = 0xC,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum GlobalItemTag {
+    UsagePage = 0x0,
+    LogicalMinimum = 0x1,
+    LogicalMaximum = 0x2,
+    PhysicalMinimum = 0x3,
+    PhysicalMaximum = 0x4,
+    UnitExponent = 0x5,
+    Unit = 0x6,
+    ReportSize = 0x7,
+    ReportId = 0x8,
+    ReportCount = 0x9,
+    Push = 0xA,
+    Pop = 0xB,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum LocalItemTag {
+    Usage = 0x0,
+    UsageMinimum = 0x1,
+    UsageMaximum = 0x2,
+    DesignatorIndex = 0x3,
+    DesignatorMinimum = 0x4,
+    DesignatorMaximum = 0x5,
+    StringIndex = 0x7,
+    StringMinimum = 0x8,
+    StringMaximum = 0x9,
+    Delimiter = 0xA,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum ItemTag {
+    Main(MainItemTag),
+    Global(GlobalItemTag),
+    Local(LocalItemTag),
+    //tags reserved by the spec, kept so the descriptor can still be dumped
+    Reserved(ItemType, u8),
+}
+
+#[derive(Clone, Debug)]
+pub enum Item {
+    Short { tag: ItemTag, size: u8, data: u32 },
+    //no long item tags are defined by HID 1.11, the payload is kept as is
+    Long { tag: u8, data: Vec<u8> },
+}
+
+#[derive(Clone, Debug)]
+pub struct ReportItem {
+    //position and total length (prefix included) inside the raw descriptor
+    pub offset: usize,
+    pub len: usize,
+    pub item: Item,
+}
+
+impl Item {
+    pub fn unsigned(&self) -> u32 {
+        match self {
+            Item::Short { data, .. } => *data,
+            Item::Long { .. } => 0,
+        }
+    }
+
+    //short item data is little endian and sign extended from its own size
+    pub fn signed(&self) -> i32 {
+        match self {
+            Item::Short { size: 1, data, .. } => *data as u8 as i8 as i32,
+            Item::Short { size: 2, data, .. } => *data as u16 as i16 as i32,
+            Item::Short { data, .. } => *data as i32,
+            Item::Long { .. } => 0,
+        }
+    }
+}
+
+impl Item {
+    //a 4 bit two's complement nibble, some devices send a plain signed byte instead
+    pub fn unit_exponent(&self) -> i32 {
+        let (unsigned, signed) = (self.unsigned(), self.signed());
+        if (-8..8).contains(&signed) && unsigned > 7 {
+            signed
+        } else if unsigned <= 0xF {
+            ((unsigned as i32) << 28) >> 28
+        } else {
+            signed
+        }
+    }
+}
+
+impl ReportItem {
+    pub fn parse_all(raw: &[u8]) -> Result<Vec<ReportItem>, ReportDescriptorError> {
+        let mut items = Vec::new();
+        let mut offset = 0;
+        while offset < raw.len() {
+            let item = Self::parse(raw, offset)?;
+            offset += item.len;
+            items.push(item);
+        }
+        Ok(items)
+    }
+
+    fn parse(raw: &[u8], offset: usize) -> Result<ReportItem, ReportDescriptorError> {
+        let prefix = raw[offset];
+
+        if prefix == 0xFE {
+            let header = raw
+                .get(offset + 1..offset + 3)
+                .ok_or(ReportDescriptorError::UnexpectedEnd(offset))?;
+            let (size, tag) = (header[0] as usize, header[1]);
+            let data = raw
+                .get(offset + 3..offset + 3 + size)
+                .ok_or(ReportDescriptorError::UnexpectedEnd(offset))?;
+            return Ok(ReportItem {
+                offset,
+                len: 3 + size,
+                item: Item::Long {
+                    tag,
+                    data: data.to_vec(),
+                },
+            });
+        }
+
+        let size = match prefix.get_bits(0..=1) {
+            3 => 4,
+            other => other,
+        };
+        let item_type = ItemType::from_u8(prefix.get_bits(2..=3)).unwrap();
+        let tag_bits = prefix.get_bits(4..=7);
+        let data = raw
+            .get(offset + 1..offset + 1 + size as usize)
+            .ok_or(ReportDescriptorError::UnexpectedEnd(offset))?
+            .iter()
+            .rev()
+            .fold(0u32, |acc, b| (acc << 8) | *b as u32);
+
+        let tag = match item_type {
+            ItemType::Main => MainItemTag::from_u8(tag_bits).map(ItemTag::Main),
+            ItemType::Global => GlobalItemTag::from_u8(tag_bits).map(ItemTag::Global),
+            ItemType::Local => LocalItemTag::from_u8(tag_bits).map(ItemTag::Local),
+            ItemType::Reserved => None,
+        }
+        .unwrap_or(ItemTag::Reserved(item_type, tag_bits));
+
+        Ok(ReportItem {
+            offset,
+            len: 1 + size as usize,
+            item: Item::Short { tag, size, data },
+        })
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GlobalItemsState {
+    pub usage_page: u32,
+    pub logical_minimum: i32,
+    pub logical_maximum: i32,
+    pub physical_minimum: i32,
+    pub physical_maximum: i32,
+    pub unit_exponent: i32,
+    pub unit: u32,
+    pub report_size: u32,
+    pub report_id: Option<u8>,
+    pub report_count: u32,
+}
+
+//an inclusive range of extended usages (page << 16 | id), a single usage has min == max
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct UsageRange {
+    pub min: u32,
+    pub max: u32,
+}
+
+impl UsageRange {
+    pub fn len(&self) -> u32 {
+        self.max.saturating_sub(self.min) + 1
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.max < self.min
+    }
+
+    pub fn contains(&self, usage: u32) -> bool {
+        (self.min..=self.max).contains(&usage)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct LocalItemsState {
+    pub usages: Vec<UsageRange>,
+    pub designator_index: Option<u32>,
+    pub designator_minimum: Option<u32>,
+    pub designator_maximum: Option<u32>,
+    pub string_index: Option<u32>,
+    pub string_minimum: Option<u32>,
+    pub string_maximum: Option<u32>,
+    usage_minimum: Option<(u32, bool)>,
+    usage_maximum: Option<(u32, bool)>,
+    //usages declared with a 1 or 2 byte item, their page is filled in by the main item
+    short_usages: BTreeSet<usize>,
+    delimiter_open: bool,
+    delimiter_branch: u32,
+}
+
+impl LocalItemsState {
+    //usage for the nth element of a variable field, the last usage repeats for the rest
+    pub fn usage(&self, index: usize) -> Option<u32> {
+        let mut remaining = index as u32;
+        for range in &self.usages {
+            if remaining < range.len() {
+                return Some(range.min + remaining);
+            }
+            remaining -= range.len();
+        }
+        self.usages.last().map(|range| range.max)
+    }
+
+    pub fn usage_count(&self) -> u32 {
+        self.usages.iter().map(|range| range.len()).sum()
+    }
+
+    pub fn contains(&self, usage: u32) -> bool {
+        self.usages.iter().any(|range| range.contains(usage))
+    }
+
+    //only the first alternative of a delimiter set is used
+    fn skip_alternative(&mut self) -> bool {
+        if !self.delimiter_open {
+            return false;
+        }
+        self.delimiter_branch += 1;
+        self.delimiter_branch > 1
+    }
+
+    fn push_usage(&mut self, data: u32, extended: bool) {
+        if self.skip_alternative() {
+            return;
+        }
+        if !extended {
+            self.short_usages.insert(self.usages.len());
+        }
+        self.usages.push(UsageRange {
+            min: data,
+            max: data,
+        });
+    }
+
+    fn try_push_usage_range(&mut self) {
+        if let (Some((min, min_extended)), Some((max, max_extended))) =
+            (self.usage_minimum, self.usage_maximum)
+        {
+            self.usage_minimum = None;
+            self.usage_maximum = None;
+            if self.skip_alternative() {
+                return;
+            }
+            if !(min_extended || max_extended) {
+                self.short_usages.insert(self.usages.len());
+            }
+            self.usages.push(UsageRange { min, max });
+        }
+    }
+
+    //HID 1.11 6.2.2.8: the usage page in effect at the main item completes short usages
+    fn resolve(mut self, usage_page: u32) -> Self {
+        for index in core::mem::take(&mut self.short_usages) {
+            let range = &mut self.usages[index];
+            range.min = (usage_page << 16) | (range.min & 0xffff);
+            range.max = (usage_page << 16) | (range.max & 0xffff);
+        }
+        self
+    }
+}
+
+//data bits of Input, Output and Feature items
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MainItemFlags(pub u32);
+
+impl MainItemFlags {
+    pub fn is_constant(&self) -> bool {
+        self.0.get_bit(0)
+    }
+
+    pub fn is_variable(&self) -> bool {
+        self.0.get_bit(1)
+    }
+
+    pub fn is_array(&self) -> bool {
+        !self.is_variable()
+    }
+
+    pub fn is_relative(&self) -> bool {
+        self.0.get_bit(2)
+    }
+
+    pub fn is_wrap(&self) -> bool {
+        self.0.get_bit(3)
+    }
+
+    pub fn is_nonlinear(&self) -> bool {
+        self.0.get_bit(4)
+    }
+
+    pub fn has_no_preferred_state(&self) -> bool {
+        self.0.get_bit(5)
+    }
+
+    pub fn has_null_state(&self) -> bool {
+        self.0.get_bit(6)
+    }
+
+    //reserved for input items
+    pub fn is_volatile(&self) -> bool {
+        self.0.get_bit(7)
+    }
+
+    pub fn is_buffered_bytes(&self) -> bool {
+        self.0.get_bit(8)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ReportType {
+    Input,
+    Output,
+    Feature,
+}
+
+impl ReportType {
+    //wValue high byte of GET_REPORT/SET_REPORT
+    pub fn request_value(&self) -> u8 {
+        match self {
+            ReportType::Input => 1,
+            ReportType::Output => 2,
+            ReportType::Feature => 3,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CollectionType {
+    Physical,
+    Application,
+    Logical,
+    Report,
+    NamedArray,
+    UsageSwitch,
+    UsageModifier,
+    Reserved(u8),
+    VendorDefined(u8),
+}
+
+impl From<u32> for CollectionType {
+    fn from(value: u32) -> Self {
+        match value as u8 {
+            0x00 => CollectionType::Physical,
+            0x01 => CollectionType::Application,
+            0x02 => CollectionType::Logical,
+            0x03 => CollectionType::Report,
+            0x04 => CollectionType::NamedArray,
+            0x05 => CollectionType::UsageSwitch,
+            0x06 => CollectionType::UsageModifier,
+            vendor @ 0x80..=0xFF => CollectionType::VendorDefined(vendor),
+            reserved => CollectionType::Reserved(reserved),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Collection {
+    pub collection_type: CollectionType,
+    pub usage: u32,
+    pub parent: Option<usize>,
+    pub children: Vec<usize>,
+    //(index into `ReportDescriptor::reports`, index into `Report::fields`)
+    pub fields: Vec<(usize, usize)>,
+}
+
+//one Input/Output/Feature main item
+#[derive(Clone, Debug)]
+pub struct ReportField {
+    pub report_type: ReportType,
+    //offset inside the report, not counting the report id byte
+    pub bit_offset: usize,
+    pub bit_length: usize,
+    pub global_state: GlobalItemsState,
+    pub local_state: LocalItemsState,
+    pub flags: MainItemFlags,
+    pub collection: Option<usize>,
+}
+
+impl ReportField {
+    pub fn report_size(&self) -> usize {
+        self.global_state.report_size as usize
+    }
+
+    pub fn report_count(&self) -> usize {
+        self.global_state.report_count as usize
+    }
+
+    pub fn report_id(&self) -> Option<u8> {
+        self.global_state.report_id
+    }
+
+    pub fn usage(&self, index: usize) -> Option<u32> {
+        self.local_state.usage(index)
+    }
+
+    //padding, constant fields without usages
+    pub fn is_padding(&self) -> bool {
+        self.flags.is_constant() && self.local_state.usages.is_empty()
+    }
+
+    pub fn is_signed(&self) -> bool {
+        self.global_state.logical_minimum < 0
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Report {
+    pub report_type: ReportType,
+    pub report_id: Option<u8>,
+    pub bit_length: usize,
+    pub fields: Vec<ReportField>,
+}
+
+impl Report {
+    //size on the wire, the report id prefix included
+    pub fn byte_length(&self) -> usize {
+        self.bit_length.div_ceil(8) + self.report_id.is_some() as usize
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReportDescriptor {
+    pub raw: Vec<u8>,
+    pub items: Vec<ReportItem>,
+    pub collections: Vec<Collection>,
+    pub reports: Vec<Report>,
+}
+
+//HID 1.11 5.6: reports longer than this are not allowed on low/full speed devices, keep it sane
+const MAX_REPORT_BITS: usize = 0xFFFF * 8;
+
+impl ReportDescriptor {
+    pub fn parse(raw: &[u8]) -> Result<Self, ReportDescriptorError> {
+        let items = ReportItem::parse_all(raw)?;
+        let mut global = GlobalItemsState::default();
+        let mut global_stack: Vec<GlobalItemsState> = Vec::new();
+        let mut local = LocalItemsState::default();
+        let mut collections: Vec<Collection> = Vec::new();
+        let mut collection_stack: Vec<usize> = Vec::new();
+        let mut reports: BTreeMap<(ReportType, Option<u8>), Report> = BTreeMap::new();
+        let mut field_refs: Vec<(ReportType, Option<u8>, usize, Option<usize>)> = Vec::new();
+
+        for report_item in &items {
+            let offset = report_item.offset;
+            let (tag, size) = match &report_item.item {
+                Item::Short { tag, size, .. } => (*tag, *size),
+                Item::Long { tag, .. } => {
+                    trace!("skip long item {:#x} at {}", tag, offset);
+                    continue;
+                }
+            };
+            let (unsigned, signed) = (report_item.item.unsigned(), report_item.item.signed());
+
+            match tag {
+                ItemTag::Main(
+                    main @ (MainItemTag::Input | MainItemTag::Output | MainItemTag::Feature),
+                ) => {
+                    let report_type = match main {
+                        MainItemTag::Input => ReportType::Input,
+                        MainItemTag::Output => ReportType::Output,
+                        _ => ReportType::Feature,
+                    };
+                    let report_id = global.report_id;
+                    let report =
+                        reports
+                            .entry((report_type, report_id))
+                            .or_insert_with(|| Report {
+                                report_type,
+                                report_id,
+                                bit_length: 0,
+                                fields: Vec::new(),
+                            });
+
+                    //both come straight from the device, don't let them wrap past the limit
+                    let bit_length = global
+                        .report_size
+                        .checked_mul(global.report_count)
+                        .ok_or(ReportDescriptorError::ReportTooLarge(offset))?
+                        as usize;
+                    if report
+                        .bit_length
+                        .checked_add(bit_length)
+                        .is_none_or(|total| total > MAX_REPORT_BITS)
+                    {
+                        return Err(ReportDescriptorError::ReportTooLarge(offset));
+                    }
+                    report.fields.push(ReportField {
+                        report_type,
+                        bit_offset: report.bit_length,
+                        bit_length,
+                        global_state: global.clone(),
+                        local_state: core::mem::take(&mut local).resolve(global.usage_page),
+                        flags: MainItemFlags(unsigned),
+                        collection: collection_stack.last().copied(),
+                    });
+                    report.bit_length += bit_length;
+                    field_refs.push((
+                        report_type,
+                        report_id,
+                        report.fields.len() - 1,
+                        collection_stack.last().copied(),
+                    ));
+                }
+                ItemTag::Main(MainItemTag::Collection) => {
+                    let local = core::mem::take(&mut local).resolve(global.usage_page);
+                    let parent = collection_stack.last().copied();
+                    collections.push(Collection {
+                        collection_type: CollectionType::from(unsigned),
+                        usage: local.usage(0).unwrap_or(0),
+                        parent,
+                        children: Vec::new(),
+                        fields: Vec::new(),
+                    });
+                    let index = collections.len() - 1;
+                    if let Some(parent) = parent {
+                        collections[parent].children.push(index);
+                    }
+                    collection_stack.push(index);
+                }
+                ItemTag::Main(MainItemTag::EndCollection) => {
+                    collection_stack
+                        .pop()
+                        .ok_or(ReportDescriptorError::UnbalancedEndCollection(offset))?;
+                    local = LocalItemsState::default();
+                }
+                ItemTag::Global(global_tag) => match global_tag {
+                    GlobalItemTag::UsagePage => global.usage_page = unsigned & 0xffff,
+                    GlobalItemTag::LogicalMinimum => global.logical_minimum = signed,
+                    //maximums are only signed if the matching minimum is negative
+                    GlobalItemTag::LogicalMaximum => {
+                        global.logical_maximum = if global.logical_minimum < 0 {
+                            signed
+                        } else {
+                            unsigned as i32
+                        }
+                    }
+                    GlobalItemTag::PhysicalMinimum => global.physical_minimum = signed,
+                    GlobalItemTag::PhysicalMaximum => {
+                        global.physical_maximum = if global.physical_minimum < 0 {
+                            signed
+                        } else {
+                            unsigned as i32
+                        }
+                    }
+                    GlobalItemTag::UnitExponent => {
+                        global.unit_exponent = report_item.item.unit_exponent()
+                    }
+                    GlobalItemTag::Unit => global.unit = unsigned,
+                    GlobalItemTag::ReportSize => global.report_size = unsigned,
+                    GlobalItemTag::ReportId => {
+                        if unsigned == 0 || unsigned > 0xFF {
+                            return Err(ReportDescriptorError::ReportIdZero(offset));
+                        }
+                        global.report_id = Some(unsigned as u8)
+                    }
+                    GlobalItemTag::ReportCount => global.report_count = unsigned,
+                    GlobalItemTag::Push => global_stack.push(global.clone()),
+                    GlobalItemTag::Pop => {
+                        global = global_stack
+                            .pop()
+                            .ok_or(ReportDescriptorError::PopWithoutPush(offset))?
+                    }
+                },
+                ItemTag::Local(local_tag) => match local_tag {
+                    LocalItemTag::Usage => local.push_usage(unsigned, size == 4),
+                    LocalItemTag::UsageMinimum => {
+                        local.usage_minimum = Some((unsigned, size == 4));
+                        local.try_push_usage_range();
+                    }
+                    LocalItemTag::UsageMaximum => {
+                        local.usage_maximum = Some((unsigned, size == 4));
+                        local.try_push_usage_range();
+                    }
+                    LocalItemTag::DesignatorIndex => local.designator_index = Some(unsigned),
+                    LocalItemTag::DesignatorMinimum => local.designator_minimum = Some(unsigned),
+                    LocalItemTag::DesignatorMaximum => local.designator_maximum = Some(unsigned),
+                    LocalItemTag::StringIndex => local.string_index = Some(unsigned),
+                    LocalItemTag::StringMinimum => local.string_minimum = Some(unsigned),
+                    LocalItemTag::StringMaximum => local.string_maximum = Some(unsigned),
+                    LocalItemTag::Delimiter => match (unsigned, local.delimiter_open) {
+                        (1, false) => {
+                            local.delimiter_open = true;
+                            local.delimiter_branch = 0;
+                        }
+                        (1, true) => return Err(ReportDescriptorError::NestedDelimiter(offset)),
+                        (0, true) => {
+                            local.delimiter_open = false;
+                        }
+                        _ => return Err(ReportDescriptorError::UnbalancedDelimiter(offset)),
+                    },
+                },
+                ItemTag::Reserved(item_type, tag) => {
+                    warn!(
+                        "reserved {:?} item tag {:#x} at {}, ignored",
+                        item_type, tag, offset
+                    );
+                }
+            }
+        }
+
+        if !collection_stack.is_empty() {
+            return Err(ReportDescriptorError::UnclosedCollection);
+        }
+        if !global_stack.is_empty() {
+            warn!("{} global push items never popped", global_stack.len());
+        }
+
+        let keys: Vec<_> = reports.keys().copied().collect();
+        for (report_type, report_id, field, collection) in field_refs {
+            if let Some(collection) = collection {
+                let report = keys
+                    .iter()
+                    .position(|key| *key == (report_type, report_id))
+                    .unwrap();
+                collections[collection].fields.push((report, field));
+            }
+        }
+
+        Ok(ReportDescriptor {
+            raw: raw.to_vec(),
+            items,
+            collections,
+            reports: reports.into_values().collect(),
+        })
+    }
+
+    pub fn report(&self, report_type: ReportType, report_id: Option<u8>) -> Option<&Report> {
+        self.reports
+            .iter()
+            .find(|report| report.report_type == report_type && report.report_id == report_id)
+    }
+
+    //encode into the report of this type that declares the first assigned usage
+    pub fn encode(
+        &self,
+        report_type: ReportType,
+        assignments: &[(u32, u32, i32)],
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        let report = match assignments.first() {
+            Some(&(usage_page, usage, _)) => self
+                .reports_of(report_type)
+                .find(|report| report.declares(usage_page, usage))
+                .ok_or(ReportDescriptorError::UndeclaredUsage(usage_page, usage))?,
+            None => self
+                .reports_of(report_type)
+                .next()
+                .ok_or(ReportDescriptorError::UnknownReportId(0))?,
+        };
+        report.encode(assignments)
+    }
+
+    pub fn reports_of(&self, report_type: ReportType) -> impl Iterator<Item = &Report> {
+        self.reports
+            .iter()
+            .filter(move |report| report.report_type == report_type)
+    }
+
+    pub fn uses_report_ids(&self) -> bool {
+        self.reports.iter().any(|report| report.report_id.is_some())
+    }
+
+    //collections without a parent, usually one Application collection per top level usage
+    pub fn root_collections(&self) -> impl Iterator<Item = (usize, &Collection)> {
+        self.collections
+            .iter()
+            .enumerate()
+            .filter(|(_, collection)| collection.parent.is_none())
+    }
+
+    pub fn field(&self, reference: (usize, usize)) -> &ReportField {
+        &self.reports[reference.0].fields[reference.1]
+    }
+
+    //fields of a collection and all collections nested in it
+    pub fn collection_fields(&self, collection: usize) -> Vec<(usize, usize)> {
+        let mut fields = self.collections[collection].fields.clone();
+        for child in &self.collections[collection].children {
+            fields.extend(self.collection_fields(*child));
+        }
+        fields
+    }
+
+    //find the report a payload belongs to and strip the report id byte
+    pub fn split_report<'a>(
+        &self,
+        report_type: ReportType,
+        data: &'a [u8],
+    ) -> Result<(usize, &'a [u8]), ReportDescriptorError> {
+        let (report_id, payload) = match (self.uses_report_ids(), data.split_first()) {
+            (true, Some((id, payload))) => (Some(*id), payload),
+            (true, None) => return Err(ReportDescriptorError::ShortReport(0)),
+            (false, _) => (None, data),
+        };
+        self.reports
+            .iter()
+            .position(|report| report.report_type == report_type && report.report_id == report_id)
+            .map(|index| (index, payload))
+            .ok_or(ReportDescriptorError::UnknownReportId(
+                report_id.unwrap_or(0),
+            ))
+    }
+}
+
+//little endian bit field of up to 32 bits, bytes past the end of a short report read as zero
+pub(crate) fn read_bits(data: &[u8], bit_offset: usize, bit_length: usize) -> u32 {
+    let mut value = 0u32;
+    for bit in 0..bit_length.min(32) {
+        let position = bit_offset + bit;
+        let byte = data.get(position / 8).copied().unwrap_or(0);
+        value |= ((byte >> (position % 8)) as u32 & 1) << bit;
+    }
+    value
+}
+
+pub(crate) fn write_bits(data: &mut [u8], bit_offset: usize, bit_length: usize, value: u32) {
+    for bit in 0..bit_length.min(32) {
+        let position = bit_offset + bit;
+        let mask = 1 << (position % 8);
+        if value.get_bit(bit) {
+            data[position / 8] |= mask;
+        } else {
+            data[position / 8] &= !mask;
+        }
+    }
+}
+
+pub(crate) fn sign_extend(value: u32, bit_length: usize) -> i32 {
+    match bit_length {
+        0 => 0,
+        1..=31 => ((value << (32 - bit_length)) as i32) >> (32 - bit_length),
+        _ => value as i32,
+    }
+}
+
+//10^exponent without std float intrinsics
+pub(crate) fn pow10(exponent: i32) -> f64 {
+    let mut factor = 1.0;
+    for _ in 0..exponent.unsigned_abs() {
+        factor *= 10.0;
+    }
+    if exponent < 0 {
+        1.0 / factor
+    } else {
+        factor
+    }
+}
+
+impl GlobalItemsState {
+    //HID 1.11 6.2.2.7: physical bounds of zero mean they equal the logical ones
+    pub fn physical_bounds(&self) -> (i32, i32) {
+        if self.physical_minimum == 0 && self.physical_maximum == 0 {
+            (self.logical_minimum, self.logical_maximum)
+        } else {
+            (self.physical_minimum, self.physical_maximum)
+        }
+    }
+
+    //logical value converted to physical units, unit exponent applied
+    pub fn to_physical(&self, logical: i32) -> f64 {
+        let (physical_minimum, physical_maximum) = self.physical_bounds();
+        let logical_span = self.logical_maximum as f64 - self.logical_minimum as f64;
+        let physical = if logical_span == 0.0 {
+            logical as f64
+        } else {
+            physical_minimum as f64
+                + (logical as f64 - self.logical_minimum as f64)
+                    * (physical_maximum as f64 - physical_minimum as f64)
+                    / logical_span
+        };
+        physical * pow10(self.unit_exponent)
+    }
+
+    //inverse of to_physical, rounded and clamped to the logical range
+    pub fn from_physical(&self, physical: f64) -> i32 {
+        let (physical_minimum, physical_maximum) = self.physical_bounds();
+        let physical = physical / pow10(self.unit_exponent);
+        let physical_span = physical_maximum as f64 - physical_minimum as f64;
+        let logical = if physical_span == 0.0 {
+            physical
+        } else {
+            self.logical_minimum as f64
+                + (physical - physical_minimum as f64)
+                    * (self.logical_maximum as f64 - self.logical_minimum as f64)
+                    / physical_span
+        };
+        let logical = if logical < 0.0 {
+            (logical - 0.5) as i32
+        } else {
+            (logical + 0.5) as i32
+        };
+        if self.logical_minimum < self.logical_maximum {
+            logical.clamp(self.logical_minimum, self.logical_maximum)
+        } else {
+            logical
+        }
+    }
+
+    pub fn in_logical_range(&self, value: i32) -> bool {
+        self.logical_minimum >= self.logical_maximum
+            || (self.logical_minimum..=self.logical_maximum).contains(&value)
+    }
+}
+
+impl ReportField {
+    //nth element of the field, sign extended when the logical range is signed
+    pub fn read(&self, report: &[u8], index: usize) -> i32 {
+        let size = self.report_size();
+        let raw = read_bits(report, self.bit_offset + index * size, size);
+        if self.is_signed() {
+            sign_extend(raw, size)
+        } else {
+            raw as i32
+        }
+    }
+}
+
+impl Report {
+    pub fn declares(&self, usage_page: u32, usage: u32) -> bool {
+        let usage = (usage_page << 16) | (usage & 0xffff);
+        self.fields
+            .iter()
+            .any(|field| !field.is_padding() && field.local_state.contains(usage))
+    }
+
+    //(field index, element) carrying a variable usage
+    pub fn locate(&self, usage_page: u32, usage: u32) -> Option<(usize, usize)> {
+        let usage = (usage_page << 16) | (usage & 0xffff);
+        self.fields.iter().enumerate().find_map(|(index, field)| {
+            if field.is_padding() || !field.flags.is_variable() {
+                return None;
+            }
+            (0..field.report_count())
+                .find(|element| field.usage(*element) == Some(usage))
+                .map(|element| (index, element))
+        })
+    }
+
+    //value of a variable usage in a report payload without the report id byte
+    pub fn value(&self, payload: &[u8], usage_page: u32, usage: u32) -> Option<i32> {
+        let (field, element) = self.locate(usage_page, usage)?;
+        Some(self.fields[field].read(payload, element))
+    }
+
+    //pack (usage page, usage, value) assignments, values are clamped to the logical range
+    //and anything not assigned stays zero
+    pub fn encode(
+        &self,
+        assignments: &[(u32, u32, i32)],
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        let prefix = self.report_id.is_some() as usize;
+        let mut data = vec![0u8; self.byte_length()];
+        if let Some(id) = self.report_id {
+            data[0] = id;
+        }
+        let body = &mut data[prefix..];
+        let mut array_slots: BTreeMap<usize, usize> = BTreeMap::new();
+
+        for &(usage_page, usage, value) in assignments {
+            let extended = (usage_page << 16) | (usage & 0xffff);
+            let (index, field) = self
+                .fields
+                .iter()
+                .enumerate()
+                .find(|(_, field)| !field.is_padding() && field.local_state.contains(extended))
+                .ok_or(ReportDescriptorError::UndeclaredUsage(usage_page, usage))?;
+            let size = field.report_size();
+            let global = &field.global_state;
+
+            if field.flags.is_variable() {
+                //a usage range longer than the report count declares usages no element carries
+                let element = (0..field.report_count())
+                    .find(|element| field.usage(*element) == Some(extended))
+                    .ok_or(ReportDescriptorError::UndeclaredUsage(usage_page, usage))?;
+                let value = if global.logical_minimum < global.logical_maximum {
+                    value.clamp(global.logical_minimum, global.logical_maximum)
+                } else {
+                    value
+                };
+                write_bits(body, field.bit_offset + element * size, size, value as u32);
+            } else if value != 0 {
+                //array fields carry the selector of each active usage in the next free slot
+                let slot = array_slots.entry(index).or_insert(0);
+                if *slot >= field.report_count() {
+                    return Err(ReportDescriptorError::ArrayOverflow(usage_page, usage));
+                }
+                let selector = (0..field.local_state.usage_count() as usize)
+                    .position(|selector| field.usage(selector) == Some(extended))
+                    .ok_or(ReportDescriptorError::UndeclaredUsage(usage_page, usage))?
+                    as i32;
+                write_bits(
+                    body,
+                    field.bit_offset + *slot * size,
+                    size,
+                    (global.logical_minimum + selector) as u32,
+                );
+                *slot += 1;
+            }
+        }
+        Ok(data)
+    }
+}
 
 #[derive(Debug)]
 pub struct ReportEvent {
@@ -6,10 +968,21 @@ pub struct ReportEvent {
     pub usage: u32,
     pub value: i32,
     pub relative: bool,
+    //value in physical units, None for array selections
+    pub physical: Option<f64>,
 }
 
-#[derive(Debug)]
+impl ReportEvent {
+    pub fn usage(&self) -> Usage {
+        Usage::new(self.usage_page as u16, self.usage as u16)
+    }
+}
+
+#[derive(Debug, Clone)]
 pub struct ReportInput {
+    //position of the report among the input reports and of the field within it
+    pub report_index: usize,
+    pub field_index: usize,
     pub bit_length: usize,
     pub bit_offset: usize,
     pub global_state: GlobalItemsState,
@@ -17,15 +990,204 @@ pub struct ReportInput {
     pub flags: MainItemFlags,
 }
 
+impl ReportInput {
+    //ReportField::read without cloning the item states into a ReportField
+    fn read(&self, payload: &[u8], index: usize) -> i32 {
+        let size = self.global_state.report_size as usize;
+        let raw = read_bits(payload, self.bit_offset + index * size, size);
+        if self.global_state.logical_minimum < 0 {
+            sign_extend(raw, size)
+        } else {
+            raw as i32
+        }
+    }
+
+    fn end(&self) -> usize {
+        self.bit_offset + self.bit_length
+    }
+
+    fn usage_key(usage: u32) -> (u32, u32) {
+        (usage >> 16, usage & 0xffff)
+    }
+}
+
 pub struct ReportHandler {
     pub inputs: Vec<ReportInput>,
     pub total_byte_length: usize,
-    pub absolutes: HashMap<(u32, u32), i32>,
-    pub arrays: HashSet<(u32, u32)>,
+    //last value of every absolute element, keyed by report, field and element since the same
+    //usage repeats per contact in digitizers and across report ids in composite devices
+    pub absolutes: BTreeMap<(usize, usize, usize), i32>,
+    pub arrays: BTreeSet<(u32, u32)>,
 }
 
-impl ReportHandler{
-    pub fn new() -> Result<self,Error>{
-        
+impl ReportHandler {
+    pub fn new(descriptor: &ReportDescriptor) -> Self {
+        let inputs = descriptor
+            .reports_of(ReportType::Input)
+            .enumerate()
+            .flat_map(|(report_index, report)| {
+                report
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .map(move |(field_index, field)| (report_index, field_index, field))
+            })
+            .filter(|(_, _, field)| !field.is_padding())
+            .map(|(report_index, field_index, field)| ReportInput {
+                report_index,
+                field_index,
+                bit_length: field.bit_length,
+                bit_offset: field.bit_offset,
+                global_state: field.global_state.clone(),
+                local_state: field.local_state.clone(),
+                flags: field.flags,
+            })
+            .collect();
+        let total_byte_length = descriptor
+            .reports_of(ReportType::Input)
+            .map(|report| report.byte_length())
+            .max()
+            .unwrap_or(0);
+
+        Self {
+            inputs,
+            total_byte_length,
+            absolutes: BTreeMap::new(),
+            arrays: BTreeSet::new(),
+        }
+    }
+
+    //decode one interrupt IN payload, only values that changed since the last report are returned,
+    //absolute values start out as zero
+    pub fn handle(&mut self, data: &[u8]) -> Result<Vec<ReportEvent>, ReportDescriptorError> {
+        let uses_report_ids = self
+            .inputs
+            .iter()
+            .any(|input| input.global_state.report_id.is_some());
+        let (report_id, payload) = match (uses_report_ids, data.split_first()) {
+            (true, Some((id, payload))) => (Some(*id), payload),
+            (true, None) => return Err(ReportDescriptorError::ShortReport(0)),
+            (false, _) => (None, data),
+        };
+
+        //fields past the end of a short report would read as zero and fake a change
+        let end = self
+            .inputs
+            .iter()
+            .filter(|input| input.global_state.report_id == report_id)
+            .map(ReportInput::end)
+            .max()
+            .ok_or(ReportDescriptorError::UnknownReportId(
+                report_id.unwrap_or(0),
+            ))?;
+        if payload.len() * 8 < end {
+            return Err(ReportDescriptorError::ShortReport(payload.len()));
+        }
+
+        let mut events = Vec::new();
+        for input in self
+            .inputs
+            .iter()
+            .filter(|input| input.global_state.report_id == report_id)
+        {
+            if input.flags.is_variable() {
+                Self::handle_variable(&mut self.absolutes, input, payload, &mut events);
+            } else {
+                Self::handle_array(&mut self.arrays, input, payload, &mut events);
+            }
+        }
+        Ok(events)
     }
-}
\ No newline at end of file
+
+    fn handle_variable(
+        absolutes: &mut BTreeMap<(usize, usize, usize), i32>,
+        input: &ReportInput,
+        payload: &[u8],
+        events: &mut Vec<ReportEvent>,
+    ) {
+        for index in 0..input.global_state.report_count as usize {
+            let Some(usage) = input.local_state.usage(index) else {
+                break;
+            };
+            let value = input.read(payload, index);
+            //out of range values are null states, nothing to report
+            if !input.global_state.in_logical_range(value) {
+                continue;
+            }
+            let key = ReportInput::usage_key(usage);
+            let relative = input.flags.is_relative();
+            if relative {
+                if value == 0 {
+                    continue;
+                }
+            } else if absolutes
+                .insert((input.report_index, input.field_index, index), value)
+                .unwrap_or(0)
+                == value
+            {
+                continue;
+            }
+            events.push(ReportEvent {
+                usage_page: key.0,
+                usage: key.1,
+                value,
+                relative,
+                physical: Some(input.global_state.to_physical(value)),
+            });
+        }
+    }
+
+    //array fields list the selected usages, a usage leaving the list is a release
+    fn handle_array(
+        arrays: &mut BTreeSet<(u32, u32)>,
+        input: &ReportInput,
+        payload: &[u8],
+        events: &mut Vec<ReportEvent>,
+    ) {
+        let mut current = BTreeSet::new();
+        for index in 0..input.global_state.report_count as usize {
+            let value = input.read(payload, index);
+            if !input.global_state.in_logical_range(value) {
+                continue;
+            }
+            let selector = (value - input.global_state.logical_minimum) as usize;
+            if selector as u32 >= input.local_state.usage_count() {
+                continue;
+            }
+            if let Some(usage) = input.local_state.usage(selector)
+                && usage & 0xffff != 0
+            {
+                current.insert(ReportInput::usage_key(usage));
+            }
+        }
+
+        let released: Vec<_> = arrays
+            .iter()
+            .filter(|key| {
+                input.local_state.contains((key.0 << 16) | key.1) && !current.contains(key)
+            })
+            .copied()
+            .collect();
+        for key in released {
+            arrays.remove(&key);
+            events.push(ReportEvent {
+                usage_page: key.0,
+                usage: key.1,
+                value: 0,
+                relative: false,
+                physical: None,
+            });
+        }
+        for key in current {
+            if arrays.insert(key) {
+                events.push(ReportEvent {
+                    usage_page: key.0,
+                    usage: key.1,
+                    value: 1,
+                    relative: false,
+                    physical: None,
+                });
+            }
+        }
+    }
+}
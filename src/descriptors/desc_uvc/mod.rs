@@ -1,7 +1,12 @@
 use num_derive::FromPrimitive;
 
+pub mod uvc_controls;
 pub mod uvc_endpoints;
+pub mod uvc_formats;
 pub mod uvc_interfaces;
+pub mod uvc_payload;
+pub mod uvc_probe;
+pub mod uvc_streaming;
 
 #[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
 #[allow(non_camel_case_types)]
@@ -0,0 +1,243 @@
+//named bits of the bmControls bitmaps found in video control terminals and units
+use alloc::vec::Vec;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+//set bits of a little endian bitmap, reserved bits are skipped
+pub(crate) fn controls_from_bitmap<T: FromPrimitive>(bitmap: &[u8]) -> Vec<T> {
+    (0..bitmap.len() * 8)
+        .filter(|bit| bitmap[bit / 8] & (1 << (bit % 8)) != 0)
+        .filter_map(T::from_usize)
+        .collect()
+}
+
+//UVC 1.5 table 3-6
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum CameraControl {
+    ScanningMode = 0,
+    AutoExposureMode = 1,
+    AutoExposurePriority = 2,
+    ExposureTimeAbsolute = 3,
+    ExposureTimeRelative = 4,
+    FocusAbsolute = 5,
+    FocusRelative = 6,
+    IrisAbsolute = 7,
+    IrisRelative = 8,
+    ZoomAbsolute = 9,
+    ZoomRelative = 10,
+    PanTiltAbsolute = 11,
+    PanTiltRelative = 12,
+    RollAbsolute = 13,
+    RollRelative = 14,
+    FocusAuto = 17,
+    Privacy = 18,
+    FocusSimple = 19,
+    Window = 20,
+    RegionOfInterest = 21,
+}
+
+impl CameraControl {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CameraControl::ScanningMode => "Scanning Mode",
+            CameraControl::AutoExposureMode => "Auto-Exposure Mode",
+            CameraControl::AutoExposurePriority => "Auto-Exposure Priority",
+            CameraControl::ExposureTimeAbsolute => "Exposure Time (Absolute)",
+            CameraControl::ExposureTimeRelative => "Exposure Time (Relative)",
+            CameraControl::FocusAbsolute => "Focus (Absolute)",
+            CameraControl::FocusRelative => "Focus (Relative)",
+            CameraControl::IrisAbsolute => "Iris (Absolute)",
+            CameraControl::IrisRelative => "Iris (Relative)",
+            CameraControl::ZoomAbsolute => "Zoom (Absolute)",
+            CameraControl::ZoomRelative => "Zoom (Relative)",
+            CameraControl::PanTiltAbsolute => "PanTilt (Absolute)",
+            CameraControl::PanTiltRelative => "PanTilt (Relative)",
+            CameraControl::RollAbsolute => "Roll (Absolute)",
+            CameraControl::RollRelative => "Roll (Relative)",
+            CameraControl::FocusAuto => "Focus, Auto",
+            CameraControl::Privacy => "Privacy",
+            CameraControl::FocusSimple => "Focus, Simple",
+            CameraControl::Window => "Window",
+            CameraControl::RegionOfInterest => "Region of Interest",
+        }
+    }
+}
+
+//UVC 1.5 table 3-8
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum ProcessingControl {
+    Brightness = 0,
+    Contrast = 1,
+    Hue = 2,
+    Saturation = 3,
+    Sharpness = 4,
+    Gamma = 5,
+    WhiteBalanceTemperature = 6,
+    WhiteBalanceComponent = 7,
+    BacklightCompensation = 8,
+    Gain = 9,
+    PowerLineFrequency = 10,
+    HueAuto = 11,
+    WhiteBalanceTemperatureAuto = 12,
+    WhiteBalanceComponentAuto = 13,
+    DigitalMultiplier = 14,
+    DigitalMultiplierLimit = 15,
+    AnalogVideoStandard = 16,
+    AnalogVideoLockStatus = 17,
+    ContrastAuto = 18,
+}
+
+impl ProcessingControl {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ProcessingControl::Brightness => "Brightness",
+            ProcessingControl::Contrast => "Contrast",
+            ProcessingControl::Hue => "Hue",
+            ProcessingControl::Saturation => "Saturation",
+            ProcessingControl::Sharpness => "Sharpness",
+            ProcessingControl::Gamma => "Gamma",
+            ProcessingControl::WhiteBalanceTemperature => "White Balance Temperature",
+            ProcessingControl::WhiteBalanceComponent => "White Balance Component",
+            ProcessingControl::BacklightCompensation => "Backlight Compensation",
+            ProcessingControl::Gain => "Gain",
+            ProcessingControl::PowerLineFrequency => "Power Line Frequency",
+            ProcessingControl::HueAuto => "Hue, Auto",
+            ProcessingControl::WhiteBalanceTemperatureAuto => "White Balance Temperature, Auto",
+            ProcessingControl::WhiteBalanceComponentAuto => "White Balance Component, Auto",
+            ProcessingControl::DigitalMultiplier => "Digital Multiplier",
+            ProcessingControl::DigitalMultiplierLimit => "Digital Multiplier Limit",
+            ProcessingControl::AnalogVideoStandard => "Analog Video Standard",
+            ProcessingControl::AnalogVideoLockStatus => "Analog Video Lock Status",
+            ProcessingControl::ContrastAuto => "Contrast, Auto",
+        }
+    }
+}
+
+//bmVideoStandards of the processing unit
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum VideoStandard {
+    None = 0,
+    Ntsc525_60 = 1,
+    Pal625_50 = 2,
+    Secam625_50 = 3,
+    Ntsc625_50 = 4,
+    Pal525_60 = 5,
+}
+
+impl VideoStandard {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VideoStandard::None => "None",
+            VideoStandard::Ntsc525_60 => "NTSC - 525/60",
+            VideoStandard::Pal625_50 => "PAL - 625/50",
+            VideoStandard::Secam625_50 => "SECAM - 625/50",
+            VideoStandard::Ntsc625_50 => "NTSC - 625/50",
+            VideoStandard::Pal525_60 => "PAL - 525/60",
+        }
+    }
+}
+
+//UVC 1.5 table 3-12, shared by bmControls and bmControlsRuntime
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum EncodingControl {
+    SelectLayer = 0,
+    ProfileAndToolset = 1,
+    VideoResolution = 2,
+    MinimumFrameInterval = 3,
+    SliceMode = 4,
+    RateControlMode = 5,
+    AverageBitRate = 6,
+    CpbSize = 7,
+    PeakBitRate = 8,
+    QuantizationParameter = 9,
+    SynchronizationAndLongTermReferenceFrame = 10,
+    LongTermBuffer = 11,
+    PictureLongTermReference = 12,
+    LtrValidation = 13,
+    LevelIdc = 14,
+    SeiMessage = 15,
+    QpRange = 16,
+    PriorityId = 17,
+    StartOrStopLayer = 18,
+    ErrorResiliency = 19,
+}
+
+impl EncodingControl {
+    pub fn name(&self) -> &'static str {
+        match self {
+            EncodingControl::SelectLayer => "Select Layer",
+            EncodingControl::ProfileAndToolset => "Profile and Toolset",
+            EncodingControl::VideoResolution => "Video Resolution",
+            EncodingControl::MinimumFrameInterval => "Minimum Frame Interval",
+            EncodingControl::SliceMode => "Slice Mode",
+            EncodingControl::RateControlMode => "Rate Control Mode",
+            EncodingControl::AverageBitRate => "Average Bit Rate",
+            EncodingControl::CpbSize => "CPB Size",
+            EncodingControl::PeakBitRate => "Peak Bit Rate",
+            EncodingControl::QuantizationParameter => "Quantization Parameter",
+            EncodingControl::SynchronizationAndLongTermReferenceFrame => {
+                "Synchronization and Long-Term Reference Frame"
+            }
+            EncodingControl::LongTermBuffer => "Long-Term Buffer",
+            EncodingControl::PictureLongTermReference => "Picture Long-Term Reference",
+            EncodingControl::LtrValidation => "LTR Validation",
+            EncodingControl::LevelIdc => "Level IDC",
+            EncodingControl::SeiMessage => "SEI Message",
+            EncodingControl::QpRange => "QP Range",
+            EncodingControl::PriorityId => "Priority ID",
+            EncodingControl::StartOrStopLayer => "Start or Stop Layer/View",
+            EncodingControl::ErrorResiliency => "Error Resiliency",
+        }
+    }
+}
+
+//media transport terminal specification, table 2-1
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum MediaTransportControl {
+    TransportControl = 0,
+    AbsoluteTrackNumber = 1,
+    MediaInformation = 2,
+    TimeCodeInformation = 3,
+}
+
+impl MediaTransportControl {
+    pub fn name(&self) -> &'static str {
+        match self {
+            MediaTransportControl::TransportControl => "Transport Control",
+            MediaTransportControl::AbsoluteTrackNumber => "Absolute Track Number",
+            MediaTransportControl::MediaInformation => "Media Information",
+            MediaTransportControl::TimeCodeInformation => "Time Code Information",
+        }
+    }
+}
+
+//bmaControls of the streaming input and output headers, one bitmap per format
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[repr(u8)]
+pub enum StreamingControl {
+    KeyFrameRate = 0,
+    PFrameRate = 1,
+    CompressionQuality = 2,
+    CompressionWindowSize = 3,
+    GenerateKeyFrame = 4,
+    UpdateFrameSegment = 5,
+}
+
+impl StreamingControl {
+    pub fn name(&self) -> &'static str {
+        match self {
+            StreamingControl::KeyFrameRate => "wKeyFrameRate",
+            StreamingControl::PFrameRate => "wPFrameRate",
+            StreamingControl::CompressionQuality => "wCompQuality",
+            StreamingControl::CompressionWindowSize => "wCompWindowSize",
+            StreamingControl::GenerateKeyFrame => "Generate Key Frame",
+            StreamingControl::UpdateFrameSegment => "Update Frame Segment",
+        }
+    }
+}
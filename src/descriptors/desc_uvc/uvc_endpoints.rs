@@ -1,4 +1,9 @@
+use core::fmt;
+
 use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use crate::descriptors::lsusb::LsusbWriter;
 
 #[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
 #[allow(non_camel_case_types)]
@@ -18,3 +23,20 @@ pub struct UVCVideoControlInterruptEndpoint {
     descriptor_sub_type: u8,
     max_transfer_size: u16,
 }
+
+impl UVCVideoControlInterruptEndpoint {
+    pub(crate) fn dump_lsusb(&self, w: &mut LsusbWriter) -> fmt::Result {
+        w.block(6, LsusbWriter::COLUMN, "VideoControl Endpoint Descriptor:")?;
+        w.field("bLength", self.len)?;
+        w.field("bDescriptorType", self.descriptor_type)?;
+        w.field_text(
+            "bDescriptorSubtype",
+            self.descriptor_sub_type,
+            match UVCVideoClassEndpointSubtypes::from_u8(self.descriptor_sub_type) {
+                Some(UVCVideoClassEndpointSubtypes::INTERRUPT) => "(EP_INTERRUPT)",
+                _ => "(invalid)",
+            },
+        )?;
+        w.field("wMaxTransferSize", self.max_transfer_size)
+    }
+}
@@ -0,0 +1,165 @@
+//pixel formats behind the guidFormat of uncompressed, frame based and stream based formats
+use alloc::{format, string::String};
+
+use super::{
+    uvc_interfaces::{
+        GuidText, UVCStreamingInterface, UVCVSInterfaceFormatFrameBased,
+        UVCVSInterfaceFormatStreamBased, UVCVSInterfaceFormatUncompressed,
+    },
+    uvc_streaming::UvcFormat,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PlaneLayout {
+    //all components interleaved in one plane
+    Packed,
+    //a luma plane followed by one interleaved chroma plane
+    SemiPlanar,
+    //one plane per component
+    Planar,
+    //a bitstream for a decoder, no pixels to speak of
+    Compressed,
+}
+
+impl PlaneLayout {
+    pub fn planes(&self) -> u8 {
+        match self {
+            PlaneLayout::Packed | PlaneLayout::Compressed => 1,
+            PlaneLayout::SemiPlanar => 2,
+            PlaneLayout::Planar => 3,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UvcPixelFormat {
+    pub guid: [u8; 16],
+    pub fourcc: [u8; 4],
+    pub name: &'static str,
+    //0 for compressed formats
+    pub bits_per_pixel: u8,
+    pub layout: PlaneLayout,
+}
+
+impl UvcPixelFormat {
+    pub fn fourcc_str(&self) -> &str {
+        core::str::from_utf8(&self.fourcc).unwrap_or("")
+    }
+}
+
+//most GUIDs are the fourcc followed by the fixed tail -0000-0010-8000-00aa00389b71
+const fn standard(
+    fourcc: &[u8; 4],
+    name: &'static str,
+    bits_per_pixel: u8,
+    layout: PlaneLayout,
+) -> UvcPixelFormat {
+    UvcPixelFormat {
+        guid: [
+            fourcc[0], fourcc[1], fourcc[2], fourcc[3], 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00,
+            0xaa, 0x00, 0x38, 0x9b, 0x71,
+        ],
+        fourcc: *fourcc,
+        name,
+        bits_per_pixel,
+        layout,
+    }
+}
+
+pub const UVC_PIXEL_FORMATS: &[UvcPixelFormat] = &[
+    standard(b"YUY2", "YUV 4:2:2 (YUYV)", 16, PlaneLayout::Packed),
+    standard(b"UYVY", "YUV 4:2:2 (UYVY)", 16, PlaneLayout::Packed),
+    standard(b"YVYU", "YUV 4:2:2 (YVYU)", 16, PlaneLayout::Packed),
+    standard(b"NV12", "YUV 4:2:0 (NV12)", 12, PlaneLayout::SemiPlanar),
+    standard(b"NV21", "YUV 4:2:0 (NV21)", 12, PlaneLayout::SemiPlanar),
+    //two lines of luma, then one line of interleaved chroma
+    standard(b"M420", "YUV 4:2:0 (M420)", 12, PlaneLayout::Packed),
+    standard(b"I420", "YUV 4:2:0 (I420)", 12, PlaneLayout::Planar),
+    standard(b"YV12", "YVU 4:2:0 (YV12)", 12, PlaneLayout::Planar),
+    standard(
+        b"P010",
+        "YUV 4:2:0 10-bit (P010)",
+        24,
+        PlaneLayout::SemiPlanar,
+    ),
+    standard(b"Y800", "Greyscale 8-bit (Y800)", 8, PlaneLayout::Packed),
+    standard(b"Y8  ", "Greyscale 8-bit (Y8  )", 8, PlaneLayout::Packed),
+    standard(b"GREY", "Greyscale 8-bit (GREY)", 8, PlaneLayout::Packed),
+    standard(b"Y16 ", "Greyscale 16-bit (Y16 )", 16, PlaneLayout::Packed),
+    standard(b"Z16 ", "Depth 16-bit (Z16 )", 16, PlaneLayout::Packed),
+    standard(b"RGBP", "RGB565", 16, PlaneLayout::Packed),
+    standard(b"BA81", "Bayer 8-bit (BGGR)", 8, PlaneLayout::Packed),
+    standard(b"GBRG", "Bayer 8-bit (GBRG)", 8, PlaneLayout::Packed),
+    standard(b"GRBG", "Bayer 8-bit (GRBG)", 8, PlaneLayout::Packed),
+    standard(b"RGGB", "Bayer 8-bit (RGGB)", 8, PlaneLayout::Packed),
+    standard(b"MJPG", "Motion-JPEG", 0, PlaneLayout::Compressed),
+    standard(b"H264", "H.264", 0, PlaneLayout::Compressed),
+    standard(b"H265", "H.265", 0, PlaneLayout::Compressed),
+    standard(b"HEVC", "H.265", 0, PlaneLayout::Compressed),
+    standard(b"VP80", "VP8", 0, PlaneLayout::Compressed),
+    //MEDIASUBTYPE_RGB24, bottom-up BGR
+    UvcPixelFormat {
+        guid: [
+            0x7d, 0xeb, 0x36, 0xe4, 0x4f, 0x52, 0xce, 0x11, 0x9f, 0x53, 0x00, 0x20, 0xaf, 0x0b,
+            0xa7, 0x70,
+        ],
+        fourcc: *b"BGR3",
+        name: "BGR 8:8:8",
+        bits_per_pixel: 24,
+        layout: PlaneLayout::Packed,
+    },
+];
+
+pub fn pixel_format(guid: &[u8; 16]) -> Option<&'static UvcPixelFormat> {
+    UVC_PIXEL_FORMATS.iter().find(|format| &format.guid == guid)
+}
+
+//the fourcc of a known GUID, the canonical "{xxxxxxxx-...}" form of any other
+pub fn guid_format_name(guid: &[u8; 16]) -> String {
+    match pixel_format(guid) {
+        Some(format) => String::from(format.fourcc_str()),
+        None => format!("{}", GuidText(guid)),
+    }
+}
+
+impl UVCVSInterfaceFormatUncompressed {
+    pub fn pixel_format(&self) -> Option<&'static UvcPixelFormat> {
+        pixel_format(&self.guid_format)
+    }
+}
+
+impl UVCVSInterfaceFormatFrameBased {
+    pub fn pixel_format(&self) -> Option<&'static UvcPixelFormat> {
+        pixel_format(&self.guid_format)
+    }
+}
+
+impl UVCVSInterfaceFormatStreamBased {
+    pub fn pixel_format(&self) -> Option<&'static UvcPixelFormat> {
+        pixel_format(&self.guid_format)
+    }
+}
+
+impl UvcFormat {
+    //formats without a guidFormat imply their fourcc by descriptor subtype
+    pub fn pixel_format(&self) -> Option<&'static UvcPixelFormat> {
+        match &self.descriptor {
+            UVCStreamingInterface::FormatUncompressed(format) => format.pixel_format(),
+            UVCStreamingInterface::FormatFrameBased(format) => format.pixel_format(),
+            UVCStreamingInterface::FormatStreamBased(format) => format.pixel_format(),
+            UVCStreamingInterface::FormatMjpeg(_) => fourcc_format(b"MJPG"),
+            UVCStreamingInterface::FormatH264(_)
+            | UVCStreamingInterface::FormatH264Simulcast(_) => fourcc_format(b"H264"),
+            UVCStreamingInterface::FormatVp8(_) | UVCStreamingInterface::FormatVp8Simulcast(_) => {
+                fourcc_format(b"VP80")
+            }
+            _ => None,
+        }
+    }
+}
+
+fn fourcc_format(fourcc: &[u8; 4]) -> Option<&'static UvcPixelFormat> {
+    UVC_PIXEL_FORMATS
+        .iter()
+        .find(|format| &format.fourcc == fourcc)
+}
@@ -1,11 +1,21 @@
-use core::ptr;
+use core::{fmt, ptr};
 
-use alloc::vec::Vec;
+use alloc::{format, vec::Vec};
 
 use log::trace;
 use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::{FromPrimitive, ToPrimitive};
 
+use crate::descriptors::{
+    desc_device::BcdVersion,
+    lsusb::{EndpointAddressText, LsusbWriter},
+};
+
+use super::uvc_controls::{
+    controls_from_bitmap, CameraControl, EncodingControl, MediaTransportControl, ProcessingControl,
+    StreamingControl, VideoStandard,
+};
+
 #[derive(Copy, Clone, Debug, PartialEq, FromPrimitive)]
 #[allow(non_camel_case_types)]
 #[repr(u8)]
@@ -81,103 +91,248 @@ pub enum UVCInterface {
 pub enum UVCControlInterface {
     Header(UVCControlInterfaceHeader),
     OutputTerminal(UVCControlInterfaceOutputTerminal),
+    //vendor specific and unknown input terminal types
     InputTerminal(UVCControlInterfaceInputTerminal),
+    CameraTerminal(UVCControlInterfaceCameraTerminal),
+    ConnectorTerminal(UVCControlInterfaceConnectorTerminal),
+    MediaTransportTerminal(UVCControlInterfaceMediaTransportTerminal),
+    SelectorUnit(UVCControlInterfaceSelectorUnit),
     ExtensionUnit(UVCControlInterfaceExtensionUnit),
     ProcessingUnit(UVCControlInterfaceProcessingUnit),
+    EncodingUnit(UVCControlInterfaceEncodingUnit),
 }
 
 #[derive(Debug, Clone)]
 pub enum UVCStreamingInterface {
     InputHeader(UVCVSInterfaceInputHeader),
-    OutputHeader,
+    OutputHeader(UVCVSInterfaceOutputHeader),
     StillImageFrame(UVCVSInterfaceStillImageFrame),
     FormatUncompressed(UVCVSInterfaceFormatUncompressed),
     FrameUncompressed(UVCVSInterfaceFrameUncompressed),
     FormatMjpeg(UVCVSInterfaceFormatMJPEG),
     FrameMjpeg(UVCVSInterfaceFrameMJPEG),
-    FormatMpeg2ts,
-    FormatDv,
+    FormatMpeg2ts(UVCVSInterfaceFormatMPEG2TS),
+    FormatDv(UVCVSInterfaceFormatDV),
     COLORFORMAT(UVCVSInterfaceColorFormat),
-    FormatFrameBased,
-    FrameFrameBased,
-    FormatStreamBased,
-    FormatH264,
-    FrameH264,
-    FormatH264Simulcast,
-    FormatVp8,
-    FrameVp8,
-    FormatVp8Simulcast,
+    FormatFrameBased(UVCVSInterfaceFormatFrameBased),
+    FrameFrameBased(UVCVSInterfaceFrameFrameBased),
+    FormatStreamBased(UVCVSInterfaceFormatStreamBased),
+    FormatH264(UVCVSInterfaceFormatH264),
+    FrameH264(UVCVSInterfaceFrameH264),
+    //simulcast formats share the layout of the plain ones
+    FormatH264Simulcast(UVCVSInterfaceFormatH264),
+    FormatVp8(UVCVSInterfaceFormatVP8),
+    FrameVp8(UVCVSInterfaceFrameVP8),
+    FormatVp8Simulcast(UVCVSInterfaceFormatVP8),
+    //undefined and reserved subtypes, kept as they came
+    Unknown(Vec<u8>),
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCControlInterfaceHeader {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    bcd_uvc: u16,
-    total_length: u16,
-    clock_frequency: u32,
-    in_collection: u8,
-    interface_nr: Vec<u8>,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub bcd_uvc: u16,
+    pub total_length: u16,
+    pub clock_frequency: u32,
+    pub in_collection: u8,
+    pub interface_nr: Vec<u8>,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCControlInterfaceInputTerminal {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    terminal_id: u8,
-    terminal_type: u16,
-    associated_terminal: u8,
-    string_index_terminal: u8,
-    reserved: Vec<u8>,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub terminal_id: u8,
+    pub terminal_type: u16,
+    pub associated_terminal: u8,
+    pub string_index_terminal: u8,
+    //vendor specific terminals may append their own fields
+    pub reserved: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCControlInterfaceCameraTerminal {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub terminal_id: u8,
+    pub terminal_type: u16,
+    pub associated_terminal: u8,
+    pub string_index_terminal: u8,
+    pub objective_focal_length_min: u16,
+    pub objective_focal_length_max: u16,
+    pub ocular_focal_length: u16,
+    pub control_size: u8,
+    pub controls: Vec<u8>,
+}
+
+//composite, s-video and component connectors carry no fields past iTerminal
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCControlInterfaceConnectorTerminal {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub terminal_id: u8,
+    pub terminal_type: u16,
+    pub associated_terminal: u8,
+    pub string_index_terminal: u8,
+}
+
+//input and output media transport terminals, only the output one has a source
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCControlInterfaceMediaTransportTerminal {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub terminal_id: u8,
+    pub terminal_type: u16,
+    pub associated_terminal: u8,
+    pub source_id: Option<u8>,
+    pub string_index_terminal: u8,
+    pub control_size: u8,
+    pub controls: Vec<u8>,
+    pub transport_mode_size: u8,
+    pub transport_modes: Vec<u8>,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCControlInterfaceOutputTerminal {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    terminal_id: u8,
-    terminal_type: u16,
-    associated_terminal: u8,
-    source_id: u8,
-    string_index_terminal: u8,
-    reserved: Vec<u8>,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub terminal_id: u8,
+    pub terminal_type: u16,
+    pub associated_terminal: u8,
+    pub source_id: u8,
+    pub string_index_terminal: u8,
+    pub reserved: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCControlInterfaceSelectorUnit {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub unit_id: u8,
+    pub nr_in_pins: u8,
+    pub source_ids: Vec<u8>,
+    pub selector: u8,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCControlInterfaceExtensionUnit {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    unit_id: u8,
-    guid_extension_code: [u8; 16],
-    num_controls: u8,
-    nr_in_pins: u8,
-    source_ids: Vec<u8>,
-    control_size: u8,
-    controls: Vec<u8>,
-    extension: u8,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub unit_id: u8,
+    pub guid_extension_code: [u8; 16],
+    pub num_controls: u8,
+    pub nr_in_pins: u8,
+    pub source_ids: Vec<u8>,
+    pub control_size: u8,
+    pub controls: Vec<u8>,
+    pub extension: u8,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCControlInterfaceProcessingUnit {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    unit_id: u8,
-    source_id: u8,
-    max_multiplier: u16,
-    control_size: u8,
-    controls: [u8; 3],
-    processing: u8,
-    video_standards: u8,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub unit_id: u8,
+    pub source_id: u8,
+    pub max_multiplier: u16,
+    pub control_size: u8,
+    pub controls: Vec<u8>,
+    pub processing: u8,
+    //absent before UVC 1.1
+    pub video_standards: Option<u8>,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCControlInterfaceEncodingUnit {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub unit_id: u8,
+    pub source_id: u8,
+    pub encoding: u8,
+    pub control_size: u8,
+    pub controls: Vec<u8>,
+    pub controls_runtime: Vec<u8>,
+}
+
+impl UVCControlInterfaceCameraTerminal {
+    pub fn controls(&self) -> Vec<CameraControl> {
+        controls_from_bitmap(&self.controls)
+    }
+}
+
+impl UVCControlInterfaceConnectorTerminal {
+    //COMPOSITE_CONNECTOR, SVIDEO_CONNECTOR or COMPONENT_CONNECTOR
+    pub fn connector(&self) -> UVCTerminalType {
+        UVCTerminalType::from_u16(self.terminal_type).unwrap()
+    }
+}
+
+impl UVCControlInterfaceMediaTransportTerminal {
+    pub fn controls(&self) -> Vec<MediaTransportControl> {
+        controls_from_bitmap(&self.controls)
+    }
+}
+
+impl UVCControlInterfaceProcessingUnit {
+    pub fn controls(&self) -> Vec<ProcessingControl> {
+        controls_from_bitmap(&self.controls)
+    }
+
+    pub fn video_standards(&self) -> Vec<VideoStandard> {
+        self.video_standards
+            .map(|standards| controls_from_bitmap(&[standards]))
+            .unwrap_or_default()
+    }
+}
+
+impl UVCControlInterfaceEncodingUnit {
+    pub fn controls(&self) -> Vec<EncodingControl> {
+        controls_from_bitmap(&self.controls)
+    }
+
+    //controls that may be changed while streaming
+    pub fn controls_runtime(&self) -> Vec<EncodingControl> {
+        controls_from_bitmap(&self.controls_runtime)
+    }
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+#[repr(u16)]
+pub enum UVCTerminalType {
+    TT_VENDOR_SPECIFIC = 0x0100,
+    TT_STREAMING = 0x0101,
+    ITT_VENDOR_SPECIFIC = 0x0200,
+    ITT_CAMERA = 0x0201,
+    ITT_MEDIA_TRANSPORT_INPUT = 0x0202,
+    OTT_VENDOR_SPECIFIC = 0x0300,
+    OTT_DISPLAY = 0x0301,
+    OTT_MEDIA_TRANSPORT_OUTPUT = 0x0302,
+    EXTERNAL_VENDOR_SPECIFIC = 0x0400,
+    COMPOSITE_CONNECTOR = 0x0401,
+    SVIDEO_CONNECTOR = 0x0402,
+    COMPONENT_CONNECTOR = 0x0403,
 }
 
 #[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
@@ -194,101 +349,319 @@ pub enum UVCCONTROLOutputTerminalType {
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCVSInterfaceInputHeader {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    num_formats: u8,
-    total_length: u16,
-    endpoint_address: u8,
-    info: u8,
-    terminal_link: u8,
-    still_capture_method: u8,
-    trigger_support: u8,
-    trigger_useage: u8,
-    control_size: u8,
-    interface_nr: Vec<u8>,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub num_formats: u8,
+    pub total_length: u16,
+    pub endpoint_address: u8,
+    pub info: u8,
+    pub terminal_link: u8,
+    pub still_capture_method: u8,
+    pub trigger_support: u8,
+    pub trigger_useage: u8,
+    pub control_size: u8,
+    pub interface_nr: Vec<u8>,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceOutputHeader {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub num_formats: u8,
+    pub total_length: u16,
+    pub endpoint_address: u8,
+    pub terminal_link: u8,
+    //UVC 1.0 output headers end after bTerminalLink
+    pub control_size: u8,
+    pub controls: Vec<u8>,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFormatMPEG2TS {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub data_offset: u8,
+    pub packet_length: u8,
+    pub stride_length: u8,
+    //added in UVC 1.1
+    pub guid_stride_format: Option<[u8; 16]>,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFormatDV {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub max_video_frame_buffer_size: u32,
+    pub format_type: u8,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+#[repr(u8)]
+pub enum DVFormatType {
+    SD_DV = 0,
+    SDL_DV = 1,
+    HD_DV = 2,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFormatStreamBased {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub guid_format: [u8; 16],
+    pub packet_length: u32,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+#[repr(C, packed)]
 pub struct UVCVSInterfaceFormatMJPEG {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    format_index: u8,
-    num_frame_descriptors: u8,
-    flags: u8,
-    default_frame_index: u8,
-    aspect_ratio_x: u8,
-    aspect_ratio_y: u8,
-    interlace_flags: u8,
-    is_copy_protect: u8,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub num_frame_descriptors: u8,
+    pub flags: u8,
+    pub default_frame_index: u8,
+    pub aspect_ratio_x: u8,
+    pub aspect_ratio_y: u8,
+    pub interlace_flags: u8,
+    pub is_copy_protect: u8,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCVSInterfaceFrameMJPEG {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    frame_index: u8,
-    capabilities: u8,
-    width: u16,
-    height: u16,
-    min_bit_rate: u32,
-    max_bit_rate: u32,
-    max_video_frame_buffer_size: u32,
-    default_frame_interval: u32,
-    frame_interval_type: u8,
-    frame_interval: FrameInterval,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub frame_index: u8,
+    pub capabilities: u8,
+    pub width: u16,
+    pub height: u16,
+    pub min_bit_rate: u32,
+    pub max_bit_rate: u32,
+    pub max_video_frame_buffer_size: u32,
+    pub default_frame_interval: u32,
+    pub frame_interval_type: u8,
+    pub frame_interval: FrameInterval,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCVSInterfaceStillImageFrame {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    endpoint_address: u8,
-    num_image_size_paterns: u8,
-    width_heights: Vec<(u16, u16)>,
-    num_compression_pattern: u8,
-    compressions: Vec<u8>,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub endpoint_address: u8,
+    pub num_image_size_paterns: u8,
+    pub width_heights: Vec<(u16, u16)>,
+    pub num_compression_pattern: u8,
+    pub compressions: Vec<u8>,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
+#[repr(C, packed)]
 pub struct UVCVSInterfaceFormatUncompressed {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    format_index: u8,
-    number_frame_descriptor: u8,
-    guid_format: [u8; 16],
-    bits_per_pixel: u8,
-    default_frame_index: u8,
-    aspect_ratio_x: u8,
-    aspect_ratio_y: u8,
-    m_interlace_flags: u8,
-    is_copy_protect: u8,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub number_frame_descriptor: u8,
+    pub guid_format: [u8; 16],
+    pub bits_per_pixel: u8,
+    pub default_frame_index: u8,
+    pub aspect_ratio_x: u8,
+    pub aspect_ratio_y: u8,
+    pub m_interlace_flags: u8,
+    pub is_copy_protect: u8,
 }
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
 pub struct UVCVSInterfaceFrameUncompressed {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    frame_index: u8,
-    capabilities: u8,
-    width: u16,
-    height: u16,
-    min_bit_rate: u32,
-    max_bit_rate: u32,
-    max_video_frame_buffer_size: u32,
-    default_frame_interval: u32,
-    frame_interval_type: u8,
-    frame_interval: FrameInterval,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub frame_index: u8,
+    pub capabilities: u8,
+    pub width: u16,
+    pub height: u16,
+    pub min_bit_rate: u32,
+    pub max_bit_rate: u32,
+    pub max_video_frame_buffer_size: u32,
+    pub default_frame_interval: u32,
+    pub frame_interval_type: u8,
+    pub frame_interval: FrameInterval,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFormatFrameBased {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub num_frame_descriptors: u8,
+    pub guid_format: [u8; 16],
+    pub bits_per_pixel: u8,
+    pub default_frame_index: u8,
+    pub aspect_ratio_x: u8,
+    pub aspect_ratio_y: u8,
+    pub interlace_flags: u8,
+    pub is_copy_protect: u8,
+    pub variable_size: u8,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFrameFrameBased {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub frame_index: u8,
+    pub capabilities: u8,
+    pub width: u16,
+    pub height: u16,
+    pub min_bit_rate: u32,
+    pub max_bit_rate: u32,
+    pub default_frame_interval: u32,
+    pub frame_interval_type: u8,
+    //0 for variable size formats
+    pub bytes_per_line: u32,
+    pub frame_interval: FrameInterval,
+}
+
+//the wMaxMBperSec fields are indexed by the number of simulcast resolutions minus one, 0 where
+//that many streams are not supported
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFormatH264 {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub num_frame_descriptors: u8,
+    pub default_frame_index: u8,
+    pub max_codec_config_delay: u8,
+    pub supported_slice_modes: u8,
+    pub supported_sync_frame_types: u8,
+    pub resolution_scaling: u8,
+    pub supported_rate_control_modes: u8,
+    pub max_mb_per_sec_no_scalability: [u16; 4],
+    pub max_mb_per_sec_temporal_scalability: [u16; 4],
+    pub max_mb_per_sec_temporal_quality_scalability: [u16; 4],
+    pub max_mb_per_sec_temporal_spatial_scalability: [u16; 4],
+    pub max_mb_per_sec_full_scalability: [u16; 4],
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFrameH264 {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub frame_index: u8,
+    pub width: u16,
+    pub height: u16,
+    pub sar_width: u16,
+    pub sar_height: u16,
+    pub profile: u16,
+    pub level_idc: u8,
+    pub constrained_toolset: u16,
+    pub supported_usages: u32,
+    pub capabilities: u16,
+    pub svc_capabilities: u32,
+    pub mvc_capabilities: u32,
+    pub min_bit_rate: u32,
+    pub max_bit_rate: u32,
+    pub default_frame_interval: u32,
+    pub num_frame_intervals: u8,
+    pub frame_interval: FrameInterval,
+}
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+#[repr(u16)]
+pub enum H264Profile {
+    ConstrainedBaseline = 0x4240,
+    Baseline = 0x4200,
+    Main = 0x4D00,
+    ConstrainedHigh = 0x640C,
+    High = 0x6400,
+    ScalableBaseline = 0x5300,
+    ScalableConstrainedBaseline = 0x5304,
+    ScalableHigh = 0x5600,
+    ScalableConstrainedHigh = 0x5604,
+    MultiviewHigh = 0x7600,
+    StereoHigh = 0x8000,
+}
+
+//bmSVCCapabilities, layer counts are stored minus one
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct H264SvcCapabilities {
+    pub max_temporal_layers: u8,
+    pub rewrite_support: bool,
+    pub max_cgs_layers: u8,
+    pub mgs_sublayers: u8,
+    pub additional_snr_scalability: bool,
+    pub max_spatial_layers: u8,
+}
+
+//bmMVCCapabilities
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct H264MvcCapabilities {
+    pub max_temporal_layers: u8,
+    pub max_views: u16,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFormatVP8 {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub format_index: u8,
+    pub num_frame_descriptors: u8,
+    pub default_frame_index: u8,
+    pub max_codec_config_delay: u8,
+    pub supported_partition_count: u8,
+    pub supported_sync_frame_types: u8,
+    pub resolution_scaling: u8,
+    pub supported_rate_control_modes: u8,
+    pub max_mb_per_sec: u16,
+}
+
+#[derive(Clone, Debug)]
+#[allow(non_camel_case_types)]
+pub struct UVCVSInterfaceFrameVP8 {
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub frame_index: u8,
+    pub width: u16,
+    pub height: u16,
+    pub supported_usages: u32,
+    pub capabilities: u16,
+    pub scalability_capabilities: u32,
+    pub min_bit_rate: u32,
+    pub max_bit_rate: u32,
+    pub default_frame_interval: u32,
+    pub num_frame_intervals: u8,
+    pub frame_interval: FrameInterval,
 }
 
 #[derive(Clone, Debug)]
@@ -300,13 +673,118 @@ pub enum FrameInterval {
 
 #[derive(Clone, Debug)]
 #[allow(non_camel_case_types)]
+#[repr(C, packed)]
 pub struct UVCVSInterfaceColorFormat {
-    length: u8,
-    descriptor_type: u8,
-    descriptor_sub_type: u8,
-    color_primaries: u8,
-    transfer_characteristics: u8,
-    matrix_coefficients: u8,
+    pub length: u8,
+    pub descriptor_type: u8,
+    pub descriptor_sub_type: u8,
+    pub color_primaries: u8,
+    pub transfer_characteristics: u8,
+    pub matrix_coefficients: u8,
+}
+
+impl UVCVSInterfaceInputHeader {
+    pub fn format_controls(&self) -> Vec<Vec<StreamingControl>> {
+        format_controls(&self.interface_nr, self.control_size)
+    }
+}
+
+impl UVCVSInterfaceOutputHeader {
+    pub fn format_controls(&self) -> Vec<Vec<StreamingControl>> {
+        format_controls(&self.controls, self.control_size)
+    }
+}
+
+//bmaControls holds one bControlSize wide bitmap per format
+fn format_controls(controls: &[u8], control_size: u8) -> Vec<Vec<StreamingControl>> {
+    if control_size == 0 {
+        return Vec::new();
+    }
+    controls
+        .chunks(control_size as usize)
+        .map(controls_from_bitmap)
+        .collect()
+}
+
+impl FrameInterval {
+    //`raw` starts at the first interval, a type of 0 means min, max and step follow
+    fn from_u8_array(raw: &[u8], frame_interval_type: u8) -> Self {
+        let interval = |i: usize| u32::from_ne_bytes(raw[i * 4..i * 4 + 4].try_into().unwrap());
+        match frame_interval_type {
+            0 => FrameInterval::Continuous((interval(0), interval(1), interval(2))),
+            other => FrameInterval::Discrete((0..other as usize).map(interval).collect()),
+        }
+    }
+
+    //the supported interval closest to `interval`, both in 100ns units
+    pub fn nearest(&self, interval: u32) -> Option<u32> {
+        match self {
+            FrameInterval::Continuous((min, max, step)) => {
+                let clamped = interval.clamp(*min, (*max).max(*min));
+                if *step == 0 {
+                    return Some(clamped);
+                }
+                let steps = (clamped - min + step / 2) / step;
+                Some((min + steps * step).min(*max))
+            }
+            FrameInterval::Discrete(intervals) => intervals
+                .iter()
+                .copied()
+                .min_by_key(|candidate| candidate.abs_diff(interval)),
+        }
+    }
+}
+
+impl UVCVSInterfaceFormatH264 {
+    //largest number of simultaneous streams the encoder can produce
+    pub fn max_simulcast_streams(&self) -> u8 {
+        self.max_mb_per_sec_no_scalability
+            .iter()
+            .rposition(|mb_per_sec| *mb_per_sec != 0)
+            .map_or(1, |i| i as u8 + 1)
+    }
+}
+
+impl UVCVSInterfaceFrameH264 {
+    pub fn h264_profile(&self) -> Option<H264Profile> {
+        H264Profile::from_u16(self.profile)
+    }
+
+    //bLevelIDC is ten times the level, 31 for level 3.1
+    pub fn level(&self) -> (u8, u8) {
+        (self.level_idc / 10, self.level_idc % 10)
+    }
+
+    pub fn svc_capabilities(&self) -> H264SvcCapabilities {
+        let bits = self.svc_capabilities;
+        H264SvcCapabilities {
+            max_temporal_layers: (bits & 0x7) as u8 + 1,
+            rewrite_support: bits & (1 << 3) != 0,
+            max_cgs_layers: ((bits >> 4) & 0x7) as u8 + 1,
+            mgs_sublayers: ((bits >> 7) & 0x7) as u8,
+            additional_snr_scalability: bits & (1 << 10) != 0,
+            max_spatial_layers: ((bits >> 11) & 0x7) as u8 + 1,
+        }
+    }
+
+    pub fn mvc_capabilities(&self) -> H264MvcCapabilities {
+        let bits = self.mvc_capabilities;
+        H264MvcCapabilities {
+            max_temporal_layers: (bits & 0x7) as u8 + 1,
+            max_views: ((bits >> 3) & 0xff) as u16 + 1,
+        }
+    }
+}
+
+impl UVCVSInterfaceFormatDV {
+    pub fn dv_format_type(&self) -> Option<DVFormatType> {
+        DVFormatType::from_u8(self.format_type & 0x7f)
+    }
+
+    //D7 tells 60Hz from 50Hz
+    pub fn is_60hz(&self) -> bool {
+        self.format_type & 0x80 != 0
+    }
 }
 
 impl UVCControlInterface {
@@ -343,45 +821,121 @@ impl UVCControlInterface {
                 }
             }),
             UVCControlInterfaceSubclass::INPUT_TERMINAL => {
-                Self::InputTerminal(UVCControlInterfaceInputTerminal {
-                    length: len,
-                    descriptor_type,
-                    descriptor_sub_type,
-                    terminal_id: raw[3],
-                    terminal_type: u16::from_ne_bytes(raw[4..=5].try_into().unwrap()),
-                    associated_terminal: raw[6],
-                    string_index_terminal: raw[7],
-                    reserved: raw[8..(len as usize)].to_vec(),
-                })
+                let terminal_type = u16::from_ne_bytes(raw[4..=5].try_into().unwrap());
+                match UVCTerminalType::from_u16(terminal_type) {
+                    Some(UVCTerminalType::ITT_CAMERA) => {
+                        let control_size = raw[14];
+                        Self::CameraTerminal(UVCControlInterfaceCameraTerminal {
+                            length: len,
+                            descriptor_type,
+                            descriptor_sub_type,
+                            terminal_id: raw[3],
+                            terminal_type,
+                            associated_terminal: raw[6],
+                            string_index_terminal: raw[7],
+                            objective_focal_length_min: u16::from_ne_bytes(
+                                raw[8..=9].try_into().unwrap(),
+                            ),
+                            objective_focal_length_max: u16::from_ne_bytes(
+                                raw[10..=11].try_into().unwrap(),
+                            ),
+                            ocular_focal_length: u16::from_ne_bytes(
+                                raw[12..=13].try_into().unwrap(),
+                            ),
+                            control_size,
+                            controls: raw[15..15 + control_size as usize].to_vec(),
+                        })
+                    }
+                    Some(UVCTerminalType::ITT_MEDIA_TRANSPORT_INPUT) => {
+                        Self::MediaTransportTerminal(
+                            UVCControlInterfaceMediaTransportTerminal::new(raw, None, 7),
+                        )
+                    }
+                    Some(
+                        UVCTerminalType::COMPOSITE_CONNECTOR
+                        | UVCTerminalType::SVIDEO_CONNECTOR
+                        | UVCTerminalType::COMPONENT_CONNECTOR,
+                    ) => Self::ConnectorTerminal(UVCControlInterfaceConnectorTerminal {
+                        length: len,
+                        descriptor_type,
+                        descriptor_sub_type,
+                        terminal_id: raw[3],
+                        terminal_type,
+                        associated_terminal: raw[6],
+                        string_index_terminal: raw[7],
+                    }),
+                    _ => Self::InputTerminal(UVCControlInterfaceInputTerminal {
+                        length: len,
+                        descriptor_type,
+                        descriptor_sub_type,
+                        terminal_id: raw[3],
+                        terminal_type,
+                        associated_terminal: raw[6],
+                        string_index_terminal: raw[7],
+                        reserved: raw[8..(len as usize)].to_vec(),
+                    }),
+                }
             }
             UVCControlInterfaceSubclass::OUTPUT_TERMINAL => {
-                Self::OutputTerminal(UVCControlInterfaceOutputTerminal {
+                let terminal_type = u16::from_ne_bytes(raw[4..=5].try_into().unwrap());
+                match UVCTerminalType::from_u16(terminal_type) {
+                    Some(UVCTerminalType::OTT_MEDIA_TRANSPORT_OUTPUT) => {
+                        Self::MediaTransportTerminal(
+                            UVCControlInterfaceMediaTransportTerminal::new(raw, Some(raw[7]), 8),
+                        )
+                    }
+                    _ => Self::OutputTerminal(UVCControlInterfaceOutputTerminal {
+                        length: len,
+                        descriptor_type,
+                        descriptor_sub_type,
+                        terminal_id: raw[3],
+                        terminal_type,
+                        associated_terminal: raw[6],
+                        source_id: raw[7],
+                        string_index_terminal: raw[8],
+                        reserved: raw[9..(len as usize)].to_vec(),
+                    }),
+                }
+            }
+            UVCControlInterfaceSubclass::SELECTOR_UNIT => {
+                let nr_in_pins = raw[4];
+                let last_in_pin = 5 + nr_in_pins as usize;
+                Self::SelectorUnit(UVCControlInterfaceSelectorUnit {
                     length: len,
                     descriptor_type,
                     descriptor_sub_type,
-                    terminal_id: raw[3],
-                    terminal_type: u16::from_ne_bytes(raw[4..=5].try_into().unwrap()),
-                    associated_terminal: raw[6],
-                    source_id: raw[7],
-                    string_index_terminal: raw[8],
-                    reserved: raw[9..(len as usize)].to_vec(),
+                    unit_id: raw[3],
+                    nr_in_pins,
+                    source_ids: raw[5..last_in_pin].to_vec(),
+                    selector: raw[last_in_pin],
                 })
             }
-            UVCControlInterfaceSubclass::SELECTOR_UNIT => todo!(),
             UVCControlInterfaceSubclass::PROCESSING_UNIT => {
-                Self::ProcessingUnit({
-                    //this descriptor may change with different uvc version
-                    unsafe { ptr::read((raw as *const [u8]).cast()) }
+                //UVC 1.0 stops after iProcessing, later versions add bmVideoStandards
+                let control_size = raw[7];
+                let last_control = 8 + control_size as usize;
+                Self::ProcessingUnit(UVCControlInterfaceProcessingUnit {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type,
+                    unit_id: raw[3],
+                    source_id: raw[4],
+                    max_multiplier: u16::from_ne_bytes(raw[5..=6].try_into().unwrap()),
+                    control_size,
+                    controls: raw[8..last_control].to_vec(),
+                    processing: raw[last_control],
+                    video_standards: (last_control + 1 < len as usize)
+                        .then(|| raw[last_control + 1]),
                 })
             }
             UVCControlInterfaceSubclass::EXTENSION_UNIT => Self::ExtensionUnit({
                 let nr_in_pins = raw[21];
-                let last_in_pin = 21 + nr_in_pins as usize;
-                let in_pins = raw[21..last_in_pin].to_vec();
+                let last_in_pin = 22 + nr_in_pins as usize;
+                let in_pins = raw[22..last_in_pin].to_vec();
 
                 let control_size = raw[last_in_pin];
-                let last_control = last_in_pin + control_size as usize;
-                let controls = raw[last_in_pin..last_control].to_vec();
+                let last_control = last_in_pin + 1 + control_size as usize;
+                let controls = raw[last_in_pin + 1..last_control].to_vec();
 
                 UVCControlInterfaceExtensionUnit {
                     length: len,
@@ -401,7 +955,99 @@ impl UVCControlInterface {
                     extension: raw[last_control],
                 }
             }),
-            UVCControlInterfaceSubclass::ENCODING_UNIT => todo!(),
+            UVCControlInterfaceSubclass::ENCODING_UNIT => {
+                //bmControls and bmControlsRuntime share bControlSize
+                let control_size = raw[6];
+                let last_control = 7 + control_size as usize;
+                Self::EncodingUnit(UVCControlInterfaceEncodingUnit {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type,
+                    unit_id: raw[3],
+                    source_id: raw[4],
+                    encoding: raw[5],
+                    control_size,
+                    controls: raw[7..last_control].to_vec(),
+                    controls_runtime: raw[last_control..last_control + control_size as usize]
+                        .to_vec(),
+                })
+            }
+        }
+    }
+}
+
+impl UVCControlInterfaceMediaTransportTerminal {
+    //`string_index` is the offset of iTerminal, one further on output terminals
+    fn new(raw: &[u8], source_id: Option<u8>, string_index: usize) -> Self {
+        let control_size = raw[string_index + 1];
+        let last_control = string_index + 2 + control_size as usize;
+        let transport_mode_size = raw.get(last_control).copied().unwrap_or(0);
+        let transport_modes = raw
+            .get(last_control + 1..last_control + 1 + transport_mode_size as usize)
+            .unwrap_or_default()
+            .to_vec();
+        Self {
+            length: raw[0],
+            descriptor_type: raw[1],
+            descriptor_sub_type: raw[2],
+            terminal_id: raw[3],
+            terminal_type: u16::from_ne_bytes(raw[4..=5].try_into().unwrap()),
+            associated_terminal: raw[6],
+            source_id,
+            string_index_terminal: raw[string_index],
+            control_size,
+            controls: raw[string_index + 2..last_control].to_vec(),
+            transport_mode_size,
+            transport_modes,
+        }
+    }
+}
+
+impl UVCVSInterfaceFormatH264 {
+    fn from_u8_array(raw: &[u8]) -> Self {
+        let max_mb_per_sec = |first: usize| -> [u16; 4] {
+            core::array::from_fn(|i| {
+                let at = first + i * 2;
+                u16::from_ne_bytes(raw[at..at + 2].try_into().unwrap())
+            })
+        };
+        Self {
+            length: raw[0],
+            descriptor_type: raw[1],
+            descriptor_sub_type: raw[2],
+            format_index: raw[3],
+            num_frame_descriptors: raw[4],
+            default_frame_index: raw[5],
+            max_codec_config_delay: raw[6],
+            supported_slice_modes: raw[7],
+            supported_sync_frame_types: raw[8],
+            resolution_scaling: raw[9],
+            //raw[10] is reserved
+            supported_rate_control_modes: raw[11],
+            max_mb_per_sec_no_scalability: max_mb_per_sec(12),
+            max_mb_per_sec_temporal_scalability: max_mb_per_sec(20),
+            max_mb_per_sec_temporal_quality_scalability: max_mb_per_sec(28),
+            max_mb_per_sec_temporal_spatial_scalability: max_mb_per_sec(36),
+            max_mb_per_sec_full_scalability: max_mb_per_sec(44),
+        }
+    }
+}
+
+impl UVCVSInterfaceFormatVP8 {
+    fn from_u8_array(raw: &[u8]) -> Self {
+        Self {
+            length: raw[0],
+            descriptor_type: raw[1],
+            descriptor_sub_type: raw[2],
+            format_index: raw[3],
+            num_frame_descriptors: raw[4],
+            default_frame_index: raw[5],
+            max_codec_config_delay: raw[6],
+            supported_partition_count: raw[7],
+            supported_sync_frame_types: raw[8],
+            resolution_scaling: raw[9],
+            supported_rate_control_modes: raw[10],
+            max_mb_per_sec: u16::from_ne_bytes(raw[11..=12].try_into().unwrap()),
         }
     }
 }
@@ -411,7 +1057,9 @@ impl UVCStreamingInterface {
         trace!("buffer:{:?}", raw);
         let len = raw[0];
         let descriptor_type = raw[1];
-        let descriptor_sub_type = UVCVSInterfaceSubclass::from_u8(raw[2]).unwrap();
+        let Some(descriptor_sub_type) = UVCVSInterfaceSubclass::from_u8(raw[2]) else {
+            return Self::Unknown(raw.to_vec());
+        };
         trace!("subtype{:?}", descriptor_sub_type);
         match descriptor_sub_type {
             UVCVSInterfaceSubclass::INPUT_HEADER => Self::InputHeader({
@@ -438,19 +1086,8 @@ impl UVCStreamingInterface {
             UVCVSInterfaceSubclass::FRAME_MJPEG => {
                 let frame_interval_type = raw[25];
 
-                let frame_interval = match frame_interval_type {
-                    0 => FrameInterval::Continuous((
-                        u32::from_ne_bytes(raw[26..30].try_into().unwrap()),
-                        u32::from_ne_bytes(raw[30..34].try_into().unwrap()),
-                        u32::from_ne_bytes(raw[34..(len as usize)].try_into().unwrap()),
-                    )),
-                    other => FrameInterval::Discrete(
-                        raw[26..((26 + other * 4) as usize)]
-                            .chunks(4)
-                            .map(|c| u32::from_ne_bytes(c.try_into().unwrap()))
-                            .collect(),
-                    ),
-                };
+                let frame_interval =
+                    FrameInterval::from_u8_array(&raw[26..len as usize], frame_interval_type);
 
                 Self::FrameMjpeg(UVCVSInterfaceFrameMJPEG {
                     length: len,
@@ -500,19 +1137,8 @@ impl UVCStreamingInterface {
             UVCVSInterfaceSubclass::FRAME_UNCOMPRESSED => {
                 let frame_interval_type = raw[25];
 
-                let frame_interval = match frame_interval_type {
-                    0 => FrameInterval::Continuous((
-                        u32::from_ne_bytes(raw[26..30].try_into().unwrap()),
-                        u32::from_ne_bytes(raw[30..34].try_into().unwrap()),
-                        u32::from_ne_bytes(raw[34..(len as usize)].try_into().unwrap()),
-                    )),
-                    other => FrameInterval::Discrete(
-                        raw[26..((26 + other * 4) as usize)]
-                            .chunks(4)
-                            .map(|c| u32::from_ne_bytes(c.try_into().unwrap()))
-                            .collect(),
-                    ),
-                };
+                let frame_interval =
+                    FrameInterval::from_u8_array(&raw[26..len as usize], frame_interval_type);
 
                 Self::FrameUncompressed(UVCVSInterfaceFrameUncompressed {
                     length: len,
@@ -535,8 +1161,1035 @@ impl UVCStreamingInterface {
             UVCVSInterfaceSubclass::COLORFORMAT => {
                 Self::COLORFORMAT(unsafe { ptr::read((raw as *const [u8]).cast()) })
             }
+            UVCVSInterfaceSubclass::OUTPUT_HEADER => {
+                let control_size = if len > 8 { raw[8] } else { 0 };
+                Self::OutputHeader(UVCVSInterfaceOutputHeader {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    num_formats: raw[3],
+                    total_length: u16::from_ne_bytes(raw[4..=5].try_into().unwrap()),
+                    endpoint_address: raw[6],
+                    terminal_link: raw[7],
+                    control_size,
+                    controls: if len > 9 {
+                        raw[9..len as usize].to_vec()
+                    } else {
+                        Vec::new()
+                    },
+                })
+            }
+            UVCVSInterfaceSubclass::FORMAT_MPEG2TS => {
+                Self::FormatMpeg2ts(UVCVSInterfaceFormatMPEG2TS {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    format_index: raw[3],
+                    data_offset: raw[4],
+                    packet_length: raw[5],
+                    stride_length: raw[6],
+                    guid_stride_format: (len >= 23).then(|| raw[7..23].try_into().unwrap()),
+                })
+            }
+            UVCVSInterfaceSubclass::FORMAT_DV => Self::FormatDv(UVCVSInterfaceFormatDV {
+                length: len,
+                descriptor_type,
+                descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                format_index: raw[3],
+                max_video_frame_buffer_size: u32::from_ne_bytes(raw[4..8].try_into().unwrap()),
+                format_type: raw[8],
+            }),
+            UVCVSInterfaceSubclass::FORMAT_STREAM_BASED => {
+                Self::FormatStreamBased(UVCVSInterfaceFormatStreamBased {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    format_index: raw[3],
+                    guid_format: raw[4..20].try_into().unwrap(),
+                    packet_length: u32::from_ne_bytes(raw[20..24].try_into().unwrap()),
+                })
+            }
+            UVCVSInterfaceSubclass::FORMAT_FRAME_BASED => {
+                Self::FormatFrameBased(UVCVSInterfaceFormatFrameBased {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    format_index: raw[3],
+                    num_frame_descriptors: raw[4],
+                    guid_format: raw[5..21].try_into().unwrap(),
+                    bits_per_pixel: raw[21],
+                    default_frame_index: raw[22],
+                    aspect_ratio_x: raw[23],
+                    aspect_ratio_y: raw[24],
+                    interlace_flags: raw[25],
+                    is_copy_protect: raw[26],
+                    variable_size: raw[27],
+                })
+            }
+            UVCVSInterfaceSubclass::FRAME_FRAME_BASED => {
+                let frame_interval_type = raw[21];
+                Self::FrameFrameBased(UVCVSInterfaceFrameFrameBased {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    frame_index: raw[3],
+                    capabilities: raw[4],
+                    width: u16::from_ne_bytes(raw[5..=6].try_into().unwrap()),
+                    height: u16::from_ne_bytes(raw[7..=8].try_into().unwrap()),
+                    min_bit_rate: u32::from_ne_bytes(raw[9..13].try_into().unwrap()),
+                    max_bit_rate: u32::from_ne_bytes(raw[13..17].try_into().unwrap()),
+                    default_frame_interval: u32::from_ne_bytes(raw[17..21].try_into().unwrap()),
+                    frame_interval_type,
+                    bytes_per_line: u32::from_ne_bytes(raw[22..26].try_into().unwrap()),
+                    frame_interval: FrameInterval::from_u8_array(
+                        &raw[26..len as usize],
+                        frame_interval_type,
+                    ),
+                })
+            }
+            UVCVSInterfaceSubclass::FORMAT_H264 => {
+                Self::FormatH264(UVCVSInterfaceFormatH264::from_u8_array(raw))
+            }
+            UVCVSInterfaceSubclass::FORMAT_H264_SIMULCAST => {
+                Self::FormatH264Simulcast(UVCVSInterfaceFormatH264::from_u8_array(raw))
+            }
+            UVCVSInterfaceSubclass::FRAME_H264 => {
+                let num_frame_intervals = raw[43];
+                Self::FrameH264(UVCVSInterfaceFrameH264 {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    frame_index: raw[3],
+                    width: u16::from_ne_bytes(raw[4..=5].try_into().unwrap()),
+                    height: u16::from_ne_bytes(raw[6..=7].try_into().unwrap()),
+                    sar_width: u16::from_ne_bytes(raw[8..=9].try_into().unwrap()),
+                    sar_height: u16::from_ne_bytes(raw[10..=11].try_into().unwrap()),
+                    profile: u16::from_ne_bytes(raw[12..=13].try_into().unwrap()),
+                    level_idc: raw[14],
+                    constrained_toolset: u16::from_ne_bytes(raw[15..=16].try_into().unwrap()),
+                    supported_usages: u32::from_ne_bytes(raw[17..21].try_into().unwrap()),
+                    capabilities: u16::from_ne_bytes(raw[21..=22].try_into().unwrap()),
+                    svc_capabilities: u32::from_ne_bytes(raw[23..27].try_into().unwrap()),
+                    mvc_capabilities: u32::from_ne_bytes(raw[27..31].try_into().unwrap()),
+                    min_bit_rate: u32::from_ne_bytes(raw[31..35].try_into().unwrap()),
+                    max_bit_rate: u32::from_ne_bytes(raw[35..39].try_into().unwrap()),
+                    default_frame_interval: u32::from_ne_bytes(raw[39..43].try_into().unwrap()),
+                    num_frame_intervals,
+                    //H.264 frames only list discrete intervals
+                    frame_interval: FrameInterval::Discrete(
+                        raw[44..44 + 4 * num_frame_intervals as usize]
+                            .chunks(4)
+                            .map(|c| u32::from_ne_bytes(c.try_into().unwrap()))
+                            .collect(),
+                    ),
+                })
+            }
+            UVCVSInterfaceSubclass::FORMAT_VP8 => {
+                Self::FormatVp8(UVCVSInterfaceFormatVP8::from_u8_array(raw))
+            }
+            UVCVSInterfaceSubclass::FORMAT_VP8_SIMULCAST => {
+                Self::FormatVp8Simulcast(UVCVSInterfaceFormatVP8::from_u8_array(raw))
+            }
+            UVCVSInterfaceSubclass::FRAME_VP8 => {
+                let num_frame_intervals = raw[30];
+                Self::FrameVp8(UVCVSInterfaceFrameVP8 {
+                    length: len,
+                    descriptor_type,
+                    descriptor_sub_type: descriptor_sub_type.to_u8().unwrap(),
+                    frame_index: raw[3],
+                    width: u16::from_ne_bytes(raw[4..=5].try_into().unwrap()),
+                    height: u16::from_ne_bytes(raw[6..=7].try_into().unwrap()),
+                    supported_usages: u32::from_ne_bytes(raw[8..12].try_into().unwrap()),
+                    capabilities: u16::from_ne_bytes(raw[12..=13].try_into().unwrap()),
+                    scalability_capabilities: u32::from_ne_bytes(raw[14..18].try_into().unwrap()),
+                    min_bit_rate: u32::from_ne_bytes(raw[18..22].try_into().unwrap()),
+                    max_bit_rate: u32::from_ne_bytes(raw[22..26].try_into().unwrap()),
+                    default_frame_interval: u32::from_ne_bytes(raw[26..30].try_into().unwrap()),
+                    num_frame_intervals,
+                    frame_interval: FrameInterval::Discrete(
+                        raw[31..31 + 4 * num_frame_intervals as usize]
+                            .chunks(4)
+                            .map(|c| u32::from_ne_bytes(c.try_into().unwrap()))
+                            .collect(),
+                    ),
+                })
+            }
+            UVCVSInterfaceSubclass::UNDEFINED => Self::Unknown(raw.to_vec()),
+        }
+    }
+}
 
-            todo => todo!("impl:{:?}", todo),
+//"{32595559-0000-0010-8000-00aa00389b71}", the first three groups are little endian
+pub(crate) struct GuidText<'a>(pub(crate) &'a [u8; 16]);
+
+impl fmt::Display for GuidText<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let g = self.0;
+        write!(
+            f,
+            "{{{:02x}{:02x}{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-{:02x}{:02x}-",
+            g[3], g[2], g[1], g[0], g[5], g[4], g[7], g[6], g[8], g[9]
+        )?;
+        g[10..].iter().try_for_each(|b| write!(f, "{:02x}", b))?;
+        write!(f, "}}")
+    }
+}
+
+//names used by lsusb for wTerminalType
+fn terminal_type_name(terminal_type: u16) -> &'static str {
+    match terminal_type {
+        0x0100 => "USB Vendor Specific",
+        0x0101 => "USB Streaming",
+        0x0200 => "Vendor Specific",
+        0x0201 => "Camera Sensor",
+        0x0202 => "Sequential Media",
+        0x0300 => "Vendor Specific",
+        0x0301 => "Generic Display",
+        0x0302 => "Sequential Media",
+        0x0400 => "External Vendor Specific",
+        0x0401 => "Composite Video",
+        0x0402 => "S-Video",
+        0x0403 => "Component Video",
+        _ => "",
+    }
+}
+
+fn dump_interlace_flags(w: &mut LsusbWriter, flags: u8) -> fmt::Result {
+    w.field("bmInterlaceFlags", format_args!("{:#04x}", flags))?;
+    w.note(format_args!(
+        "Interlaced stream or variable: {}",
+        if flags & 0x1 != 0 { "Yes" } else { "No" }
+    ))?;
+    w.note(format_args!(
+        "Fields per frame: {} fields",
+        if flags & 0x2 != 0 { 1 } else { 2 }
+    ))?;
+    w.note(format_args!(
+        "Field 1 first: {}",
+        if flags & 0x4 != 0 { "Yes" } else { "No" }
+    ))?;
+    w.note(format_args!(
+        "Field pattern: {}",
+        [
+            "Field 1 only",
+            "Field 2 only",
+            "Regular pattern of fields 1 and 2",
+            "Random pattern of fields 1 and 2",
+        ][((flags >> 4) & 0x3) as usize]
+    ))
+}
+
+fn dump_frame_intervals(w: &mut LsusbWriter, frame_interval: &FrameInterval) -> fmt::Result {
+    match frame_interval {
+        FrameInterval::Continuous((min, max, step)) => {
+            w.field("dwMinFrameInterval", min)?;
+            w.field("dwMaxFrameInterval", max)?;
+            w.field("dwFrameIntervalStep", step)
+        }
+        FrameInterval::Discrete(intervals) => intervals
+            .iter()
+            .enumerate()
+            .try_for_each(|(i, interval)| w.field(&format!("dwFrameInterval({:2})", i), interval)),
+    }
+}
+
+impl UVCInterface {
+    pub(crate) fn dump_lsusb(&self, w: &mut LsusbWriter) -> fmt::Result {
+        match self {
+            UVCInterface::Control(control) => control.dump_lsusb(w),
+            UVCInterface::Streaming(streaming) => streaming.dump_lsusb(w),
         }
     }
 }
+
+impl UVCControlInterface {
+    fn dump_lsusb(&self, w: &mut LsusbWriter) -> fmt::Result {
+        w.block(6, LsusbWriter::COLUMN, "VideoControl Interface Descriptor:")?;
+        match self {
+            UVCControlInterface::Header(header) => {
+                dump_vc_header(
+                    w,
+                    header.length,
+                    header.descriptor_type,
+                    header.descriptor_sub_type,
+                )?;
+                w.field("bcdUVC", BcdVersion::from_raw(header.bcd_uvc))?;
+                w.field("wTotalLength", format_args!("{:#06x}", header.total_length))?;
+                w.field_unit(
+                    "dwClockFrequency",
+                    header.clock_frequency / 1_000_000,
+                    format_args!(".{:06}MHz", header.clock_frequency % 1_000_000),
+                )?;
+                w.field("bInCollection", header.in_collection)?;
+                header
+                    .interface_nr
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(i, nr)| w.field(&format!("baInterfaceNr({:2})", i), nr))
+            }
+            UVCControlInterface::InputTerminal(terminal) => {
+                dump_vc_header(
+                    w,
+                    terminal.length,
+                    terminal.descriptor_type,
+                    terminal.descriptor_sub_type,
+                )?;
+                w.field("bTerminalID", terminal.terminal_id)?;
+                w.field_text(
+                    "wTerminalType",
+                    format_args!("{:#06x}", terminal.terminal_type),
+                    terminal_type_name(terminal.terminal_type),
+                )?;
+                w.field("bAssocTerminal", terminal.associated_terminal)?;
+                w.field_string("iTerminal", terminal.string_index_terminal)?;
+                if !terminal.reserved.is_empty() {
+                    w.note(format_args!("{:02x?}", terminal.reserved))?;
+                }
+                Ok(())
+            }
+            UVCControlInterface::ConnectorTerminal(terminal) => {
+                dump_vc_header(
+                    w,
+                    terminal.length,
+                    terminal.descriptor_type,
+                    terminal.descriptor_sub_type,
+                )?;
+                w.field("bTerminalID", terminal.terminal_id)?;
+                w.field_text(
+                    "wTerminalType",
+                    format_args!("{:#06x}", terminal.terminal_type),
+                    terminal_type_name(terminal.terminal_type),
+                )?;
+                w.field("bAssocTerminal", terminal.associated_terminal)?;
+                w.field_string("iTerminal", terminal.string_index_terminal)
+            }
+            UVCControlInterface::OutputTerminal(terminal) => {
+                dump_vc_header(
+                    w,
+                    terminal.length,
+                    terminal.descriptor_type,
+                    terminal.descriptor_sub_type,
+                )?;
+                w.field("bTerminalID", terminal.terminal_id)?;
+                w.field_text(
+                    "wTerminalType",
+                    format_args!("{:#06x}", terminal.terminal_type),
+                    terminal_type_name(terminal.terminal_type),
+                )?;
+                w.field("bAssocTerminal", terminal.associated_terminal)?;
+                w.field("bSourceID", terminal.source_id)?;
+                w.field_string("iTerminal", terminal.string_index_terminal)
+            }
+            UVCControlInterface::ExtensionUnit(unit) => {
+                dump_vc_header(
+                    w,
+                    unit.length,
+                    unit.descriptor_type,
+                    unit.descriptor_sub_type,
+                )?;
+                w.field("bUnitID", unit.unit_id)?;
+                w.field_unit("guidExtensionCode", "", GuidText(&unit.guid_extension_code))?;
+                w.field("bNumControls", unit.num_controls)?;
+                w.field("bNrInPins", unit.nr_in_pins)?;
+                for (i, source) in unit.source_ids.iter().enumerate() {
+                    w.field(&format!("baSourceID({:2})", i), source)?;
+                }
+                w.field("bControlSize", unit.control_size)?;
+                for (i, control) in unit.controls.iter().enumerate() {
+                    w.field(
+                        &format!("bmControls({:2})", i),
+                        format_args!("{:#04x}", control),
+                    )?;
+                }
+                w.field_string("iExtension", unit.extension)
+            }
+            UVCControlInterface::ProcessingUnit(unit) => {
+                dump_vc_header(
+                    w,
+                    unit.length,
+                    unit.descriptor_type,
+                    unit.descriptor_sub_type,
+                )?;
+                w.field("bUnitID", unit.unit_id)?;
+                w.field("bSourceID", unit.source_id)?;
+                w.field("wMaxMultiplier", unit.max_multiplier)?;
+                w.field("bControlSize", unit.control_size)?;
+                dump_control_bitmap(
+                    w,
+                    "bmControls",
+                    &unit.controls,
+                    unit.controls().iter().map(|control| control.name()),
+                )?;
+                w.field_string("iProcessing", unit.processing)?;
+                match unit.video_standards {
+                    Some(video_standards) => {
+                        w.field("bmVideoStandards", format_args!("{:#04x}", video_standards))?;
+                        unit.video_standards()
+                            .iter()
+                            .try_for_each(|standard| w.note(standard.name()))
+                    }
+                    None => Ok(()),
+                }
+            }
+            UVCControlInterface::CameraTerminal(terminal) => {
+                dump_vc_header(
+                    w,
+                    terminal.length,
+                    terminal.descriptor_type,
+                    terminal.descriptor_sub_type,
+                )?;
+                w.field("bTerminalID", terminal.terminal_id)?;
+                w.field_text(
+                    "wTerminalType",
+                    format_args!("{:#06x}", terminal.terminal_type),
+                    terminal_type_name(terminal.terminal_type),
+                )?;
+                w.field("bAssocTerminal", terminal.associated_terminal)?;
+                w.field_string("iTerminal", terminal.string_index_terminal)?;
+                w.field(
+                    "wObjectiveFocalLengthMin",
+                    terminal.objective_focal_length_min,
+                )?;
+                w.field(
+                    "wObjectiveFocalLengthMax",
+                    terminal.objective_focal_length_max,
+                )?;
+                w.field("wOcularFocalLength", terminal.ocular_focal_length)?;
+                w.field("bControlSize", terminal.control_size)?;
+                dump_control_bitmap(
+                    w,
+                    "bmControls",
+                    &terminal.controls,
+                    terminal.controls().iter().map(|control| control.name()),
+                )
+            }
+            UVCControlInterface::MediaTransportTerminal(terminal) => {
+                dump_vc_header(
+                    w,
+                    terminal.length,
+                    terminal.descriptor_type,
+                    terminal.descriptor_sub_type,
+                )?;
+                w.field("bTerminalID", terminal.terminal_id)?;
+                w.field_text(
+                    "wTerminalType",
+                    format_args!("{:#06x}", terminal.terminal_type),
+                    terminal_type_name(terminal.terminal_type),
+                )?;
+                w.field("bAssocTerminal", terminal.associated_terminal)?;
+                if let Some(source_id) = terminal.source_id {
+                    w.field("bSourceID", source_id)?;
+                }
+                w.field_string("iTerminal", terminal.string_index_terminal)?;
+                w.field("bControlSize", terminal.control_size)?;
+                dump_control_bitmap(
+                    w,
+                    "bmControls",
+                    &terminal.controls,
+                    terminal.controls().iter().map(|control| control.name()),
+                )?;
+                w.field("bTransportModeSize", terminal.transport_mode_size)?;
+                w.field(
+                    "bmTransportModes",
+                    format_args!("{:02x?}", terminal.transport_modes),
+                )
+            }
+            UVCControlInterface::SelectorUnit(unit) => {
+                dump_vc_header(
+                    w,
+                    unit.length,
+                    unit.descriptor_type,
+                    unit.descriptor_sub_type,
+                )?;
+                w.field("bUnitID", unit.unit_id)?;
+                w.field("bNrInPins", unit.nr_in_pins)?;
+                for (i, source) in unit.source_ids.iter().enumerate() {
+                    w.field(&format!("baSourceID({:2})", i), source)?;
+                }
+                w.field_string("iSelector", unit.selector)
+            }
+            UVCControlInterface::EncodingUnit(unit) => {
+                dump_vc_header(
+                    w,
+                    unit.length,
+                    unit.descriptor_type,
+                    unit.descriptor_sub_type,
+                )?;
+                w.field("bUnitID", unit.unit_id)?;
+                w.field("bSourceID", unit.source_id)?;
+                w.field_string("iEncoding", unit.encoding)?;
+                w.field("bControlSize", unit.control_size)?;
+                dump_control_bitmap(
+                    w,
+                    "bmControls",
+                    &unit.controls,
+                    unit.controls().iter().map(|control| control.name()),
+                )?;
+                dump_control_bitmap(
+                    w,
+                    "bmControlsRuntime",
+                    &unit.controls_runtime,
+                    unit.controls_runtime().iter().map(|control| control.name()),
+                )
+            }
+        }
+    }
+}
+
+//the bitmap as one little endian number followed by the names of its set bits
+fn dump_control_bitmap<'a>(
+    w: &mut LsusbWriter,
+    name: &str,
+    bitmap: &[u8],
+    mut names: impl Iterator<Item = &'a str>,
+) -> fmt::Result {
+    let value = bitmap
+        .iter()
+        .take(8)
+        .rev()
+        .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+    w.field(name, format_args!("0x{:08x}", value))?;
+    names.try_for_each(|name| w.note(name))
+}
+
+//one bmaControls entry per format, each bControlSize bytes wide
+fn dump_format_controls(w: &mut LsusbWriter, controls: &[u8], control_size: u8) -> fmt::Result {
+    if control_size == 0 {
+        return Ok(());
+    }
+    controls
+        .chunks(control_size as usize)
+        .enumerate()
+        .try_for_each(|(i, bitmap)| {
+            let value = bitmap
+                .iter()
+                .take(8)
+                .rev()
+                .fold(0u64, |value, byte| (value << 8) | *byte as u64);
+            w.field(&format!("bmaControls({:2})", i), value)
+        })
+}
+
+impl UVCStreamingInterface {
+    fn dump_lsusb(&self, w: &mut LsusbWriter) -> fmt::Result {
+        w.block(
+            6,
+            LsusbWriter::WIDE_COLUMN,
+            "VideoStreaming Interface Descriptor:",
+        )?;
+        match self {
+            UVCStreamingInterface::InputHeader(header) => {
+                dump_vs_header(
+                    w,
+                    header.length,
+                    header.descriptor_type,
+                    header.descriptor_sub_type,
+                )?;
+                w.field("bNumFormats", header.num_formats)?;
+                w.field("wTotalLength", format_args!("{:#06x}", header.total_length))?;
+                w.field_unit(
+                    "bEndpointAddress",
+                    format_args!("{:#04x}", header.endpoint_address),
+                    EndpointAddressText(header.endpoint_address),
+                )?;
+                w.field("bmInfo", header.info)?;
+                w.field("bTerminalLink", header.terminal_link)?;
+                w.field("bStillCaptureMethod", header.still_capture_method)?;
+                w.field("bTriggerSupport", header.trigger_support)?;
+                w.field("bTriggerUsage", header.trigger_useage)?;
+                w.field("bControlSize", header.control_size)?;
+                dump_format_controls(w, &header.interface_nr, header.control_size)
+            }
+            UVCStreamingInterface::FormatUncompressed(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field("bNumFrameDescriptors", format.number_frame_descriptor)?;
+                w.field_unit("guidFormat", "", GuidText(&format.guid_format))?;
+                w.field("bBitsPerPixel", format.bits_per_pixel)?;
+                w.field("bDefaultFrameIndex", format.default_frame_index)?;
+                w.field("bAspectRatioX", format.aspect_ratio_x)?;
+                w.field("bAspectRatioY", format.aspect_ratio_y)?;
+                dump_interlace_flags(w, format.m_interlace_flags)?;
+                w.field("bCopyProtect", format.is_copy_protect)
+            }
+            UVCStreamingInterface::FormatMjpeg(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field("bNumFrameDescriptors", format.num_frame_descriptors)?;
+                w.field("bFlags", format.flags)?;
+                w.note(format_args!(
+                    "Fixed-size samples: {}",
+                    if format.flags & 0x1 != 0 { "Yes" } else { "No" }
+                ))?;
+                w.field("bDefaultFrameIndex", format.default_frame_index)?;
+                w.field("bAspectRatioX", format.aspect_ratio_x)?;
+                w.field("bAspectRatioY", format.aspect_ratio_y)?;
+                dump_interlace_flags(w, format.interlace_flags)?;
+                w.field("bCopyProtect", format.is_copy_protect)
+            }
+            UVCStreamingInterface::FrameUncompressed(UVCVSInterfaceFrameUncompressed {
+                length,
+                descriptor_type,
+                descriptor_sub_type,
+                frame_index,
+                capabilities,
+                width,
+                height,
+                min_bit_rate,
+                max_bit_rate,
+                max_video_frame_buffer_size,
+                default_frame_interval,
+                frame_interval_type,
+                frame_interval,
+            })
+            | UVCStreamingInterface::FrameMjpeg(UVCVSInterfaceFrameMJPEG {
+                length,
+                descriptor_type,
+                descriptor_sub_type,
+                frame_index,
+                capabilities,
+                width,
+                height,
+                min_bit_rate,
+                max_bit_rate,
+                max_video_frame_buffer_size,
+                default_frame_interval,
+                frame_interval_type,
+                frame_interval,
+            }) => {
+                dump_vs_header(w, *length, *descriptor_type, *descriptor_sub_type)?;
+                w.field("bFrameIndex", frame_index)?;
+                w.field("bmCapabilities", format_args!("{:#04x}", capabilities))?;
+                if capabilities & 0x1 != 0 {
+                    w.note("Still image supported")?;
+                } else {
+                    w.note("Still image unsupported")?;
+                }
+                if capabilities & 0x2 != 0 {
+                    w.note("Fixed frame-rate")?;
+                }
+                w.field("wWidth", width)?;
+                w.field("wHeight", height)?;
+                w.field("dwMinBitRate", min_bit_rate)?;
+                w.field("dwMaxBitRate", max_bit_rate)?;
+                w.field("dwMaxVideoFrameBufferSize", max_video_frame_buffer_size)?;
+                w.field("dwDefaultFrameInterval", default_frame_interval)?;
+                w.field("bFrameIntervalType", frame_interval_type)?;
+                dump_frame_intervals(w, frame_interval)
+            }
+            UVCStreamingInterface::StillImageFrame(frame) => {
+                dump_vs_header(
+                    w,
+                    frame.length,
+                    frame.descriptor_type,
+                    frame.descriptor_sub_type,
+                )?;
+                w.field(
+                    "bEndpointAddress",
+                    format_args!("{:#04x}", frame.endpoint_address),
+                )?;
+                w.field("bNumImageSizePatterns", frame.num_image_size_paterns)?;
+                for (i, (width, height)) in frame.width_heights.iter().enumerate() {
+                    w.field(&format!("wWidth({:2})", i), width)?;
+                    w.field(&format!("wHeight({:2})", i), height)?;
+                }
+                w.field("bNumCompressionPatterns", frame.num_compression_pattern)?;
+                frame
+                    .compressions
+                    .iter()
+                    .enumerate()
+                    .try_for_each(|(i, compression)| {
+                        w.field(&format!("bCompression({:2})", i), compression)
+                    })
+            }
+            UVCStreamingInterface::COLORFORMAT(color) => {
+                dump_vs_header(
+                    w,
+                    color.length,
+                    color.descriptor_type,
+                    color.descriptor_sub_type,
+                )?;
+                w.field_text(
+                    "bColorPrimaries",
+                    color.color_primaries,
+                    format_args!(
+                        "({})",
+                        match color.color_primaries {
+                            0 => "Unspecified",
+                            1 => "BT.709,sRGB",
+                            2 => "BT.470-2 (M)",
+                            3 => "BT.470-2 (B,G)",
+                            4 => "SMPTE 170M",
+                            5 => "SMPTE 240M",
+                            _ => "Unknown",
+                        }
+                    ),
+                )?;
+                w.field_text(
+                    "bTransferCharacteristics",
+                    color.transfer_characteristics,
+                    format_args!(
+                        "({})",
+                        match color.transfer_characteristics {
+                            0 => "Unspecified",
+                            1 => "BT.709",
+                            2 => "BT.470-2 (M)",
+                            3 => "BT.470-2 (B,G)",
+                            4 => "SMPTE 170M",
+                            5 => "SMPTE 240M",
+                            6 => "Linear",
+                            7 => "sRGB",
+                            _ => "Unknown",
+                        }
+                    ),
+                )?;
+                w.field_text(
+                    "bMatrixCoefficients",
+                    color.matrix_coefficients,
+                    format_args!(
+                        "({})",
+                        match color.matrix_coefficients {
+                            0 => "Unspecified",
+                            1 => "BT.709",
+                            2 => "FCC",
+                            3 => "BT.470-2 (B,G)",
+                            4 => "SMPTE 170M (BT.601)",
+                            5 => "SMPTE 240M",
+                            _ => "Unknown",
+                        }
+                    ),
+                )
+            }
+            UVCStreamingInterface::OutputHeader(header) => {
+                dump_vs_header(
+                    w,
+                    header.length,
+                    header.descriptor_type,
+                    header.descriptor_sub_type,
+                )?;
+                w.field("bNumFormats", header.num_formats)?;
+                w.field("wTotalLength", format_args!("{:#06x}", header.total_length))?;
+                w.field_unit(
+                    "bEndpointAddress",
+                    format_args!("{:#04x}", header.endpoint_address),
+                    EndpointAddressText(header.endpoint_address),
+                )?;
+                w.field("bTerminalLink", header.terminal_link)?;
+                w.field("bControlSize", header.control_size)?;
+                dump_format_controls(w, &header.controls, header.control_size)
+            }
+            UVCStreamingInterface::FormatMpeg2ts(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field("bDataOffset", format.data_offset)?;
+                w.field("bPacketLength", format.packet_length)?;
+                w.field("bStrideLength", format.stride_length)?;
+                match &format.guid_stride_format {
+                    Some(guid) => w.field_unit("guidStrideFormat", "", GuidText(guid)),
+                    None => Ok(()),
+                }
+            }
+            UVCStreamingInterface::FormatDv(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field(
+                    "dwMaxVideoFrameBufferSize",
+                    format.max_video_frame_buffer_size,
+                )?;
+                w.field_text(
+                    "bFormatType",
+                    format_args!("{:#04x}", format.format_type),
+                    format_args!(
+                        "{} {}Hz",
+                        match format.dv_format_type() {
+                            Some(DVFormatType::SD_DV) => "SD-DV",
+                            Some(DVFormatType::SDL_DV) => "SDL-DV",
+                            Some(DVFormatType::HD_DV) => "HD-DV",
+                            None => "Unknown",
+                        },
+                        if format.is_60hz() { 60 } else { 50 }
+                    ),
+                )
+            }
+            UVCStreamingInterface::FormatStreamBased(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field_unit("guidFormat", "", GuidText(&format.guid_format))?;
+                w.field("dwPacketLength", format.packet_length)
+            }
+            UVCStreamingInterface::FormatFrameBased(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field("bNumFrameDescriptors", format.num_frame_descriptors)?;
+                w.field_unit("guidFormat", "", GuidText(&format.guid_format))?;
+                w.field("bBitsPerPixel", format.bits_per_pixel)?;
+                w.field("bDefaultFrameIndex", format.default_frame_index)?;
+                w.field("bAspectRatioX", format.aspect_ratio_x)?;
+                w.field("bAspectRatioY", format.aspect_ratio_y)?;
+                dump_interlace_flags(w, format.interlace_flags)?;
+                w.field("bCopyProtect", format.is_copy_protect)?;
+                w.field("bVariableSize", format.variable_size)
+            }
+            UVCStreamingInterface::FrameFrameBased(frame) => {
+                dump_vs_header(
+                    w,
+                    frame.length,
+                    frame.descriptor_type,
+                    frame.descriptor_sub_type,
+                )?;
+                w.field("bFrameIndex", frame.frame_index)?;
+                w.field(
+                    "bmCapabilities",
+                    format_args!("{:#04x}", frame.capabilities),
+                )?;
+                w.field("wWidth", frame.width)?;
+                w.field("wHeight", frame.height)?;
+                w.field("dwMinBitRate", frame.min_bit_rate)?;
+                w.field("dwMaxBitRate", frame.max_bit_rate)?;
+                w.field("dwDefaultFrameInterval", frame.default_frame_interval)?;
+                w.field("bFrameIntervalType", frame.frame_interval_type)?;
+                w.field("dwBytesPerLine", frame.bytes_per_line)?;
+                dump_frame_intervals(w, &frame.frame_interval)
+            }
+            UVCStreamingInterface::FormatH264(format)
+            | UVCStreamingInterface::FormatH264Simulcast(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field("bNumFrameDescriptors", format.num_frame_descriptors)?;
+                w.field("bDefaultFrameIndex", format.default_frame_index)?;
+                w.field("bMaxCodecConfigDelay", format.max_codec_config_delay)?;
+                w.field(
+                    "bmSupportedSliceModes",
+                    format_args!("{:#04x}", format.supported_slice_modes),
+                )?;
+                w.field(
+                    "bmSupportedSyncFrameTypes",
+                    format_args!("{:#04x}", format.supported_sync_frame_types),
+                )?;
+                w.field("bResolutionScaling", format.resolution_scaling)?;
+                w.field(
+                    "bmSupportedRateControlModes",
+                    format_args!("{:#04x}", format.supported_rate_control_modes),
+                )?;
+                [
+                    ("NoScalability", &format.max_mb_per_sec_no_scalability),
+                    (
+                        "TemporalScalability",
+                        &format.max_mb_per_sec_temporal_scalability,
+                    ),
+                    (
+                        "TemporalQualityScalability",
+                        &format.max_mb_per_sec_temporal_quality_scalability,
+                    ),
+                    (
+                        "TemporalSpatialScalability",
+                        &format.max_mb_per_sec_temporal_spatial_scalability,
+                    ),
+                    ("FullScalability", &format.max_mb_per_sec_full_scalability),
+                ]
+                .iter()
+                .try_for_each(|(scalability, max_mb_per_sec)| {
+                    max_mb_per_sec
+                        .iter()
+                        .enumerate()
+                        .try_for_each(|(i, mb_per_sec)| {
+                            w.field(
+                                &format!("wMaxMBperSec{}Res{}", i + 1, scalability),
+                                mb_per_sec,
+                            )
+                        })
+                })
+            }
+            UVCStreamingInterface::FrameH264(frame) => {
+                dump_vs_header(
+                    w,
+                    frame.length,
+                    frame.descriptor_type,
+                    frame.descriptor_sub_type,
+                )?;
+                w.field("bFrameIndex", frame.frame_index)?;
+                w.field("wWidth", frame.width)?;
+                w.field("wHeight", frame.height)?;
+                w.field("wSARwidth", frame.sar_width)?;
+                w.field("wSARheight", frame.sar_height)?;
+                match frame.h264_profile() {
+                    Some(profile) => w.field_text(
+                        "wProfile",
+                        format_args!("{:#06x}", frame.profile),
+                        format_args!("({:?})", profile),
+                    )?,
+                    None => w.field("wProfile", format_args!("{:#06x}", frame.profile))?,
+                }
+                let (major, minor) = frame.level();
+                w.field_text(
+                    "bLevelIDC",
+                    frame.level_idc,
+                    format_args!("(Level {}.{})", major, minor),
+                )?;
+                w.field(
+                    "wConstrainedToolset",
+                    format_args!("{:#06x}", frame.constrained_toolset),
+                )?;
+                w.field(
+                    "bmSupportedUsages",
+                    format_args!("{:#010x}", frame.supported_usages),
+                )?;
+                w.field(
+                    "bmCapabilities",
+                    format_args!("{:#06x}", frame.capabilities),
+                )?;
+                w.field(
+                    "bmSVCCapabilities",
+                    format_args!("{:#010x}", frame.svc_capabilities),
+                )?;
+                w.field(
+                    "bmMVCCapabilities",
+                    format_args!("{:#010x}", frame.mvc_capabilities),
+                )?;
+                w.field("dwMinBitRate", frame.min_bit_rate)?;
+                w.field("dwMaxBitRate", frame.max_bit_rate)?;
+                w.field("dwDefaultFrameInterval", frame.default_frame_interval)?;
+                w.field("bNumFrameIntervals", frame.num_frame_intervals)?;
+                dump_frame_intervals(w, &frame.frame_interval)
+            }
+            UVCStreamingInterface::FormatVp8(format)
+            | UVCStreamingInterface::FormatVp8Simulcast(format) => {
+                dump_vs_header(
+                    w,
+                    format.length,
+                    format.descriptor_type,
+                    format.descriptor_sub_type,
+                )?;
+                w.field("bFormatIndex", format.format_index)?;
+                w.field("bNumFrameDescriptors", format.num_frame_descriptors)?;
+                w.field("bDefaultFrameIndex", format.default_frame_index)?;
+                w.field("bMaxCodecConfigDelay", format.max_codec_config_delay)?;
+                w.field("bSupportedPartitionCount", format.supported_partition_count)?;
+                w.field(
+                    "bmSupportedSyncFrameTypes",
+                    format_args!("{:#04x}", format.supported_sync_frame_types),
+                )?;
+                w.field("bResolutionScaling", format.resolution_scaling)?;
+                w.field(
+                    "bmSupportedRateControlModes",
+                    format_args!("{:#04x}", format.supported_rate_control_modes),
+                )?;
+                w.field("wMaxMBperSec", format.max_mb_per_sec)
+            }
+            UVCStreamingInterface::FrameVp8(frame) => {
+                dump_vs_header(
+                    w,
+                    frame.length,
+                    frame.descriptor_type,
+                    frame.descriptor_sub_type,
+                )?;
+                w.field("bFrameIndex", frame.frame_index)?;
+                w.field("wWidth", frame.width)?;
+                w.field("wHeight", frame.height)?;
+                w.field(
+                    "bmSupportedUsages",
+                    format_args!("{:#010x}", frame.supported_usages),
+                )?;
+                w.field(
+                    "bmCapabilities",
+                    format_args!("{:#06x}", frame.capabilities),
+                )?;
+                w.field(
+                    "bmScalabilityCapabilities",
+                    format_args!("{:#010x}", frame.scalability_capabilities),
+                )?;
+                w.field("dwMinBitRate", frame.min_bit_rate)?;
+                w.field("dwMaxBitRate", frame.max_bit_rate)?;
+                w.field("dwDefaultFrameInterval", frame.default_frame_interval)?;
+                w.field("bNumFrameIntervals", frame.num_frame_intervals)?;
+                dump_frame_intervals(w, &frame.frame_interval)
+            }
+            //lsusb's "invalid desc subtype" followed by the bytes
+            UVCStreamingInterface::Unknown(raw) => w.unrecognized(8, raw),
+        }
+    }
+}
+
+fn dump_vc_header(
+    w: &mut LsusbWriter,
+    length: u8,
+    descriptor_type: u8,
+    descriptor_sub_type: u8,
+) -> fmt::Result {
+    dump_header(
+        w,
+        length,
+        descriptor_type,
+        descriptor_sub_type,
+        UVCControlInterfaceSubclass::from_u8(descriptor_sub_type),
+    )
+}
+
+fn dump_vs_header(
+    w: &mut LsusbWriter,
+    length: u8,
+    descriptor_type: u8,
+    descriptor_sub_type: u8,
+) -> fmt::Result {
+    dump_header(
+        w,
+        length,
+        descriptor_type,
+        descriptor_sub_type,
+        UVCVSInterfaceSubclass::from_u8(descriptor_sub_type),
+    )
+}
+
+fn dump_header(
+    w: &mut LsusbWriter,
+    length: u8,
+    descriptor_type: u8,
+    descriptor_sub_type: u8,
+    subtype: Option<impl fmt::Debug>,
+) -> fmt::Result {
+    w.field("bLength", length)?;
+    w.field("bDescriptorType", descriptor_type)?;
+    match subtype {
+        Some(subtype) => w.field_text(
+            "bDescriptorSubtype",
+            descriptor_sub_type,
+            format_args!("({:?})", subtype),
+        ),
+        None => w.field_text("bDescriptorSubtype", descriptor_sub_type, "(invalid)"),
+    }
+}
@@ -0,0 +1,300 @@
+//payload headers in front of every isochronous packet or bulk payload and the frames they
+//add up to, UVC 1.5 2.4.3.3
+use alloc::{collections::VecDeque, vec::Vec};
+
+use super::uvc_probe::UvcProbeCommit;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UvcPayloadError {
+    //bHeaderLength, bytes left in the packet
+    InvalidHeaderLength(u8, usize),
+    //bHeaderLength does not cover the PTS and SCR fields bmHeaderInfo announces
+    HeaderTooShort(u8),
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct UvcPayloadHeader {
+    pub header_length: u8,
+    pub header_info: u8,
+    pub pts: Option<u32>,
+    //source time clock and the 11 bit usb frame number it was sampled in
+    pub scr: Option<(u32, u16)>,
+}
+
+impl UvcPayloadHeader {
+    pub const FID: u8 = 1 << 0;
+    pub const EOF: u8 = 1 << 1;
+    pub const PTS: u8 = 1 << 2;
+    pub const SCR: u8 = 1 << 3;
+    pub const STI: u8 = 1 << 5;
+    pub const ERR: u8 = 1 << 6;
+    pub const EOH: u8 = 1 << 7;
+
+    pub fn from_u8_array(raw: &[u8]) -> Result<Self, UvcPayloadError> {
+        let header_length = raw.first().copied().unwrap_or(0);
+        if header_length < 2 || header_length as usize > raw.len() {
+            return Err(UvcPayloadError::InvalidHeaderLength(
+                header_length,
+                raw.len(),
+            ));
+        }
+        let header_info = raw[1];
+
+        let pts_len = if header_info & Self::PTS != 0 { 4 } else { 0 };
+        let scr_len = if header_info & Self::SCR != 0 { 6 } else { 0 };
+        if 2 + pts_len + scr_len > header_length as usize {
+            return Err(UvcPayloadError::HeaderTooShort(header_length));
+        }
+
+        Ok(Self {
+            header_length,
+            header_info,
+            pts: (pts_len != 0).then(|| u32::from_le_bytes(raw[2..6].try_into().unwrap())),
+            scr: (scr_len != 0).then(|| {
+                let scr = &raw[2 + pts_len..2 + pts_len + 6];
+                (
+                    u32::from_le_bytes(scr[0..4].try_into().unwrap()),
+                    u16::from_le_bytes(scr[4..6].try_into().unwrap()) & 0x7ff,
+                )
+            }),
+        })
+    }
+
+    pub fn fid(&self) -> bool {
+        self.header_info & Self::FID != 0
+    }
+
+    pub fn eof(&self) -> bool {
+        self.header_info & Self::EOF != 0
+    }
+
+    pub fn still_image(&self) -> bool {
+        self.header_info & Self::STI != 0
+    }
+
+    pub fn error(&self) -> bool {
+        self.header_info & Self::ERR != 0
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UvcTransferMode {
+    //every packet is one payload and starts with a header, empty packets are allowed
+    Isochronous,
+    //a payload of up to dwMaxPayloadTransferSize bytes spans as many transfers of
+    //`transfer_size` bytes as needed and only the first one carries the header. a shorter
+    //transfer ends the payload early
+    Bulk {
+        max_payload_transfer_size: u32,
+        transfer_size: usize,
+    },
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct UvcVideoFrame {
+    pub data: Vec<u8>,
+    pub fid: bool,
+    //of the first payload of the frame carrying them
+    pub pts: Option<u32>,
+    pub scr: Option<(u32, u16)>,
+    pub still_image: bool,
+}
+
+#[derive(Clone, Debug)]
+pub struct UvcFrameAssembler {
+    mode: UvcTransferMode,
+    //dwMaxVideoFrameSize, larger frames are dropped
+    max_frame_size: Option<usize>,
+    //uncompressed formats know their frame size up front, other sizes are dropped
+    frame_size: Option<usize>,
+    current: UvcVideoFrame,
+    in_frame: bool,
+    errored: bool,
+    //fid of the frame EOF closed, payloads still carrying it are stale
+    closed_fid: Option<bool>,
+    stale: bool,
+    //bytes of the bulk payload in progress, None between payloads
+    bulk_payload: Option<usize>,
+    //EOF of a bulk payload whose remaining transfers are still to come
+    pending_eof: bool,
+    frames: VecDeque<UvcVideoFrame>,
+    dropped_frames: usize,
+}
+
+impl UvcFrameAssembler {
+    pub fn new(mode: UvcTransferMode) -> Self {
+        Self {
+            mode,
+            max_frame_size: None,
+            frame_size: None,
+            current: UvcVideoFrame::default(),
+            in_frame: false,
+            errored: false,
+            closed_fid: None,
+            stale: false,
+            bulk_payload: None,
+            pending_eof: false,
+            frames: VecDeque::new(),
+            dropped_frames: 0,
+        }
+    }
+
+    //limits taken from the committed probe, isochronous when `isochronous` is set
+    pub fn from_probe(probe: &UvcProbeCommit, isochronous: bool, transfer_size: usize) -> Self {
+        let mode = if isochronous {
+            UvcTransferMode::Isochronous
+        } else {
+            UvcTransferMode::Bulk {
+                max_payload_transfer_size: probe.max_payload_transfer_size,
+                transfer_size,
+            }
+        };
+        let assembler = Self::new(mode);
+        match probe.max_video_frame_size {
+            0 => assembler,
+            size => assembler.max_frame_size(size as usize),
+        }
+    }
+
+    pub fn max_frame_size(mut self, size: usize) -> Self {
+        self.max_frame_size = Some(size);
+        self
+    }
+
+    pub fn frame_size(mut self, size: usize) -> Self {
+        self.frame_size = Some(size);
+        self
+    }
+
+    //one isochronous packet or one completed bulk transfer
+    pub fn push(&mut self, packet: &[u8]) -> Result<(), UvcPayloadError> {
+        match self.mode {
+            UvcTransferMode::Isochronous => {
+                if packet.is_empty() {
+                    return Ok(());
+                }
+                let header = self.header(packet)?;
+                self.payload(&header, &packet[header.header_length as usize..]);
+                Ok(())
+            }
+            UvcTransferMode::Bulk {
+                max_payload_transfer_size,
+                transfer_size,
+            } => {
+                let (header, data) = match self.bulk_payload {
+                    Some(_) => (None, packet),
+                    None => {
+                        if packet.is_empty() {
+                            return Ok(());
+                        }
+                        let header = self.header(packet)?;
+                        (Some(header), &packet[header.header_length as usize..])
+                    }
+                };
+
+                let received = self.bulk_payload.unwrap_or(0) + packet.len();
+                let last =
+                    received >= max_payload_transfer_size as usize || packet.len() < transfer_size;
+                self.bulk_payload = (!last).then_some(received);
+
+                match header {
+                    Some(header) => {
+                        //EOF of a payload split over transfers counts once all of it arrived
+                        let mut first = header;
+                        if !last {
+                            first.header_info &= !UvcPayloadHeader::EOF;
+                        }
+                        self.payload(&first, data);
+                        self.pending_eof = !last && header.eof();
+                    }
+                    None if !self.stale => {
+                        self.append(data);
+                        if last && self.pending_eof {
+                            self.finish_frame();
+                        }
+                    }
+                    None => {}
+                }
+                Ok(())
+            }
+        }
+    }
+
+    pub fn take_frame(&mut self) -> Option<UvcVideoFrame> {
+        self.frames.pop_front()
+    }
+
+    //frames thrown away for ERR, a bad size or a malformed header
+    pub fn dropped_frames(&self) -> usize {
+        self.dropped_frames
+    }
+
+    fn header(&mut self, packet: &[u8]) -> Result<UvcPayloadHeader, UvcPayloadError> {
+        UvcPayloadHeader::from_u8_array(packet).inspect_err(|_| {
+            //whatever the packet held is missing from the frame now
+            self.errored |= self.in_frame;
+            self.bulk_payload = None;
+        })
+    }
+
+    fn payload(&mut self, header: &UvcPayloadHeader, data: &[u8]) {
+        self.stale = self.closed_fid == Some(header.fid());
+        if self.stale {
+            return;
+        }
+        self.closed_fid = None;
+
+        //a toggled fid without EOF still ends the previous frame
+        if self.in_frame && header.fid() != self.current.fid {
+            self.finish_frame();
+            //only EOF makes the fid stale, the next toggle back starts a frame as usual
+            self.closed_fid = None;
+        }
+        if !self.in_frame {
+            self.in_frame = true;
+            self.current.fid = header.fid();
+        }
+        if self.current.pts.is_none() {
+            self.current.pts = header.pts;
+        }
+        if self.current.scr.is_none() {
+            self.current.scr = header.scr;
+        }
+        self.current.still_image |= header.still_image();
+        self.errored |= header.error();
+
+        self.append(data);
+        if header.eof() {
+            self.finish_frame();
+        }
+    }
+
+    fn append(&mut self, data: &[u8]) {
+        if self
+            .max_frame_size
+            .is_some_and(|max| self.current.data.len() + data.len() > max)
+        {
+            self.errored = true;
+            return;
+        }
+        self.current.data.extend_from_slice(data);
+    }
+
+    fn finish_frame(&mut self) {
+        let frame = core::mem::take(&mut self.current);
+        self.closed_fid = Some(frame.fid);
+        self.in_frame = false;
+        self.pending_eof = false;
+
+        let errored = core::mem::take(&mut self.errored);
+        if frame.data.is_empty() && !errored {
+            return;
+        }
+        let wrong_size = self.frame_size.is_some_and(|size| size != frame.data.len());
+        if errored || wrong_size {
+            self.dropped_frames += 1;
+        } else {
+            self.frames.push_back(frame);
+        }
+    }
+}
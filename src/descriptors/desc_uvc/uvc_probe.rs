@@ -0,0 +1,254 @@
+//VS_PROBE_CONTROL and VS_COMMIT_CONTROL payloads and picking what to ask the device for
+use alloc::vec::Vec;
+
+use num_derive::FromPrimitive;
+
+use crate::descriptors::{
+    desc_endpoint::{Endpoint, EndpointType},
+    desc_interface::Interface,
+    topological_desc::TopologicalUSBDescriptorEndpoint,
+    USBDescriptor,
+};
+
+use super::uvc_streaming::{UvcFrame, UvcStreamingError, UvcStreamingInterface};
+
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+#[repr(u8)]
+pub enum UVCRequestCode {
+    RC_UNDEFINED = 0x00,
+    SET_CUR = 0x01,
+    SET_CUR_ALL = 0x11,
+    GET_CUR = 0x81,
+    GET_MIN = 0x82,
+    GET_MAX = 0x83,
+    GET_RES = 0x84,
+    GET_LEN = 0x85,
+    GET_INFO = 0x86,
+    GET_DEF = 0x87,
+    GET_CUR_ALL = 0x91,
+    GET_MIN_ALL = 0x92,
+    GET_MAX_ALL = 0x93,
+    GET_RES_ALL = 0x94,
+    GET_DEF_ALL = 0x97,
+}
+
+//wValue high byte of videostreaming interface control requests, wIndex is the interface number
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq)]
+#[allow(non_camel_case_types)]
+#[repr(u8)]
+pub enum UVCVSControlSelector {
+    VS_CONTROL_UNDEFINED = 0x00,
+    VS_PROBE_CONTROL = 0x01,
+    VS_COMMIT_CONTROL = 0x02,
+    VS_STILL_PROBE_CONTROL = 0x03,
+    VS_STILL_COMMIT_CONTROL = 0x04,
+    VS_STILL_IMAGE_TRIGGER_CONTROL = 0x05,
+    VS_STREAM_ERROR_CODE_CONTROL = 0x06,
+    VS_GENERATE_KEY_FRAME_CONTROL = 0x07,
+    VS_UPDATE_FRAME_SEGMENT_CONTROL = 0x08,
+    VS_SYNCH_DELAY_CONTROL = 0x09,
+}
+
+//UVC 1.5 table 4-75, fields past the length of the negotiated UVC version stay 0
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct UvcProbeCommit {
+    pub hint: u16,
+    pub format_index: u8,
+    pub frame_index: u8,
+    pub frame_interval: u32,
+    pub key_frame_rate: u16,
+    pub p_frame_rate: u16,
+    pub comp_quality: u16,
+    pub comp_window_size: u16,
+    pub delay: u16,
+    pub max_video_frame_size: u32,
+    pub max_payload_transfer_size: u32,
+    //UVC 1.1
+    pub clock_frequency: u32,
+    pub framing_info: u8,
+    pub prefered_version: u8,
+    pub min_version: u8,
+    pub max_version: u8,
+    //UVC 1.5
+    pub usage: u8,
+    pub bit_depth_luma: u8,
+    pub settings: u8,
+    pub max_number_of_ref_frames_plus1: u8,
+    pub rate_control_modes: u16,
+    pub layout_per_stream: u64,
+}
+
+impl UvcProbeCommit {
+    //bmHint: keep dwFrameInterval fixed while the device fills in the rest
+    pub const HINT_FRAME_INTERVAL: u16 = 0x0001;
+
+    //wLength of probe and commit requests for the bcdUVC of the video control header
+    pub fn len_for(bcd_uvc: u16) -> usize {
+        match bcd_uvc {
+            0..=0x0109 => 26,
+            0x010a..=0x014f => 34,
+            _ => 48,
+        }
+    }
+
+    pub fn from_u8_array(raw: &[u8]) -> Self {
+        let mut padded = [0u8; 48];
+        let len = raw.len().min(48);
+        padded[..len].copy_from_slice(&raw[..len]);
+        let u16_at = |i: usize| u16::from_le_bytes(padded[i..i + 2].try_into().unwrap());
+        let u32_at = |i: usize| u32::from_le_bytes(padded[i..i + 4].try_into().unwrap());
+        Self {
+            hint: u16_at(0),
+            format_index: padded[2],
+            frame_index: padded[3],
+            frame_interval: u32_at(4),
+            key_frame_rate: u16_at(8),
+            p_frame_rate: u16_at(10),
+            comp_quality: u16_at(12),
+            comp_window_size: u16_at(14),
+            delay: u16_at(16),
+            max_video_frame_size: u32_at(18),
+            max_payload_transfer_size: u32_at(22),
+            clock_frequency: u32_at(26),
+            framing_info: padded[30],
+            prefered_version: padded[31],
+            min_version: padded[32],
+            max_version: padded[33],
+            usage: padded[34],
+            bit_depth_luma: padded[35],
+            settings: padded[36],
+            max_number_of_ref_frames_plus1: padded[37],
+            rate_control_modes: u16_at(38),
+            layout_per_stream: u64::from_le_bytes(padded[40..48].try_into().unwrap()),
+        }
+    }
+
+    pub fn to_u8_array(&self, bcd_uvc: u16) -> Vec<u8> {
+        let mut raw = Vec::with_capacity(48);
+        raw.extend_from_slice(&self.hint.to_le_bytes());
+        raw.push(self.format_index);
+        raw.push(self.frame_index);
+        raw.extend_from_slice(&self.frame_interval.to_le_bytes());
+        raw.extend_from_slice(&self.key_frame_rate.to_le_bytes());
+        raw.extend_from_slice(&self.p_frame_rate.to_le_bytes());
+        raw.extend_from_slice(&self.comp_quality.to_le_bytes());
+        raw.extend_from_slice(&self.comp_window_size.to_le_bytes());
+        raw.extend_from_slice(&self.delay.to_le_bytes());
+        raw.extend_from_slice(&self.max_video_frame_size.to_le_bytes());
+        raw.extend_from_slice(&self.max_payload_transfer_size.to_le_bytes());
+        raw.extend_from_slice(&self.clock_frequency.to_le_bytes());
+        raw.extend_from_slice(&[
+            self.framing_info,
+            self.prefered_version,
+            self.min_version,
+            self.max_version,
+            self.usage,
+            self.bit_depth_luma,
+            self.settings,
+            self.max_number_of_ref_frames_plus1,
+        ]);
+        raw.extend_from_slice(&self.rate_control_modes.to_le_bytes());
+        raw.extend_from_slice(&self.layout_per_stream.to_le_bytes());
+        raw.truncate(Self::len_for(bcd_uvc));
+        raw
+    }
+}
+
+impl UvcStreamingInterface {
+    //the probe to SET_CUR first: the frame closest to `width`x`height`, then the interval
+    //closest to `frames_per_second`, earlier formats win ties. `format_index` narrows the
+    //search to one format
+    pub fn negotiate(
+        &self,
+        format_index: Option<u8>,
+        width: u16,
+        height: u16,
+        frames_per_second: u32,
+    ) -> Result<UvcProbeCommit, UvcStreamingError> {
+        let interval = 10_000_000 / frames_per_second.max(1);
+        let requested_area = width as u64 * height as u64;
+
+        let (format_index, frame, frame_interval) = self
+            .formats
+            .iter()
+            .filter(|format| format_index.is_none_or(|index| index == format.format_index))
+            .flat_map(|format| {
+                format.frames.iter().filter_map(move |frame| {
+                    frame
+                        .frame_interval
+                        .nearest(interval)
+                        .map(|frame_interval| (format.format_index, frame, frame_interval))
+                })
+            })
+            .min_by_key(|(_, frame, frame_interval)| {
+                let exact = frame.width == width && frame.height == height;
+                let area = frame.width as u64 * frame.height as u64;
+                (
+                    !exact,
+                    area.abs_diff(requested_area),
+                    frame_interval.abs_diff(interval),
+                )
+            })
+            .ok_or(UvcStreamingError::NoMatchingFrame)?;
+
+        Ok(probe_for(format_index, frame, frame_interval))
+    }
+}
+
+fn probe_for(format_index: u8, frame: &UvcFrame, frame_interval: u32) -> UvcProbeCommit {
+    UvcProbeCommit {
+        hint: UvcProbeCommit::HINT_FRAME_INTERVAL,
+        format_index,
+        frame_index: frame.frame_index,
+        frame_interval,
+        max_video_frame_size: frame.max_video_frame_buffer_size.unwrap_or(0),
+        ..Default::default()
+    }
+}
+
+//the alternate setting to switch to once the device answered GET_CUR(probe) with
+//`max_payload_transfer_size`: the one with the smallest isochronous endpoint that still fits it.
+//Ok(None) when the interface has no isochronous endpoints, bulk streams stay on setting 0
+pub fn select_alternate_setting(
+    alternates: &[(
+        Interface,
+        Vec<USBDescriptor>,
+        Vec<TopologicalUSBDescriptorEndpoint>,
+    )],
+    max_payload_transfer_size: u32,
+) -> Result<Option<(u8, Endpoint)>, UvcStreamingError> {
+    let isochronous: Vec<(u8, Endpoint)> = alternates
+        .iter()
+        .flat_map(|(interface, _, endpoints)| {
+            endpoints.iter().filter_map(|endpoint| match endpoint {
+                TopologicalUSBDescriptorEndpoint::Standard(endpoint)
+                    if endpoint.endpoint_type() == EndpointType::IsochIn =>
+                {
+                    Some((interface.alternate_setting, *endpoint))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+
+    if isochronous.is_empty() {
+        return Ok(None);
+    }
+
+    match isochronous
+        .iter()
+        .filter(|(_, endpoint)| endpoint.max_bytes_per_interval() >= max_payload_transfer_size)
+        .min_by_key(|(_, endpoint)| endpoint.max_bytes_per_interval())
+    {
+        Some(selected) => Ok(Some(*selected)),
+        None => Err(UvcStreamingError::PayloadTooLarge(
+            max_payload_transfer_size,
+            isochronous
+                .iter()
+                .map(|(_, endpoint)| endpoint.max_bytes_per_interval())
+                .max()
+                .unwrap_or(0),
+        )),
+    }
+}
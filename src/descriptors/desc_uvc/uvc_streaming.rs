@@ -0,0 +1,336 @@
+//a videostreaming interface as a tree: header, formats, their frames and frame intervals
+use alloc::vec::Vec;
+
+use crate::descriptors::USBDescriptor;
+
+use super::uvc_interfaces::{
+    FrameInterval, UVCInterface, UVCStreamingInterface, UVCVSInterfaceColorFormat,
+    UVCVSInterfaceInputHeader, UVCVSInterfaceOutputHeader, UVCVSInterfaceStillImageFrame,
+};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum UvcStreamingError {
+    MissingHeader,
+    //a frame, still image or color matching descriptor ahead of any format, by subtype
+    OrphanDescriptor(u8),
+    DuplicateFormatIndex(u8),
+    //format index, frame index
+    DuplicateFrameIndex(u8, u8),
+    //format index, subtype of the frame that does not belong to it
+    FrameTypeMismatch(u8, u8),
+    //announced by the header, found
+    NumFormatsMismatch(u8, usize),
+    //format index, announced by the format, found
+    NumFramesMismatch(u8, u8, usize),
+    //nothing to negotiate, the requested format index has no frames or does not exist
+    NoMatchingFrame,
+    //dwMaxPayloadTransferSize, the most any isochronous alternate setting moves per interval
+    PayloadTooLarge(u32, u32),
+}
+
+#[derive(Clone, Debug)]
+pub enum UvcStreamingHeader {
+    Input(UVCVSInterfaceInputHeader),
+    Output(UVCVSInterfaceOutputHeader),
+}
+
+impl UvcStreamingHeader {
+    pub fn num_formats(&self) -> u8 {
+        match self {
+            UvcStreamingHeader::Input(header) => header.num_formats,
+            UvcStreamingHeader::Output(header) => header.num_formats,
+        }
+    }
+
+    pub fn endpoint_address(&self) -> u8 {
+        match self {
+            UvcStreamingHeader::Input(header) => header.endpoint_address,
+            UvcStreamingHeader::Output(header) => header.endpoint_address,
+        }
+    }
+}
+
+//the fields every frame descriptor has in common, the full descriptor is kept alongside
+#[derive(Clone, Debug)]
+pub struct UvcFrame {
+    pub frame_index: u8,
+    pub width: u16,
+    pub height: u16,
+    pub min_bit_rate: u32,
+    pub max_bit_rate: u32,
+    //only uncompressed and MJPEG frames carry it
+    pub max_video_frame_buffer_size: Option<u32>,
+    pub default_frame_interval: u32,
+    pub frame_interval: FrameInterval,
+    pub descriptor: UVCStreamingInterface,
+}
+
+#[derive(Clone, Debug)]
+pub struct UvcFormat {
+    pub format_index: u8,
+    pub descriptor: UVCStreamingInterface,
+    pub frames: Vec<UvcFrame>,
+    pub still_image_frame: Option<UVCVSInterfaceStillImageFrame>,
+    pub color_matching: Option<UVCVSInterfaceColorFormat>,
+}
+
+#[derive(Clone, Debug)]
+pub struct UvcStreamingInterface {
+    pub header: UvcStreamingHeader,
+    pub formats: Vec<UvcFormat>,
+}
+
+impl UvcFrame {
+    fn from_descriptor(descriptor: &UVCStreamingInterface) -> Option<Self> {
+        let frame = match descriptor {
+            UVCStreamingInterface::FrameUncompressed(frame) => Self {
+                frame_index: frame.frame_index,
+                width: frame.width,
+                height: frame.height,
+                min_bit_rate: frame.min_bit_rate,
+                max_bit_rate: frame.max_bit_rate,
+                max_video_frame_buffer_size: Some(frame.max_video_frame_buffer_size),
+                default_frame_interval: frame.default_frame_interval,
+                frame_interval: frame.frame_interval.clone(),
+                descriptor: descriptor.clone(),
+            },
+            UVCStreamingInterface::FrameMjpeg(frame) => Self {
+                frame_index: frame.frame_index,
+                width: frame.width,
+                height: frame.height,
+                min_bit_rate: frame.min_bit_rate,
+                max_bit_rate: frame.max_bit_rate,
+                max_video_frame_buffer_size: Some(frame.max_video_frame_buffer_size),
+                default_frame_interval: frame.default_frame_interval,
+                frame_interval: frame.frame_interval.clone(),
+                descriptor: descriptor.clone(),
+            },
+            UVCStreamingInterface::FrameFrameBased(frame) => Self {
+                frame_index: frame.frame_index,
+                width: frame.width,
+                height: frame.height,
+                min_bit_rate: frame.min_bit_rate,
+                max_bit_rate: frame.max_bit_rate,
+                max_video_frame_buffer_size: None,
+                default_frame_interval: frame.default_frame_interval,
+                frame_interval: frame.frame_interval.clone(),
+                descriptor: descriptor.clone(),
+            },
+            UVCStreamingInterface::FrameH264(frame) => Self {
+                frame_index: frame.frame_index,
+                width: frame.width,
+                height: frame.height,
+                min_bit_rate: frame.min_bit_rate,
+                max_bit_rate: frame.max_bit_rate,
+                max_video_frame_buffer_size: None,
+                default_frame_interval: frame.default_frame_interval,
+                frame_interval: frame.frame_interval.clone(),
+                descriptor: descriptor.clone(),
+            },
+            UVCStreamingInterface::FrameVp8(frame) => Self {
+                frame_index: frame.frame_index,
+                width: frame.width,
+                height: frame.height,
+                min_bit_rate: frame.min_bit_rate,
+                max_bit_rate: frame.max_bit_rate,
+                max_video_frame_buffer_size: None,
+                default_frame_interval: frame.default_frame_interval,
+                frame_interval: frame.frame_interval.clone(),
+                descriptor: descriptor.clone(),
+            },
+            _ => return None,
+        };
+        Some(frame)
+    }
+}
+
+//(format index, announced frame count) for format descriptors, frame counts are None for
+//formats without frame descriptors
+fn format_info(descriptor: &UVCStreamingInterface) -> Option<(u8, Option<u8>)> {
+    match descriptor {
+        UVCStreamingInterface::FormatUncompressed(format) => {
+            Some((format.format_index, Some(format.number_frame_descriptor)))
+        }
+        UVCStreamingInterface::FormatMjpeg(format) => {
+            Some((format.format_index, Some(format.num_frame_descriptors)))
+        }
+        UVCStreamingInterface::FormatFrameBased(format) => {
+            Some((format.format_index, Some(format.num_frame_descriptors)))
+        }
+        UVCStreamingInterface::FormatH264(format)
+        | UVCStreamingInterface::FormatH264Simulcast(format) => {
+            Some((format.format_index, Some(format.num_frame_descriptors)))
+        }
+        UVCStreamingInterface::FormatVp8(format)
+        | UVCStreamingInterface::FormatVp8Simulcast(format) => {
+            Some((format.format_index, Some(format.num_frame_descriptors)))
+        }
+        UVCStreamingInterface::FormatMpeg2ts(format) => Some((format.format_index, None)),
+        UVCStreamingInterface::FormatDv(format) => Some((format.format_index, None)),
+        UVCStreamingInterface::FormatStreamBased(format) => Some((format.format_index, None)),
+        _ => None,
+    }
+}
+
+//whether a frame descriptor may follow the format descriptor
+fn frame_matches_format(format: &UVCStreamingInterface, frame: &UVCStreamingInterface) -> bool {
+    matches!(
+        (format, frame),
+        (
+            UVCStreamingInterface::FormatUncompressed(_),
+            UVCStreamingInterface::FrameUncompressed(_)
+        ) | (
+            UVCStreamingInterface::FormatMjpeg(_),
+            UVCStreamingInterface::FrameMjpeg(_)
+        ) | (
+            UVCStreamingInterface::FormatFrameBased(_),
+            UVCStreamingInterface::FrameFrameBased(_)
+        ) | (
+            UVCStreamingInterface::FormatH264(_) | UVCStreamingInterface::FormatH264Simulcast(_),
+            UVCStreamingInterface::FrameH264(_)
+        ) | (
+            UVCStreamingInterface::FormatVp8(_) | UVCStreamingInterface::FormatVp8Simulcast(_),
+            UVCStreamingInterface::FrameVp8(_)
+        )
+    )
+}
+
+fn descriptor_sub_type(descriptor: &UVCStreamingInterface) -> u8 {
+    match descriptor {
+        UVCStreamingInterface::StillImageFrame(still) => still.descriptor_sub_type,
+        UVCStreamingInterface::COLORFORMAT(color) => color.descriptor_sub_type,
+        UVCStreamingInterface::FrameUncompressed(frame) => frame.descriptor_sub_type,
+        UVCStreamingInterface::FrameMjpeg(frame) => frame.descriptor_sub_type,
+        UVCStreamingInterface::FrameFrameBased(frame) => frame.descriptor_sub_type,
+        UVCStreamingInterface::FrameH264(frame) => frame.descriptor_sub_type,
+        UVCStreamingInterface::FrameVp8(frame) => frame.descriptor_sub_type,
+        _ => 0,
+    }
+}
+
+impl UvcStreamingInterface {
+    //class specific descriptors of one videostreaming interface in the order the device sent
+    //them, anything that is not a videostreaming descriptor is skipped
+    pub fn from_descriptors(descriptors: &[USBDescriptor]) -> Result<Self, UvcStreamingError> {
+        let mut header = None;
+        let mut formats: Vec<UvcFormat> = Vec::new();
+
+        for descriptor in descriptors
+            .iter()
+            .filter_map(|descriptor| match descriptor {
+                USBDescriptor::UVCInterface(UVCInterface::Streaming(streaming)) => Some(streaming),
+                _ => None,
+            })
+        {
+            match descriptor {
+                UVCStreamingInterface::InputHeader(input) => {
+                    header = Some(UvcStreamingHeader::Input(input.clone()))
+                }
+                UVCStreamingInterface::OutputHeader(output) => {
+                    header = Some(UvcStreamingHeader::Output(output.clone()))
+                }
+                UVCStreamingInterface::StillImageFrame(still) => {
+                    formats
+                        .last_mut()
+                        .ok_or(UvcStreamingError::OrphanDescriptor(
+                            still.descriptor_sub_type,
+                        ))?
+                        .still_image_frame = Some(still.clone());
+                }
+                UVCStreamingInterface::COLORFORMAT(color) => {
+                    formats
+                        .last_mut()
+                        .ok_or(UvcStreamingError::OrphanDescriptor(
+                            color.descriptor_sub_type,
+                        ))?
+                        .color_matching = Some(color.clone());
+                }
+                format if let Some((format_index, _)) = format_info(format) => {
+                    if formats
+                        .iter()
+                        .any(|known| known.format_index == format_index)
+                    {
+                        return Err(UvcStreamingError::DuplicateFormatIndex(format_index));
+                    }
+                    formats.push(UvcFormat {
+                        format_index,
+                        descriptor: format.clone(),
+                        frames: Vec::new(),
+                        still_image_frame: None,
+                        color_matching: None,
+                    });
+                }
+                frame if let Some(parsed) = UvcFrame::from_descriptor(frame) => {
+                    let format = formats
+                        .last_mut()
+                        .ok_or(UvcStreamingError::OrphanDescriptor(descriptor_sub_type(
+                            frame,
+                        )))?;
+                    if !frame_matches_format(&format.descriptor, frame) {
+                        return Err(UvcStreamingError::FrameTypeMismatch(
+                            format.format_index,
+                            descriptor_sub_type(frame),
+                        ));
+                    }
+                    if format.frame(parsed.frame_index).is_some() {
+                        return Err(UvcStreamingError::DuplicateFrameIndex(
+                            format.format_index,
+                            parsed.frame_index,
+                        ));
+                    }
+                    format.frames.push(parsed);
+                }
+                _ => {}
+            }
+        }
+
+        let header = header.ok_or(UvcStreamingError::MissingHeader)?;
+        if header.num_formats() as usize != formats.len() {
+            return Err(UvcStreamingError::NumFormatsMismatch(
+                header.num_formats(),
+                formats.len(),
+            ));
+        }
+        for format in &formats {
+            if let Some((_, Some(num_frames))) = format_info(&format.descriptor)
+                && num_frames as usize != format.frames.len()
+            {
+                return Err(UvcStreamingError::NumFramesMismatch(
+                    format.format_index,
+                    num_frames,
+                    format.frames.len(),
+                ));
+            }
+        }
+
+        Ok(Self { header, formats })
+    }
+
+    pub fn format(&self, format_index: u8) -> Option<&UvcFormat> {
+        self.formats
+            .iter()
+            .find(|format| format.format_index == format_index)
+    }
+}
+
+impl UvcFormat {
+    pub fn frame(&self, frame_index: u8) -> Option<&UvcFrame> {
+        self.frames
+            .iter()
+            .find(|frame| frame.frame_index == frame_index)
+    }
+
+    pub fn default_frame(&self) -> Option<&UvcFrame> {
+        let default_frame_index = match &self.descriptor {
+            UVCStreamingInterface::FormatUncompressed(format) => format.default_frame_index,
+            UVCStreamingInterface::FormatMjpeg(format) => format.default_frame_index,
+            UVCStreamingInterface::FormatFrameBased(format) => format.default_frame_index,
+            UVCStreamingInterface::FormatH264(format)
+            | UVCStreamingInterface::FormatH264Simulcast(format) => format.default_frame_index,
+            UVCStreamingInterface::FormatVp8(format)
+            | UVCStreamingInterface::FormatVp8Simulcast(format) => format.default_frame_index,
+            _ => return None,
+        };
+        self.frame(default_frame_index)
+    }
+}
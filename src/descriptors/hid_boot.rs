@@ -0,0 +1,361 @@
+//HID 1.11 appendix B: fixed layout boot protocol reports
+use alloc::vec::Vec;
+
+use bit_field::BitField;
+
+use super::{
+    desc_hid::{USBHIDProtocolDescriptorType, USBHIDSubclassDescriptorType},
+    desc_interface::Interface,
+};
+
+//class specific request, wValue 0 selects the boot protocol
+pub const SET_PROTOCOL: u8 = 0x0B;
+pub const GET_PROTOCOL: u8 = 0x03;
+pub const BOOT_PROTOCOL: u16 = 0;
+pub const REPORT_PROTOCOL: u16 = 1;
+
+const HID_CLASS: u8 = 0x03;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BootReportError {
+    //bytes received, shorter than the fixed boot layout
+    ShortReport(usize),
+}
+
+//the boot device an interface implements, if it advertises one
+pub fn boot_protocol(interface: &Interface) -> Option<USBHIDProtocolDescriptorType> {
+    match interface.ty() {
+        (HID_CLASS, subclass, protocol)
+            if subclass == USBHIDSubclassDescriptorType::BootInterface as u8 =>
+        {
+            match protocol {
+                1 => Some(USBHIDProtocolDescriptorType::KeyBoard),
+                2 => Some(USBHIDProtocolDescriptorType::Mouse),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+//boot interfaces power up in report protocol (HID 1.11 7.2.6), switch them when no report
+//descriptor parser is going to drive the device
+pub fn should_use_boot_protocol(interface: &Interface, report_parser_available: bool) -> bool {
+    !report_parser_available && boot_protocol(interface).is_some()
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ModifierKey {
+    LeftCtrl,
+    LeftShift,
+    LeftAlt,
+    LeftGui,
+    RightCtrl,
+    RightShift,
+    RightAlt,
+    RightGui,
+}
+
+impl ModifierKey {
+    const ALL: [ModifierKey; 8] = [
+        ModifierKey::LeftCtrl,
+        ModifierKey::LeftShift,
+        ModifierKey::LeftAlt,
+        ModifierKey::LeftGui,
+        ModifierKey::RightCtrl,
+        ModifierKey::RightShift,
+        ModifierKey::RightAlt,
+        ModifierKey::RightGui,
+    ];
+
+    //keyboard page usage, 0xE0..=0xE7
+    pub fn scancode(&self) -> u8 {
+        0xE0 + *self as u8
+    }
+}
+
+//byte 0 of the boot keyboard report
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModifierState(pub u8);
+
+impl ModifierState {
+    pub fn is_pressed(&self, key: ModifierKey) -> bool {
+        self.0.get_bit(key as usize)
+    }
+
+    pub fn ctrl(&self) -> bool {
+        self.is_pressed(ModifierKey::LeftCtrl) || self.is_pressed(ModifierKey::RightCtrl)
+    }
+
+    pub fn shift(&self) -> bool {
+        self.is_pressed(ModifierKey::LeftShift) || self.is_pressed(ModifierKey::RightShift)
+    }
+
+    pub fn alt(&self) -> bool {
+        self.is_pressed(ModifierKey::LeftAlt) || self.is_pressed(ModifierKey::RightAlt)
+    }
+
+    pub fn gui(&self) -> bool {
+        self.is_pressed(ModifierKey::LeftGui) || self.is_pressed(ModifierKey::RightGui)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum KeyCode {
+    Char(char),
+    Enter,
+    Escape,
+    Backspace,
+    Tab,
+    CapsLock,
+    F(u8),
+    PrintScreen,
+    ScrollLock,
+    Pause,
+    Insert,
+    Home,
+    PageUp,
+    Delete,
+    End,
+    PageDown,
+    Right,
+    Left,
+    Down,
+    Up,
+    NumLock,
+    KeypadEnter,
+    Application,
+    Modifier(ModifierKey),
+    Unknown(u8),
+}
+
+//maps keyboard page usages to key codes, implement it for non US layouts
+pub trait KeyboardLayout {
+    fn map(&self, scancode: u8, modifiers: ModifierState, caps_lock: bool) -> KeyCode;
+}
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UsQwerty;
+
+impl UsQwerty {
+    //usages 0x1E..=0x38 without and with shift, 0x28..=0x2B are control keys and skipped
+    const DIGITS: &'static [u8; 10] = b"1234567890";
+    const SHIFTED_DIGITS: &'static [u8; 10] = b"!@#$%^&*()";
+    const PUNCTUATION: &'static [u8; 11] = b" -=[]\\#;'`,";
+    const SHIFTED_PUNCTUATION: &'static [u8; 11] = b" _+{}|~:\"~<";
+}
+
+impl KeyboardLayout for UsQwerty {
+    fn map(&self, scancode: u8, modifiers: ModifierState, caps_lock: bool) -> KeyCode {
+        let shift = modifiers.shift();
+        match scancode {
+            0x04..=0x1D => {
+                let letter = (b'a' + scancode - 0x04) as char;
+                KeyCode::Char(if shift ^ caps_lock {
+                    letter.to_ascii_uppercase()
+                } else {
+                    letter
+                })
+            }
+            0x1E..=0x27 => {
+                let table = if shift {
+                    Self::SHIFTED_DIGITS
+                } else {
+                    Self::DIGITS
+                };
+                KeyCode::Char(table[(scancode - 0x1E) as usize] as char)
+            }
+            0x28 => KeyCode::Enter,
+            0x29 => KeyCode::Escape,
+            0x2A => KeyCode::Backspace,
+            0x2B => KeyCode::Tab,
+            0x2C..=0x36 => {
+                let table = if shift {
+                    Self::SHIFTED_PUNCTUATION
+                } else {
+                    Self::PUNCTUATION
+                };
+                KeyCode::Char(table[(scancode - 0x2C) as usize] as char)
+            }
+            0x37 => KeyCode::Char(if shift { '>' } else { '.' }),
+            0x38 => KeyCode::Char(if shift { '?' } else { '/' }),
+            0x39 => KeyCode::CapsLock,
+            0x3A..=0x45 => KeyCode::F(scancode - 0x3A + 1),
+            0x46 => KeyCode::PrintScreen,
+            0x47 => KeyCode::ScrollLock,
+            0x48 => KeyCode::Pause,
+            0x49 => KeyCode::Insert,
+            0x4A => KeyCode::Home,
+            0x4B => KeyCode::PageUp,
+            0x4C => KeyCode::Delete,
+            0x4D => KeyCode::End,
+            0x4E => KeyCode::PageDown,
+            0x4F => KeyCode::Right,
+            0x50 => KeyCode::Left,
+            0x51 => KeyCode::Down,
+            0x52 => KeyCode::Up,
+            0x53 => KeyCode::NumLock,
+            0x54 => KeyCode::Char('/'),
+            0x55 => KeyCode::Char('*'),
+            0x56 => KeyCode::Char('-'),
+            0x57 => KeyCode::Char('+'),
+            0x58 => KeyCode::KeypadEnter,
+            0x59..=0x61 => KeyCode::Char((b'1' + scancode - 0x59) as char),
+            0x62 => KeyCode::Char('0'),
+            0x63 => KeyCode::Char('.'),
+            0x64 => KeyCode::Char(if shift { '|' } else { '\\' }),
+            0x65 => KeyCode::Application,
+            0xE0..=0xE7 => KeyCode::Modifier(ModifierKey::ALL[(scancode - 0xE0) as usize]),
+            other => KeyCode::Unknown(other),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct KeyEvent {
+    pub scancode: u8,
+    pub key: KeyCode,
+    pub pressed: bool,
+    pub modifiers: ModifierState,
+}
+
+//ErrorRollOver, POSTFail and ErrorUndefined fill every key slot when the keyboard can not
+//tell which keys are down
+const PHANTOM_CODES: core::ops::RangeInclusive<u8> = 0x01..=0x03;
+
+pub const BOOT_KEYBOARD_REPORT_LEN: usize = 8;
+
+pub struct BootKeyboard<L: KeyboardLayout = UsQwerty> {
+    pub layout: L,
+    pub modifiers: ModifierState,
+    pub pressed: [u8; 6],
+    //set while the keyboard reports a phantom state, the previous keys stay pressed
+    pub rollover: bool,
+    pub caps_lock: bool,
+    pub num_lock: bool,
+    pub scroll_lock: bool,
+}
+
+impl Default for BootKeyboard<UsQwerty> {
+    fn default() -> Self {
+        Self::new(UsQwerty)
+    }
+}
+
+impl<L: KeyboardLayout> BootKeyboard<L> {
+    pub fn new(layout: L) -> Self {
+        Self {
+            layout,
+            modifiers: ModifierState::default(),
+            pressed: [0; 6],
+            rollover: false,
+            caps_lock: false,
+            num_lock: false,
+            scroll_lock: false,
+        }
+    }
+
+    pub fn handle(&mut self, report: &[u8]) -> Result<Vec<KeyEvent>, BootReportError> {
+        if report.len() < BOOT_KEYBOARD_REPORT_LEN {
+            return Err(BootReportError::ShortReport(report.len()));
+        }
+        let mut events = Vec::new();
+
+        let modifiers = ModifierState(report[0]);
+        for key in ModifierKey::ALL {
+            if modifiers.is_pressed(key) != self.modifiers.is_pressed(key) {
+                events.push(KeyEvent {
+                    scancode: key.scancode(),
+                    key: KeyCode::Modifier(key),
+                    pressed: modifiers.is_pressed(key),
+                    modifiers,
+                });
+            }
+        }
+        self.modifiers = modifiers;
+
+        let keys: [u8; 6] = report[2..8].try_into().unwrap();
+        self.rollover = keys.iter().any(|key| PHANTOM_CODES.contains(key));
+        if self.rollover {
+            return Ok(events);
+        }
+
+        for &scancode in self.pressed.iter().filter(|key| **key != 0) {
+            if !keys.contains(&scancode) {
+                events.push(self.event(scancode, false));
+            }
+        }
+        for &scancode in keys.iter().filter(|key| **key != 0) {
+            if !self.pressed.contains(&scancode) {
+                self.toggle_lock(scancode);
+                events.push(self.event(scancode, true));
+            }
+        }
+        self.pressed = keys;
+        Ok(events)
+    }
+
+    fn event(&self, scancode: u8, pressed: bool) -> KeyEvent {
+        KeyEvent {
+            scancode,
+            key: self.layout.map(scancode, self.modifiers, self.caps_lock),
+            pressed,
+            modifiers: self.modifiers,
+        }
+    }
+
+    fn toggle_lock(&mut self, scancode: u8) {
+        match scancode {
+            0x39 => self.caps_lock = !self.caps_lock,
+            0x47 => self.scroll_lock = !self.scroll_lock,
+            0x53 => self.num_lock = !self.num_lock,
+            _ => {}
+        }
+    }
+
+    //boot output report: Num Lock, Caps Lock, Scroll Lock LEDs in bits 0..=2
+    pub fn led_report(&self) -> u8 {
+        let mut leds = 0u8;
+        leds.set_bit(0, self.num_lock);
+        leds.set_bit(1, self.caps_lock);
+        leds.set_bit(2, self.scroll_lock);
+        leds
+    }
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MouseEvent {
+    pub buttons: u8,
+    pub pressed: u8,
+    pub released: u8,
+    pub dx: i8,
+    pub dy: i8,
+    //not part of the boot layout, most mice send it as a fourth byte anyway
+    pub wheel: i8,
+}
+
+pub const BOOT_MOUSE_REPORT_LEN: usize = 3;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct BootMouse {
+    pub buttons: u8,
+}
+
+impl BootMouse {
+    pub fn handle(&mut self, report: &[u8]) -> Result<MouseEvent, BootReportError> {
+        if report.len() < BOOT_MOUSE_REPORT_LEN {
+            return Err(BootReportError::ShortReport(report.len()));
+        }
+        let buttons = report[0] & 0x1F;
+        let event = MouseEvent {
+            buttons,
+            pressed: buttons & !self.buttons,
+            released: self.buttons & !buttons,
+            dx: report[1] as i8,
+            dy: report[2] as i8,
+            wheel: report.get(3).map(|wheel| *wheel as i8).unwrap_or(0),
+        };
+        self.buttons = buttons;
+        Ok(event)
+    }
+}
@@ -0,0 +1,304 @@
+//builds report descriptors out of short items, each item gets the smallest data size that
+//holds its value
+use alloc::vec::Vec;
+
+use super::{
+    desc_hid::Hid,
+    desc_report::{
+        CollectionType, GlobalItemTag, ItemType, LocalItemTag, MainItemFlags, MainItemTag,
+        ReportDescriptorError, ReportType,
+    },
+};
+
+impl MainItemFlags {
+    pub const DATA_ARRAY_ABSOLUTE: MainItemFlags = MainItemFlags(0x00);
+    pub const CONSTANT: MainItemFlags = MainItemFlags(0x01);
+    pub const DATA_VARIABLE_ABSOLUTE: MainItemFlags = MainItemFlags(0x02);
+    pub const CONSTANT_VARIABLE_ABSOLUTE: MainItemFlags = MainItemFlags(0x03);
+    pub const DATA_VARIABLE_RELATIVE: MainItemFlags = MainItemFlags(0x06);
+}
+
+impl CollectionType {
+    pub fn raw(&self) -> u8 {
+        match self {
+            CollectionType::Physical => 0x00,
+            CollectionType::Application => 0x01,
+            CollectionType::Logical => 0x02,
+            CollectionType::Report => 0x03,
+            CollectionType::NamedArray => 0x04,
+            CollectionType::UsageSwitch => 0x05,
+            CollectionType::UsageModifier => 0x06,
+            CollectionType::Reserved(raw) | CollectionType::VendorDefined(raw) => *raw,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct ReportDescriptorBuilder {
+    raw: Vec<u8>,
+    collection_depth: usize,
+    push_depth: usize,
+    delimiter_open: bool,
+    //the first mistake, reported by build() so calls can stay chained
+    error: Option<ReportDescriptorError>,
+}
+
+impl ReportDescriptorBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //errors carry the offset the rejected item would have had
+    fn fail(mut self, error: fn(usize) -> ReportDescriptorError) -> Self {
+        self.error.get_or_insert(error(self.raw.len()));
+        self
+    }
+
+    fn short_item(mut self, item_type: ItemType, tag: u8, data: &[u8]) -> Self {
+        let size_code = match data.len() {
+            0 => 0,
+            1 => 1,
+            2 => 2,
+            _ => 3,
+        };
+        self.raw
+            .push((tag << 4) | ((item_type as u8) << 2) | size_code);
+        self.raw.extend_from_slice(data);
+        self
+    }
+
+    fn unsigned(self, item_type: ItemType, tag: u8, value: u32) -> Self {
+        let bytes = value.to_le_bytes();
+        let len = match value {
+            0..=0xFF => 1,
+            0x100..=0xFFFF => 2,
+            _ => 4,
+        };
+        self.short_item(item_type, tag, &bytes[..len])
+    }
+
+    //two's complement, so a positive maximum of 255 takes two bytes
+    fn signed(self, item_type: ItemType, tag: u8, value: i32) -> Self {
+        let bytes = value.to_le_bytes();
+        let len = match value {
+            -0x80..=0x7F => 1,
+            -0x8000..=0x7FFF => 2,
+            _ => 4,
+        };
+        self.short_item(item_type, tag, &bytes[..len])
+    }
+
+    fn global(self, tag: GlobalItemTag, value: u32) -> Self {
+        self.unsigned(ItemType::Global, tag as u8, value)
+    }
+
+    fn global_signed(self, tag: GlobalItemTag, value: i32) -> Self {
+        self.signed(ItemType::Global, tag as u8, value)
+    }
+
+    fn local(self, tag: LocalItemTag, value: u32) -> Self {
+        self.unsigned(ItemType::Local, tag as u8, value)
+    }
+
+    fn main(self, tag: MainItemTag, flags: MainItemFlags) -> Self {
+        self.unsigned(ItemType::Main, tag as u8, flags.0)
+    }
+
+    pub fn usage_page(self, page: u16) -> Self {
+        self.global(GlobalItemTag::UsagePage, page as u32)
+    }
+
+    pub fn logical_minimum(self, value: i32) -> Self {
+        self.global_signed(GlobalItemTag::LogicalMinimum, value)
+    }
+
+    pub fn logical_maximum(self, value: i32) -> Self {
+        self.global_signed(GlobalItemTag::LogicalMaximum, value)
+    }
+
+    pub fn logical_range(self, minimum: i32, maximum: i32) -> Self {
+        self.logical_minimum(minimum).logical_maximum(maximum)
+    }
+
+    pub fn physical_minimum(self, value: i32) -> Self {
+        self.global_signed(GlobalItemTag::PhysicalMinimum, value)
+    }
+
+    pub fn physical_maximum(self, value: i32) -> Self {
+        self.global_signed(GlobalItemTag::PhysicalMaximum, value)
+    }
+
+    pub fn physical_range(self, minimum: i32, maximum: i32) -> Self {
+        self.physical_minimum(minimum).physical_maximum(maximum)
+    }
+
+    //HID 1.11 6.2.2.7: a 4 bit two's complement nibble
+    pub fn unit_exponent(self, exponent: i8) -> Self {
+        if !(-8..8).contains(&exponent) {
+            return self.fail(ReportDescriptorError::ValueOutOfRange);
+        }
+        self.global(GlobalItemTag::UnitExponent, (exponent as u32) & 0xF)
+    }
+
+    pub fn unit(self, unit: u32) -> Self {
+        self.global(GlobalItemTag::Unit, unit)
+    }
+
+    pub fn report_size(self, bits: u32) -> Self {
+        self.global(GlobalItemTag::ReportSize, bits)
+    }
+
+    pub fn report_count(self, count: u32) -> Self {
+        self.global(GlobalItemTag::ReportCount, count)
+    }
+
+    pub fn report_id(self, id: u8) -> Self {
+        if id == 0 {
+            return self.fail(ReportDescriptorError::ReportIdZero);
+        }
+        self.global(GlobalItemTag::ReportId, id as u32)
+    }
+
+    pub fn push(mut self) -> Self {
+        self.push_depth += 1;
+        self.short_item(ItemType::Global, GlobalItemTag::Push as u8, &[])
+    }
+
+    pub fn pop(mut self) -> Self {
+        if self.push_depth == 0 {
+            return self.fail(ReportDescriptorError::PopWithoutPush);
+        }
+        self.push_depth -= 1;
+        self.short_item(ItemType::Global, GlobalItemTag::Pop as u8, &[])
+    }
+
+    //usage on the current usage page
+    pub fn usage(self, id: u16) -> Self {
+        self.local(LocalItemTag::Usage, id as u32)
+    }
+
+    //usage on another page, always four bytes
+    pub fn extended_usage(self, page: u16, id: u16) -> Self {
+        let extended = ((page as u32) << 16) | id as u32;
+        self.short_item(
+            ItemType::Local,
+            LocalItemTag::Usage as u8,
+            &extended.to_le_bytes(),
+        )
+    }
+
+    pub fn usage_minimum(self, id: u16) -> Self {
+        self.local(LocalItemTag::UsageMinimum, id as u32)
+    }
+
+    pub fn usage_maximum(self, id: u16) -> Self {
+        self.local(LocalItemTag::UsageMaximum, id as u32)
+    }
+
+    pub fn usage_range(self, minimum: u16, maximum: u16) -> Self {
+        self.usage_minimum(minimum).usage_maximum(maximum)
+    }
+
+    pub fn designator_index(self, index: u32) -> Self {
+        self.local(LocalItemTag::DesignatorIndex, index)
+    }
+
+    pub fn designator_range(self, minimum: u32, maximum: u32) -> Self {
+        self.local(LocalItemTag::DesignatorMinimum, minimum)
+            .local(LocalItemTag::DesignatorMaximum, maximum)
+    }
+
+    pub fn string_index(self, index: u32) -> Self {
+        self.local(LocalItemTag::StringIndex, index)
+    }
+
+    pub fn string_range(self, minimum: u32, maximum: u32) -> Self {
+        self.local(LocalItemTag::StringMinimum, minimum)
+            .local(LocalItemTag::StringMaximum, maximum)
+    }
+
+    pub fn open_delimiter(mut self) -> Self {
+        if self.delimiter_open {
+            return self.fail(ReportDescriptorError::NestedDelimiter);
+        }
+        self.delimiter_open = true;
+        self.local(LocalItemTag::Delimiter, 1)
+    }
+
+    pub fn close_delimiter(mut self) -> Self {
+        if !self.delimiter_open {
+            return self.fail(ReportDescriptorError::UnbalancedDelimiter);
+        }
+        self.delimiter_open = false;
+        self.local(LocalItemTag::Delimiter, 0)
+    }
+
+    pub fn collection(mut self, collection_type: CollectionType) -> Self {
+        self.collection_depth += 1;
+        self.short_item(
+            ItemType::Main,
+            MainItemTag::Collection as u8,
+            &[collection_type.raw()],
+        )
+    }
+
+    pub fn end_collection(mut self) -> Self {
+        if self.collection_depth == 0 {
+            return self.fail(ReportDescriptorError::UnbalancedEndCollection);
+        }
+        self.collection_depth -= 1;
+        self.short_item(ItemType::Main, MainItemTag::EndCollection as u8, &[])
+    }
+
+    pub fn input(self, flags: MainItemFlags) -> Self {
+        self.main(MainItemTag::Input, flags)
+    }
+
+    pub fn output(self, flags: MainItemFlags) -> Self {
+        self.main(MainItemTag::Output, flags)
+    }
+
+    pub fn feature(self, flags: MainItemFlags) -> Self {
+        self.main(MainItemTag::Feature, flags)
+    }
+
+    //constant field of `bits` bits, leaves report size 1 and report count `bits` behind
+    pub fn padding(self, report_type: ReportType, bits: u32) -> Self {
+        let tag = match report_type {
+            ReportType::Input => MainItemTag::Input,
+            ReportType::Output => MainItemTag::Output,
+            ReportType::Feature => MainItemTag::Feature,
+        };
+        self.report_size(1)
+            .report_count(bits)
+            .main(tag, MainItemFlags::CONSTANT)
+    }
+
+    pub fn len(&self) -> usize {
+        self.raw.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.raw.is_empty()
+    }
+
+    pub fn build(self) -> Result<Vec<u8>, ReportDescriptorError> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        if self.collection_depth != 0 {
+            return Err(ReportDescriptorError::UnclosedCollection);
+        }
+        if self.delimiter_open {
+            return Err(ReportDescriptorError::UnbalancedDelimiter(self.raw.len()));
+        }
+        Ok(self.raw)
+    }
+
+    //builds and records the length in the HID descriptor announcing it
+    pub fn build_for(self, hid: &mut Hid) -> Result<Vec<u8>, ReportDescriptorError> {
+        let raw = self.build()?;
+        hid.set_report_descriptor_len(raw.len() as u16);
+        Ok(raw)
+    }
+}
@@ -0,0 +1,416 @@
+//hid-decode style listing of a report descriptor, plus the field layout of each report
+use core::fmt::{self, Display, Write};
+
+use alloc::{string::String, vec::Vec};
+
+use super::{
+    desc_report::{
+        CollectionType, GlobalItemTag, Item, ItemTag, LocalItemTag, MainItemFlags, MainItemTag,
+        Report, ReportDescriptor, ReportType,
+    },
+    hid_usage::{Usage, UsagePage},
+};
+
+pub struct ReportDescriptorDump<'a> {
+    descriptor: &'a ReportDescriptor,
+    layout: bool,
+}
+
+impl<'a> ReportDescriptorDump<'a> {
+    const BYTES_COLUMN: usize = 32;
+    const TEXT_COLUMN: usize = 40;
+
+    pub fn new(descriptor: &'a ReportDescriptor) -> Self {
+        Self {
+            descriptor,
+            layout: true,
+        }
+    }
+
+    //only the item listing, without the per report tables
+    pub fn items_only(mut self) -> Self {
+        self.layout = false;
+        self
+    }
+}
+
+impl Display for ReportDescriptorDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut usage_page = 0u32;
+        let mut page_stack = Vec::new();
+        let mut logical_minimum = 0i32;
+        let mut physical_minimum = 0i32;
+        let mut depth = 0usize;
+
+        for report_item in &self.descriptor.items {
+            let raw =
+                &self.descriptor.raw[report_item.offset..report_item.offset + report_item.len];
+            let mut bytes = String::new();
+            for byte in raw {
+                write!(bytes, "0x{:02x}, ", byte)?;
+            }
+
+            let mut text = String::new();
+            match &report_item.item {
+                Item::Long { tag, data } => {
+                    write!(text, "Long Item (tag 0x{:02x}, {} bytes)", tag, data.len())?
+                }
+                Item::Short { tag, .. } => {
+                    let (unsigned, signed) =
+                        (report_item.item.unsigned(), report_item.item.signed());
+                    if let ItemTag::Main(MainItemTag::EndCollection) = tag {
+                        depth = depth.saturating_sub(1);
+                    }
+                    match tag {
+                        ItemTag::Main(main) => match main {
+                            MainItemTag::Input => {
+                                write!(text, "Input ({})", MainItemFlags(unsigned))?
+                            }
+                            MainItemTag::Output => {
+                                write!(text, "Output ({})", MainItemFlags(unsigned))?
+                            }
+                            MainItemTag::Feature => {
+                                write!(text, "Feature ({})", MainItemFlags(unsigned))?
+                            }
+                            MainItemTag::Collection => write!(
+                                text,
+                                "Collection ({})",
+                                CollectionType::from(unsigned).name()
+                            )?,
+                            MainItemTag::EndCollection => text.push_str("End Collection"),
+                        },
+                        ItemTag::Global(global) => match global {
+                            GlobalItemTag::UsagePage => {
+                                usage_page = unsigned & 0xffff;
+                                write!(text, "Usage Page ({})", UsagePage::from(usage_page as u16))?
+                            }
+                            GlobalItemTag::LogicalMinimum => {
+                                logical_minimum = signed;
+                                write!(text, "Logical Minimum ({})", signed)?
+                            }
+                            GlobalItemTag::LogicalMaximum => write!(
+                                text,
+                                "Logical Maximum ({})",
+                                if logical_minimum < 0 {
+                                    signed as i64
+                                } else {
+                                    unsigned as i64
+                                }
+                            )?,
+                            GlobalItemTag::PhysicalMinimum => {
+                                physical_minimum = signed;
+                                write!(text, "Physical Minimum ({})", signed)?
+                            }
+                            GlobalItemTag::PhysicalMaximum => write!(
+                                text,
+                                "Physical Maximum ({})",
+                                if physical_minimum < 0 {
+                                    signed as i64
+                                } else {
+                                    unsigned as i64
+                                }
+                            )?,
+                            GlobalItemTag::UnitExponent => write!(
+                                text,
+                                "Unit Exponent ({})",
+                                report_item.item.unit_exponent()
+                            )?,
+                            GlobalItemTag::Unit => write!(text, "Unit ({})", UnitText(unsigned))?,
+                            GlobalItemTag::ReportSize => {
+                                write!(text, "Report Size ({})", unsigned)?
+                            }
+                            GlobalItemTag::ReportId => write!(text, "Report ID ({})", unsigned)?,
+                            GlobalItemTag::ReportCount => {
+                                write!(text, "Report Count ({})", unsigned)?
+                            }
+                            GlobalItemTag::Push => {
+                                page_stack.push((usage_page, logical_minimum, physical_minimum));
+                                text.push_str("Push")
+                            }
+                            GlobalItemTag::Pop => {
+                                if let Some(state) = page_stack.pop() {
+                                    (usage_page, logical_minimum, physical_minimum) = state;
+                                }
+                                text.push_str("Pop")
+                            }
+                        },
+                        ItemTag::Local(local) => {
+                            let usage = || {
+                                //4 byte usages carry their own page
+                                if report_item.len == 5 {
+                                    Usage::from_extended(unsigned)
+                                } else {
+                                    Usage::new(usage_page as u16, unsigned as u16)
+                                }
+                            };
+                            match local {
+                                LocalItemTag::Usage => write!(text, "Usage ({})", usage())?,
+                                LocalItemTag::UsageMinimum => {
+                                    write!(text, "Usage Minimum ({})", unsigned)?
+                                }
+                                LocalItemTag::UsageMaximum => {
+                                    write!(text, "Usage Maximum ({})", unsigned)?
+                                }
+                                LocalItemTag::DesignatorIndex => {
+                                    write!(text, "Designator Index ({})", unsigned)?
+                                }
+                                LocalItemTag::DesignatorMinimum => {
+                                    write!(text, "Designator Minimum ({})", unsigned)?
+                                }
+                                LocalItemTag::DesignatorMaximum => {
+                                    write!(text, "Designator Maximum ({})", unsigned)?
+                                }
+                                LocalItemTag::StringIndex => {
+                                    write!(text, "String Index ({})", unsigned)?
+                                }
+                                LocalItemTag::StringMinimum => {
+                                    write!(text, "String Minimum ({})", unsigned)?
+                                }
+                                LocalItemTag::StringMaximum => {
+                                    write!(text, "String Maximum ({})", unsigned)?
+                                }
+                                LocalItemTag::Delimiter => write!(
+                                    text,
+                                    "Delimiter ({})",
+                                    if unsigned == 1 { "Open" } else { "Close" }
+                                )?,
+                            }
+                        }
+                        ItemTag::Reserved(item_type, tag) => {
+                            write!(text, "Reserved {:?} Item (tag 0x{:x})", item_type, tag)?
+                        }
+                    }
+                }
+            }
+
+            writeln!(
+                f,
+                "{:bytes$}// {:depth$}{:text$} {}",
+                bytes.trim_end(),
+                "",
+                text,
+                report_item.offset,
+                bytes = Self::BYTES_COLUMN,
+                depth = depth,
+                text = Self::TEXT_COLUMN.saturating_sub(depth),
+            )?;
+
+            if let Item::Short {
+                tag: ItemTag::Main(MainItemTag::Collection),
+                ..
+            } = report_item.item
+            {
+                depth += 1;
+            }
+        }
+
+        if self.layout {
+            for report in &self.descriptor.reports {
+                writeln!(f)?;
+                ReportLayout(report).fmt(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for ReportDescriptor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        ReportDescriptorDump::new(self).fmt(f)
+    }
+}
+
+//field table of a single report, bit offsets do not count the report id byte
+pub struct ReportLayout<'a>(pub &'a Report);
+
+impl Display for ReportLayout<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let report = self.0;
+        let report_type = match report.report_type {
+            ReportType::Input => "Input",
+            ReportType::Output => "Output",
+            ReportType::Feature => "Feature",
+        };
+        match report.report_id {
+            Some(id) => write!(f, "{} Report {}", report_type, id)?,
+            None => write!(f, "{} Report", report_type)?,
+        }
+        writeln!(
+            f,
+            " ({} bits, {} bytes)",
+            report.bit_length,
+            report.byte_length()
+        )?;
+        writeln!(
+            f,
+            "  {:>5} {:>4} {:>5}  {:40} {:>16}  Flags",
+            "Bit", "Size", "Count", "Usage", "Logical"
+        )?;
+
+        for field in &report.fields {
+            let global = &field.global_state;
+            let range = {
+                let mut range = String::new();
+                write!(
+                    range,
+                    "{}..{}",
+                    global.logical_minimum, global.logical_maximum
+                )?;
+                range
+            };
+
+            if field.is_padding() {
+                writeln!(
+                    f,
+                    "  {:>5} {:>4} {:>5}  {:40} {:>16}  {}",
+                    field.bit_offset,
+                    field.report_size(),
+                    field.report_count(),
+                    "Padding",
+                    "",
+                    field.flags
+                )?;
+            } else if field.flags.is_variable() {
+                for index in 0..field.report_count() {
+                    let usage = field.usage(index).map(Usage::from_extended);
+                    let mut name = String::new();
+                    match usage {
+                        Some(usage) => write!(name, "{}", usage)?,
+                        None => name.push_str("(none)"),
+                    }
+                    writeln!(
+                        f,
+                        "  {:>5} {:>4} {:>5}  {:40} {:>16}  {}",
+                        field.bit_offset + index * field.report_size(),
+                        field.report_size(),
+                        1,
+                        name,
+                        range,
+                        field.flags
+                    )?;
+                }
+            } else {
+                let mut name = String::new();
+                let usages = &field.local_state.usages;
+                match (usages.first(), usages.last()) {
+                    (Some(first), Some(last)) => write!(
+                        name,
+                        "{} .. {}",
+                        Usage::from_extended(first.min),
+                        Usage::from_extended(last.max)
+                    )?,
+                    _ => name.push_str("(none)"),
+                }
+                writeln!(
+                    f,
+                    "  {:>5} {:>4} {:>5}  {:40} {:>16}  {}",
+                    field.bit_offset,
+                    field.report_size(),
+                    field.report_count(),
+                    name,
+                    range,
+                    field.flags
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl CollectionType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            CollectionType::Physical => "Physical",
+            CollectionType::Application => "Application",
+            CollectionType::Logical => "Logical",
+            CollectionType::Report => "Report",
+            CollectionType::NamedArray => "Named Array",
+            CollectionType::UsageSwitch => "Usage Switch",
+            CollectionType::UsageModifier => "Usage Modifier",
+            CollectionType::Reserved(_) => "Reserved",
+            CollectionType::VendorDefined(_) => "Vendor Defined",
+        }
+    }
+}
+
+//same spelling as hid-decode: the three basic flags always, the rest only when set
+impl Display for MainItemFlags {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(if self.is_constant() { "Cnst" } else { "Data" })?;
+        f.write_str(if self.is_variable() { ",Var" } else { ",Arr" })?;
+        f.write_str(if self.is_relative() { ",Rel" } else { ",Abs" })?;
+        for (set, name) in [
+            (self.is_wrap(), ",Wrap"),
+            (self.is_nonlinear(), ",NonLin"),
+            (self.has_no_preferred_state(), ",NoPref"),
+            (self.has_null_state(), ",Null"),
+            (self.is_volatile(), ",Vol"),
+            (self.is_buffered_bytes(), ",Buff"),
+        ] {
+            if set {
+                f.write_str(name)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+//HID 1.11 6.2.2.7 unit item: a system nibble followed by signed exponents per base unit
+pub struct UnitText(pub u32);
+
+impl UnitText {
+    pub fn system(&self) -> u32 {
+        self.0 & 0xF
+    }
+
+    //exponent of length, mass, time, temperature, current, luminous intensity
+    pub fn exponent(&self, dimension: usize) -> i32 {
+        (((self.0 >> (4 * (dimension + 1))) & 0xF) as i32) << 28 >> 28
+    }
+}
+
+impl Display for UnitText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const SYSTEMS: [&str; 5] = [
+            "None",
+            "SILinear",
+            "SIRotation",
+            "EnglishLinear",
+            "EnglishRotation",
+        ];
+        const UNITS: [[&str; 4]; 6] = [
+            ["cm", "rad", "in", "deg"],
+            ["g", "g", "slug", "slug"],
+            ["s", "s", "s", "s"],
+            ["K", "K", "F", "F"],
+            ["A", "A", "A", "A"],
+            ["cd", "cd", "cd", "cd"],
+        ];
+
+        let system = self.system() as usize;
+        if self.0 == 0 || !(1..=4).contains(&system) {
+            return match SYSTEMS.get(system) {
+                Some(name) if self.0 >> 4 == 0 => f.write_str(name),
+                _ => write!(f, "0x{:x}", self.0),
+            };
+        }
+
+        write!(f, "{}: ", SYSTEMS[system])?;
+        let mut first = true;
+        for (dimension, names) in UNITS.iter().enumerate() {
+            let exponent = self.exponent(dimension);
+            if exponent == 0 {
+                continue;
+            }
+            if !first {
+                f.write_str(" * ")?;
+            }
+            first = false;
+            f.write_str(names[system - 1])?;
+            if exponent != 1 {
+                write!(f, "^{}", exponent)?;
+            }
+        }
+        Ok(())
+    }
+}
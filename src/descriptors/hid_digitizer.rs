@@ -0,0 +1,368 @@
+//touch screens and precision touchpads: Finger collections collected into per scan frames,
+//hybrid mode devices spread one frame over several reports
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use log::trace;
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::{
+    desc_report::{Report, ReportDescriptor, ReportDescriptorError, ReportField, ReportType},
+    hid_usage::{Digitizer, GenericDesktop},
+};
+
+const DIGITIZER_PAGE: u32 = 0x0D;
+const GENERIC_DESKTOP_PAGE: u32 = 0x01;
+const BUTTON_PAGE: u32 = 0x09;
+
+fn usage(page: u32, id: u16) -> u32 {
+    (page << 16) | id as u32
+}
+
+//value of Device Mode in the Device Configuration feature report
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum DeviceMode {
+    Mouse = 0,
+    SingleInput = 1,
+    MultiInput = 2,
+}
+
+//where one usage of a Finger collection lives: (field index, element)
+type Slot = Option<(usize, usize)>;
+
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ContactSlot {
+    pub report: usize,
+    pub contact_identifier: Slot,
+    pub tip_switch: Slot,
+    pub confidence: Slot,
+    pub in_range: Slot,
+    pub x: Slot,
+    pub y: Slot,
+    pub width: Slot,
+    pub height: Slot,
+    pub pressure: Slot,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Contact {
+    //stable for as long as the finger stays on the surface, unlike `contact_identifier`
+    //which devices are free to reuse
+    pub tracking_id: u32,
+    pub contact_identifier: u32,
+    pub tip: bool,
+    pub confident: bool,
+    pub x: i32,
+    pub y: i32,
+    //position in physical units, unit exponent applied
+    pub physical_x: f64,
+    pub physical_y: f64,
+    pub width: Option<i32>,
+    pub height: Option<i32>,
+    pub pressure: Option<i32>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ContactFrame {
+    pub contacts: Vec<Contact>,
+    //100us units, wraps around
+    pub scan_time: Option<u32>,
+    //touchpad buttons, bit n is button n+1
+    pub buttons: u32,
+}
+
+pub struct DigitizerInterpreter {
+    pub application: u32,
+    pub slots: Vec<ContactSlot>,
+    //application level Contact Count and Scan Time per input report
+    contact_count: BTreeMap<usize, (usize, usize)>,
+    scan_time: BTreeMap<usize, (usize, usize)>,
+    //feature reports holding Contact Count Maximum and Device Mode
+    pub contact_count_maximum_report: Option<usize>,
+    pub device_mode_report: Option<usize>,
+    //contacts still expected for the frame in progress
+    expected: usize,
+    pending: ContactFrame,
+    tracks: BTreeMap<u32, u32>,
+    next_tracking_id: u32,
+}
+
+impl DigitizerInterpreter {
+    //None without a Touch Screen or Touch Pad application that declares Finger collections
+    pub fn from_descriptor(descriptor: &ReportDescriptor) -> Option<Self> {
+        let touch_screen = usage(DIGITIZER_PAGE, Digitizer::TouchScreen as u16);
+        let touch_pad = usage(DIGITIZER_PAGE, Digitizer::TouchPad as u16);
+        let (index, collection) = descriptor.root_collections().find(|(_, collection)| {
+            collection.usage == touch_screen || collection.usage == touch_pad
+        })?;
+
+        let finger = usage(DIGITIZER_PAGE, Digitizer::Finger as u16);
+        let slots: Vec<_> = descriptor
+            .collections
+            .iter()
+            .enumerate()
+            .filter(|(child, child_collection)| {
+                child_collection.usage == finger && Self::descends_from(descriptor, *child, index)
+            })
+            .filter_map(|(child, _)| Self::slot(descriptor, child))
+            .collect();
+        if slots.is_empty() {
+            return None;
+        }
+
+        let mut interpreter = Self {
+            application: collection.usage,
+            slots,
+            contact_count: BTreeMap::new(),
+            scan_time: BTreeMap::new(),
+            contact_count_maximum_report: None,
+            device_mode_report: None,
+            expected: 0,
+            pending: ContactFrame::default(),
+            tracks: BTreeMap::new(),
+            next_tracking_id: 0,
+        };
+        for (report_index, report) in descriptor.reports.iter().enumerate() {
+            let locate = |id: Digitizer| report.locate(DIGITIZER_PAGE, id as u32);
+            match report.report_type {
+                ReportType::Input => {
+                    if let Some(location) = locate(Digitizer::ContactCount) {
+                        interpreter.contact_count.insert(report_index, location);
+                    }
+                    if let Some(location) = locate(Digitizer::ScanTime) {
+                        interpreter.scan_time.insert(report_index, location);
+                    }
+                }
+                ReportType::Feature => {
+                    if locate(Digitizer::ContactCountMaximum).is_some() {
+                        interpreter.contact_count_maximum_report = Some(report_index);
+                    }
+                    if locate(Digitizer::DeviceMode).is_some() {
+                        interpreter.device_mode_report = Some(report_index);
+                    }
+                }
+                ReportType::Output => {}
+            }
+        }
+        Some(interpreter)
+    }
+
+    fn descends_from(
+        descriptor: &ReportDescriptor,
+        mut collection: usize,
+        ancestor: usize,
+    ) -> bool {
+        while let Some(parent) = descriptor.collections[collection].parent {
+            if parent == ancestor {
+                return true;
+            }
+            collection = parent;
+        }
+        false
+    }
+
+    fn slot(descriptor: &ReportDescriptor, collection: usize) -> Option<ContactSlot> {
+        let fields = descriptor.collection_fields(collection);
+        let report = fields
+            .iter()
+            .map(|(report, _)| *report)
+            .find(|report| descriptor.reports[*report].report_type == ReportType::Input)?;
+        let mut slot = ContactSlot {
+            report,
+            ..Default::default()
+        };
+        for (field_report, field_index) in fields {
+            let field = descriptor.field((field_report, field_index));
+            if field_report != report || field.is_padding() || !field.flags.is_variable() {
+                continue;
+            }
+            for element in 0..field.report_count() {
+                let Some(extended) = field.usage(element) else {
+                    break;
+                };
+                let target = match (extended >> 16, (extended & 0xffff) as u16) {
+                    (DIGITIZER_PAGE, id) => match Digitizer::from_u16(id) {
+                        Some(Digitizer::ContactIdentifier) => &mut slot.contact_identifier,
+                        Some(Digitizer::TipSwitch) => &mut slot.tip_switch,
+                        Some(Digitizer::TouchValid) => &mut slot.confidence,
+                        Some(Digitizer::InRange) => &mut slot.in_range,
+                        Some(Digitizer::Width) => &mut slot.width,
+                        Some(Digitizer::Height) => &mut slot.height,
+                        Some(Digitizer::TipPressure) => &mut slot.pressure,
+                        _ => continue,
+                    },
+                    (GENERIC_DESKTOP_PAGE, id) if id == GenericDesktop::X as u16 => &mut slot.x,
+                    (GENERIC_DESKTOP_PAGE, id) if id == GenericDesktop::Y as u16 => &mut slot.y,
+                    _ => continue,
+                };
+                target.get_or_insert((field_index, element));
+            }
+        }
+        Some(slot)
+    }
+
+    //feed one input report, returns a frame once every contact of the scan has arrived
+    pub fn handle(
+        &mut self,
+        descriptor: &ReportDescriptor,
+        data: &[u8],
+    ) -> Result<Option<ContactFrame>, ReportDescriptorError> {
+        let (report_index, payload) = descriptor.split_report(ReportType::Input, data)?;
+        let report = &descriptor.reports[report_index];
+        let read = |location: Option<&(usize, usize)>| {
+            location.map(|(field, element)| report.fields[*field].read(payload, *element))
+        };
+        let slots: Vec<_> = self
+            .slots
+            .iter()
+            .filter(|slot| slot.report == report_index)
+            .copied()
+            .collect();
+        if slots.is_empty() {
+            return Ok(None);
+        }
+
+        //a nonzero Contact Count starts a frame, hybrid mode follow up reports carry 0
+        match read(self.contact_count.get(&report_index)) {
+            Some(count) if count > 0 => self.start_frame(count as usize),
+            Some(_) if self.expected > self.pending.contacts.len() => {}
+            //a zero count with no frame in progress: every finger lifted, the frame still
+            //carries the scan time and button releases
+            Some(_) => self.start_frame(0),
+            None => self.start_frame(slots.len()),
+        }
+        if self.pending.contacts.is_empty() {
+            self.pending.scan_time =
+                read(self.scan_time.get(&report_index)).map(|time| time as u32);
+            self.pending.buttons = Self::buttons(report, payload);
+        }
+        if self.expected == 0 {
+            return Ok(Some(self.finish_frame()));
+        }
+
+        for slot in slots {
+            if self.pending.contacts.len() >= self.expected {
+                break;
+            }
+            let contact = self.contact(report.fields.as_slice(), payload, &slot);
+            self.pending.contacts.push(contact);
+        }
+        if self.pending.contacts.len() >= self.expected {
+            return Ok(Some(self.finish_frame()));
+        }
+        trace!(
+            "digitizer: {} of {} contacts",
+            self.pending.contacts.len(),
+            self.expected
+        );
+        Ok(None)
+    }
+
+    fn start_frame(&mut self, expected: usize) {
+        self.expected = expected;
+        self.pending = ContactFrame::default();
+    }
+
+    fn buttons(report: &Report, payload: &[u8]) -> u32 {
+        let mut buttons = 0;
+        for field in report
+            .fields
+            .iter()
+            .filter(|field| field.flags.is_variable())
+        {
+            for element in 0..field.report_count() {
+                if let Some(extended) = field.usage(element)
+                    && extended >> 16 == BUTTON_PAGE
+                    && (1..=32).contains(&(extended & 0xffff))
+                    && field.read(payload, element) != 0
+                {
+                    buttons |= 1 << ((extended & 0xffff) - 1);
+                }
+            }
+        }
+        buttons
+    }
+
+    fn contact(&self, fields: &[ReportField], payload: &[u8], slot: &ContactSlot) -> Contact {
+        let read =
+            |location: Slot| location.map(|(field, element)| fields[field].read(payload, element));
+        let physical = |location: Slot| {
+            location
+                .map(|(field, element)| {
+                    let field = &fields[field];
+                    field.global_state.to_physical(field.read(payload, element))
+                })
+                .unwrap_or(0.0)
+        };
+        Contact {
+            tracking_id: 0,
+            contact_identifier: read(slot.contact_identifier).unwrap_or(0) as u32,
+            //a slot without Tip Switch reports touch through In Range
+            tip: read(slot.tip_switch.or(slot.in_range)).unwrap_or(1) != 0,
+            confident: read(slot.confidence).unwrap_or(1) != 0,
+            x: read(slot.x).unwrap_or(0),
+            y: read(slot.y).unwrap_or(0),
+            physical_x: physical(slot.x),
+            physical_y: physical(slot.y),
+            width: read(slot.width),
+            height: read(slot.height),
+            pressure: read(slot.pressure),
+        }
+    }
+
+    //contacts keep their tracking id until they lift or drop out of a frame
+    fn finish_frame(&mut self) -> ContactFrame {
+        let mut frame = core::mem::take(&mut self.pending);
+        self.expected = 0;
+        let mut tracks = BTreeMap::new();
+        for contact in &mut frame.contacts {
+            let tracking_id = match self.tracks.get(&contact.contact_identifier) {
+                Some(tracking_id) => *tracking_id,
+                None => {
+                    self.next_tracking_id = self.next_tracking_id.wrapping_add(1);
+                    self.next_tracking_id
+                }
+            };
+            contact.tracking_id = tracking_id;
+            if contact.tip {
+                tracks.insert(contact.contact_identifier, tracking_id);
+            }
+        }
+        self.tracks = tracks;
+        frame
+    }
+
+    //Contact Count Maximum, from a GET_REPORT(Feature) response
+    pub fn contact_count_maximum(&self, descriptor: &ReportDescriptor, data: &[u8]) -> Option<u32> {
+        self.read_feature(descriptor, data, Digitizer::ContactCountMaximum)
+            .map(|count| count as u32)
+    }
+
+    pub fn device_mode(&self, descriptor: &ReportDescriptor, data: &[u8]) -> Option<DeviceMode> {
+        DeviceMode::from_i32(self.read_feature(descriptor, data, Digitizer::DeviceMode)?)
+    }
+
+    //SET_REPORT(Feature) payload switching the device mode, Windows precision touchpads
+    //stay in mouse mode until the host asks for multi input
+    pub fn device_mode_request(
+        &self,
+        descriptor: &ReportDescriptor,
+        mode: DeviceMode,
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        descriptor.encode(
+            ReportType::Feature,
+            &[(DIGITIZER_PAGE, Digitizer::DeviceMode as u32, mode as i32)],
+        )
+    }
+
+    fn read_feature(
+        &self,
+        descriptor: &ReportDescriptor,
+        data: &[u8],
+        id: Digitizer,
+    ) -> Option<i32> {
+        let (report, payload) = descriptor.split_report(ReportType::Feature, data).ok()?;
+        descriptor.reports[report].value(payload, DIGITIZER_PAGE, id as u32)
+    }
+}
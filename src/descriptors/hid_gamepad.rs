@@ -0,0 +1,497 @@
+//maps Generic Desktop Joystick/Gamepad applications onto a standard controller layout, the
+//button order follows the W3C gamepad "standard" mapping
+use alloc::vec::Vec;
+
+use log::trace;
+use num_traits::FromPrimitive;
+
+use super::{
+    desc_report::{ReportDescriptor, ReportDescriptorError, ReportField, ReportType},
+    hid_usage::{GenericDesktop, UsagePage},
+};
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Button {
+    South,
+    East,
+    West,
+    North,
+    LeftShoulder,
+    RightShoulder,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DpadUp,
+    DpadDown,
+    DpadLeft,
+    DpadRight,
+    Mode,
+}
+
+impl Button {
+    pub const COUNT: usize = 17;
+    const STANDARD: [Button; Button::COUNT] = [
+        Button::South,
+        Button::East,
+        Button::West,
+        Button::North,
+        Button::LeftShoulder,
+        Button::RightShoulder,
+        Button::LeftTrigger,
+        Button::RightTrigger,
+        Button::Select,
+        Button::Start,
+        Button::LeftStick,
+        Button::RightStick,
+        Button::DpadUp,
+        Button::DpadDown,
+        Button::DpadLeft,
+        Button::DpadRight,
+        Button::Mode,
+    ];
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Axis {
+    LeftX,
+    LeftY,
+    RightX,
+    RightY,
+    LeftTrigger,
+    RightTrigger,
+}
+
+impl Axis {
+    pub const COUNT: usize = 6;
+
+    pub fn is_trigger(&self) -> bool {
+        matches!(self, Axis::LeftTrigger | Axis::RightTrigger)
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Control {
+    Button(Button),
+    Axis(Axis),
+    //hat switch, drives the four d-pad buttons
+    Hat,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct AxisInfo {
+    pub logical_minimum: i32,
+    pub logical_maximum: i32,
+    //fraction of the normalized range around rest that should read as zero
+    pub dead_zone: f32,
+}
+
+impl AxisInfo {
+    //sticks map to -1.0..=1.0, triggers to 0.0..=1.0; up and left are negative as in HID
+    pub fn normalize(&self, axis: Axis, value: i32) -> f32 {
+        let span = (self.logical_maximum as f32 - self.logical_minimum as f32).max(1.0);
+        let unit = ((value as f32 - self.logical_minimum as f32) / span).clamp(0.0, 1.0);
+        if axis.is_trigger() {
+            unit
+        } else {
+            unit * 2.0 - 1.0
+        }
+    }
+
+    //rescales so the output still covers the full range outside the dead zone
+    pub fn apply_dead_zone(&self, normalized: f32) -> f32 {
+        let magnitude = if normalized < 0.0 {
+            -normalized
+        } else {
+            normalized
+        };
+        if magnitude <= self.dead_zone || self.dead_zone >= 1.0 {
+            return 0.0;
+        }
+        let scaled = (magnitude - self.dead_zone) / (1.0 - self.dead_zone);
+        if normalized < 0.0 {
+            -scaled
+        } else {
+            scaled
+        }
+    }
+}
+
+//one report element wired to a standard control
+#[derive(Copy, Clone, Debug)]
+pub struct MappedControl {
+    pub report: usize,
+    pub field: usize,
+    pub element: usize,
+    pub control: Control,
+    pub axis_info: Option<AxisInfo>,
+}
+
+//a device whose descriptor does not tell the truth about its layout
+pub struct ControllerOverride {
+    pub vendor_id: u16,
+    pub product_id: u16,
+    pub name: &'static str,
+    //Button page usage ids, in standard order, None for controls the device lacks
+    pub buttons: &'static [Option<u16>; Button::COUNT],
+    //Generic Desktop usage ids for LeftX, LeftY, RightX, RightY, LeftTrigger, RightTrigger
+    pub axes: &'static [Option<u16>; Axis::COUNT],
+    pub dead_zone: f32,
+}
+
+const DUALSHOCK_BUTTONS: [Option<u16>; Button::COUNT] = [
+    Some(2),
+    Some(3),
+    Some(1),
+    Some(4),
+    Some(5),
+    Some(6),
+    Some(7),
+    Some(8),
+    Some(9),
+    Some(10),
+    Some(11),
+    Some(12),
+    None,
+    None,
+    None,
+    None,
+    Some(13),
+];
+const DUALSHOCK_AXES: [Option<u16>; Axis::COUNT] = [
+    Some(0x30),
+    Some(0x31),
+    Some(0x32),
+    Some(0x35),
+    Some(0x33),
+    Some(0x34),
+];
+
+const SWITCH_PRO_BUTTONS: [Option<u16>; Button::COUNT] = [
+    Some(1),
+    Some(2),
+    Some(3),
+    Some(4),
+    Some(5),
+    Some(6),
+    Some(7),
+    Some(8),
+    Some(9),
+    Some(10),
+    Some(11),
+    Some(12),
+    None,
+    None,
+    None,
+    None,
+    Some(13),
+];
+const SWITCH_PRO_AXES: [Option<u16>; Axis::COUNT] =
+    [Some(0x30), Some(0x31), Some(0x33), Some(0x34), None, None];
+
+const F310_BUTTONS: [Option<u16>; Button::COUNT] = [
+    Some(2),
+    Some(3),
+    Some(1),
+    Some(4),
+    Some(5),
+    Some(6),
+    Some(7),
+    Some(8),
+    Some(9),
+    Some(10),
+    Some(11),
+    Some(12),
+    None,
+    None,
+    None,
+    None,
+    None,
+];
+const F310_AXES: [Option<u16>; Axis::COUNT] =
+    [Some(0x30), Some(0x31), Some(0x32), Some(0x35), None, None];
+
+pub static CONTROLLER_OVERRIDES: &[ControllerOverride] = &[
+    ControllerOverride {
+        vendor_id: 0x054c,
+        product_id: 0x05c4,
+        name: "Sony DualShock 4",
+        buttons: &DUALSHOCK_BUTTONS,
+        axes: &DUALSHOCK_AXES,
+        dead_zone: 0.05,
+    },
+    ControllerOverride {
+        vendor_id: 0x054c,
+        product_id: 0x09cc,
+        name: "Sony DualShock 4 (2nd gen)",
+        buttons: &DUALSHOCK_BUTTONS,
+        axes: &DUALSHOCK_AXES,
+        dead_zone: 0.05,
+    },
+    ControllerOverride {
+        vendor_id: 0x054c,
+        product_id: 0x0ce6,
+        name: "Sony DualSense",
+        buttons: &DUALSHOCK_BUTTONS,
+        axes: &DUALSHOCK_AXES,
+        dead_zone: 0.05,
+    },
+    ControllerOverride {
+        vendor_id: 0x057e,
+        product_id: 0x2009,
+        name: "Nintendo Switch Pro Controller",
+        buttons: &SWITCH_PRO_BUTTONS,
+        axes: &SWITCH_PRO_AXES,
+        dead_zone: 0.1,
+    },
+    ControllerOverride {
+        vendor_id: 0x046d,
+        product_id: 0xc216,
+        name: "Logitech F310 (DirectInput)",
+        buttons: &F310_BUTTONS,
+        axes: &F310_AXES,
+        dead_zone: 0.1,
+    },
+];
+
+pub fn find_override(vendor_id: u16, product_id: u16) -> Option<&'static ControllerOverride> {
+    CONTROLLER_OVERRIDES
+        .iter()
+        .find(|entry| entry.vendor_id == vendor_id && entry.product_id == product_id)
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct GamepadState {
+    pub buttons: [bool; Button::COUNT],
+    pub axes: [f32; Axis::COUNT],
+}
+
+impl GamepadState {
+    pub fn button(&self, button: Button) -> bool {
+        self.buttons[button as usize]
+    }
+
+    pub fn axis(&self, axis: Axis) -> f32 {
+        self.axes[axis as usize]
+    }
+}
+
+pub struct Gamepad {
+    pub application: u32,
+    pub controls: Vec<MappedControl>,
+    pub override_name: Option<&'static str>,
+    pub state: GamepadState,
+}
+
+//1/16 of the range, what evdev joystick drivers commonly use as flat
+const DEFAULT_DEAD_ZONE: f32 = 0.0625;
+
+const USAGE_JOYSTICK: u32 = 0x0001_0004;
+const USAGE_GAMEPAD: u32 = 0x0001_0005;
+const USAGE_MULTI_AXIS: u32 = 0x0001_0008;
+
+impl Gamepad {
+    //None if the descriptor has no joystick or gamepad application collection
+    pub fn from_descriptor(
+        descriptor: &ReportDescriptor,
+        vendor_id: u16,
+        product_id: u16,
+    ) -> Option<Self> {
+        let (index, collection) = descriptor.root_collections().find(|(_, collection)| {
+            matches!(
+                collection.usage,
+                USAGE_JOYSTICK | USAGE_GAMEPAD | USAGE_MULTI_AXIS
+            )
+        })?;
+        let fields: Vec<_> = descriptor
+            .collection_fields(index)
+            .into_iter()
+            .filter(|reference| {
+                let field = descriptor.field(*reference);
+                field.report_type == ReportType::Input && !field.is_padding()
+            })
+            .collect();
+        let entry = find_override(vendor_id, product_id);
+
+        //generic descriptors put the right stick on Z/Rz when Rz exists, else on Rx/Ry
+        let has_usage = |usage: u16| {
+            fields.iter().any(|reference| {
+                descriptor
+                    .field(*reference)
+                    .local_state
+                    .contains(0x0001_0000 | usage as u32)
+            })
+        };
+        let generic_axes: [Option<u16>; Axis::COUNT] = if has_usage(GenericDesktop::Rz as u16) {
+            [
+                Some(0x30),
+                Some(0x31),
+                Some(0x32),
+                Some(0x35),
+                Some(0x33),
+                Some(0x34),
+            ]
+        } else {
+            [
+                Some(0x30),
+                Some(0x31),
+                Some(0x33),
+                Some(0x34),
+                Some(0x32),
+                None,
+            ]
+        };
+        let axes = entry.map(|entry| entry.axes).unwrap_or(&generic_axes);
+        let dead_zone = entry
+            .map(|entry| entry.dead_zone)
+            .unwrap_or(DEFAULT_DEAD_ZONE);
+
+        let mut controls = Vec::new();
+        for (report, field_index) in fields {
+            let field = descriptor.field((report, field_index));
+            if field.flags.is_array() {
+                trace!("gamepad: skip array field at bit {}", field.bit_offset);
+                continue;
+            }
+            for element in 0..field.report_count() {
+                let Some(usage) = field.usage(element) else {
+                    break;
+                };
+                let control = Self::map_usage(usage, entry, axes);
+                if let Some(control) = control {
+                    let global = &field.global_state;
+                    controls.push(MappedControl {
+                        report,
+                        field: field_index,
+                        element,
+                        control,
+                        axis_info: matches!(control, Control::Axis(_)).then(|| AxisInfo {
+                            logical_minimum: global.logical_minimum,
+                            logical_maximum: global.logical_maximum,
+                            dead_zone: match control {
+                                Control::Axis(axis) if axis.is_trigger() => 0.0,
+                                _ => dead_zone,
+                            },
+                        }),
+                    });
+                }
+            }
+        }
+
+        Some(Self {
+            application: collection.usage,
+            controls,
+            override_name: entry.map(|entry| entry.name),
+            state: GamepadState::default(),
+        })
+    }
+
+    fn map_usage(
+        usage: u32,
+        entry: Option<&ControllerOverride>,
+        axes: &[Option<u16>; Axis::COUNT],
+    ) -> Option<Control> {
+        let (page, id) = ((usage >> 16) as u16, usage as u16);
+        match UsagePage::from(page) {
+            UsagePage::Button => match entry {
+                Some(entry) => entry
+                    .buttons
+                    .iter()
+                    .position(|button| *button == Some(id))
+                    .map(|index| Control::Button(Button::STANDARD[index])),
+                None => Button::STANDARD
+                    .get((id as usize).checked_sub(1)?)
+                    .map(|button| Control::Button(*button)),
+            },
+            UsagePage::GenericDesktop => match GenericDesktop::from_u16(id)? {
+                GenericDesktop::HatSwitch => Some(Control::Hat),
+                GenericDesktop::DpadUp => Some(Control::Button(Button::DpadUp)),
+                GenericDesktop::DpadDown => Some(Control::Button(Button::DpadDown)),
+                GenericDesktop::DpadLeft => Some(Control::Button(Button::DpadLeft)),
+                GenericDesktop::DpadRight => Some(Control::Button(Button::DpadRight)),
+                GenericDesktop::Start => Some(Control::Button(Button::Start)),
+                GenericDesktop::Select => Some(Control::Button(Button::Select)),
+                GenericDesktop::SystemMainMenu => Some(Control::Button(Button::Mode)),
+                _ => axes
+                    .iter()
+                    .position(|axis| *axis == Some(id))
+                    .map(|index| Control::Axis(Self::axis_from_index(index))),
+            },
+            //Simulation Controls Brake/Accelerator, used by some pads for the triggers
+            UsagePage::SimulationControls => match id {
+                0xC5 => Some(Control::Axis(Axis::LeftTrigger)),
+                0xC4 => Some(Control::Axis(Axis::RightTrigger)),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn axis_from_index(index: usize) -> Axis {
+        [
+            Axis::LeftX,
+            Axis::LeftY,
+            Axis::RightX,
+            Axis::RightY,
+            Axis::LeftTrigger,
+            Axis::RightTrigger,
+        ][index]
+    }
+
+    //decode one input report into `state`, axes are normalized but not dead zoned
+    pub fn update(
+        &mut self,
+        descriptor: &ReportDescriptor,
+        data: &[u8],
+    ) -> Result<&GamepadState, ReportDescriptorError> {
+        let (report, payload) = descriptor.split_report(ReportType::Input, data)?;
+        for control in self
+            .controls
+            .iter()
+            .filter(|control| control.report == report)
+        {
+            let field = descriptor.field((control.report, control.field));
+            let value = field.read(payload, control.element);
+            match control.control {
+                Control::Button(button) => self.state.buttons[button as usize] = value != 0,
+                Control::Axis(axis) => {
+                    if let Some(info) = control.axis_info
+                        && field.global_state.in_logical_range(value)
+                    {
+                        self.state.axes[axis as usize] = info.normalize(axis, value);
+                    }
+                }
+                Control::Hat => Self::update_hat(&mut self.state, field, value),
+            }
+        }
+        Ok(&self.state)
+    }
+
+    //8 way hats count clockwise from north, 4 way hats only have the cardinal points
+    fn update_hat(state: &mut GamepadState, field: &ReportField, value: i32) {
+        let global = &field.global_state;
+        let positions = global.logical_maximum - global.logical_minimum + 1;
+        let (up, right, down, left) = if global.in_logical_range(value) {
+            let direction = value - global.logical_minimum;
+            let eighths = if positions == 4 {
+                direction * 2
+            } else {
+                direction
+            };
+            (
+                matches!(eighths, 7 | 0 | 1),
+                matches!(eighths, 1..=3),
+                matches!(eighths, 3..=5),
+                matches!(eighths, 5..=7),
+            )
+        } else {
+            (false, false, false, false)
+        };
+        state.buttons[Button::DpadUp as usize] = up;
+        state.buttons[Button::DpadRight as usize] = right;
+        state.buttons[Button::DpadDown as usize] = down;
+        state.buttons[Button::DpadLeft as usize] = left;
+    }
+}
@@ -0,0 +1,606 @@
+//PID 1.0 force feedback: effects are written with output reports, block allocation and the
+//pool go through feature reports and the device state comes back in an input report
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use super::{
+    desc_report::{
+        write_bits, GlobalItemsState, Report, ReportDescriptor, ReportDescriptorError, ReportType,
+    },
+    hid_usage::PhysicalInputDevice as Pid,
+};
+
+const PID_PAGE: u32 = 0x0F;
+const GENERIC_DESKTOP_PAGE: u32 = 0x01;
+const ORDINAL_PAGE: u32 = 0x0A;
+
+//magnitudes, levels, coefficients and gains are given in -10000..=10000 like DirectInput,
+//then scaled onto whatever logical range the device declares
+pub const FULL_SCALE: i32 = 10000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Waveform {
+    Square,
+    Sine,
+    Triangle,
+    SawtoothUp,
+    SawtoothDown,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ConditionType {
+    Spring,
+    Damper,
+    Inertia,
+    Friction,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Envelope {
+    pub attack_level: u16,
+    pub attack_time_ms: u16,
+    pub fade_level: u16,
+    pub fade_time_ms: u16,
+}
+
+//one Set Condition report, sent once per axis
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Condition {
+    pub center_point_offset: i16,
+    pub positive_coefficient: i16,
+    pub negative_coefficient: i16,
+    pub positive_saturation: u16,
+    pub negative_saturation: u16,
+    pub dead_band: u16,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum EffectKind {
+    Constant {
+        magnitude: i16,
+    },
+    Ramp {
+        start: i16,
+        end: i16,
+    },
+    Periodic {
+        waveform: Waveform,
+        magnitude: u16,
+        offset: i16,
+        //hundredths of a degree
+        phase: u16,
+        period_ms: u16,
+    },
+    Condition {
+        condition_type: ConditionType,
+        axes: Vec<Condition>,
+    },
+}
+
+impl EffectKind {
+    //ET usage selected in the Effect Type array
+    pub fn effect_type(&self) -> Pid {
+        match self {
+            EffectKind::Constant { .. } => Pid::EtConstantForce,
+            EffectKind::Ramp { .. } => Pid::EtRamp,
+            EffectKind::Periodic { waveform, .. } => match waveform {
+                Waveform::Square => Pid::EtSquare,
+                Waveform::Sine => Pid::EtSine,
+                Waveform::Triangle => Pid::EtTriangle,
+                Waveform::SawtoothUp => Pid::EtSawtoothUp,
+                Waveform::SawtoothDown => Pid::EtSawtoothDown,
+            },
+            EffectKind::Condition { condition_type, .. } => match condition_type {
+                ConditionType::Spring => Pid::EtSpring,
+                ConditionType::Damper => Pid::EtDamper,
+                ConditionType::Inertia => Pid::EtInertia,
+                ConditionType::Friction => Pid::EtFriction,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Effect {
+    pub kind: EffectKind,
+    //None plays until stopped
+    pub duration_ms: Option<u16>,
+    pub trigger_button: Option<u8>,
+    pub trigger_repeat_interval_ms: u16,
+    pub sample_period_ms: u16,
+    pub start_delay_ms: u16,
+    pub gain: u16,
+    //polar direction in hundredths of a degree, 0 pushes away from the user
+    pub direction: u16,
+    pub envelope: Option<Envelope>,
+}
+
+impl Effect {
+    pub fn new(kind: EffectKind) -> Self {
+        Self {
+            kind,
+            duration_ms: None,
+            trigger_button: None,
+            trigger_repeat_interval_ms: 0,
+            sample_period_ms: 0,
+            start_delay_ms: 0,
+            gain: FULL_SCALE as u16,
+            direction: 0,
+            envelope: None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum EffectOperation {
+    Start,
+    StartSolo,
+    Stop,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DeviceControl {
+    EnableActuators,
+    DisableActuators,
+    StopAllEffects,
+    Reset,
+    Pause,
+    Continue,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum BlockLoadStatus {
+    Success,
+    Full,
+    Error,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct BlockLoad {
+    pub effect_block_index: u8,
+    pub status: BlockLoadStatus,
+    pub ram_pool_available: Option<u32>,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PidState {
+    //the effect `effect_playing` refers to, devices without it report the device as a whole
+    pub effect_block_index: Option<u8>,
+    pub effect_playing: bool,
+    pub device_paused: bool,
+    pub actuators_enabled: bool,
+    pub safety_switch: bool,
+    pub actuator_override_switch: bool,
+    pub actuator_power: bool,
+}
+
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct PidPool {
+    pub ram_pool_size: Option<u32>,
+    pub simultaneous_effects_max: Option<u32>,
+    pub device_managed_pool: bool,
+    pub shared_parameter_blocks: bool,
+}
+
+//assignments for one report, usages the device did not declare are dropped
+struct ReportWriter<'a> {
+    report: &'a Report,
+    assignments: Vec<(u32, u32, i32)>,
+    //(field, element) filled with ones after encoding, bypassing the logical range clamp
+    all_ones: Vec<(usize, usize)>,
+}
+
+impl<'a> ReportWriter<'a> {
+    fn new(report: &'a Report) -> Self {
+        Self {
+            report,
+            assignments: Vec::new(),
+            all_ones: Vec::new(),
+        }
+    }
+
+    fn global(&self, page: u32, usage: u32) -> Option<&GlobalItemsState> {
+        let (field, _) = self.report.locate(page, usage)?;
+        Some(&self.report.fields[field].global_state)
+    }
+
+    fn raw(&mut self, page: u32, usage: u32, value: i32) -> &mut Self {
+        if self.report.declares(page, usage) {
+            self.assignments.push((page, usage, value));
+        }
+        self
+    }
+
+    //-FULL_SCALE..=FULL_SCALE onto the logical range, unsigned fields take magnitudes only
+    fn scaled(&mut self, usage: Pid, value: i32) -> &mut Self {
+        if let Some(global) = self.global(PID_PAGE, usage as u32) {
+            let value = value.clamp(-FULL_SCALE, FULL_SCALE);
+            let logical = if global.logical_minimum < 0 {
+                value as i64 * global.logical_maximum as i64 / FULL_SCALE as i64
+            } else {
+                value.max(0) as i64 * global.logical_maximum as i64 / FULL_SCALE as i64
+            };
+            self.assignments
+                .push((PID_PAGE, usage as u32, logical as i32));
+        }
+        self
+    }
+
+    //times are declared in seconds with an exponent, or as plain milliseconds without a unit
+    fn time(&mut self, usage: Pid, milliseconds: u32) -> &mut Self {
+        if let Some(global) = self.global(PID_PAGE, usage as u32) {
+            let logical = if global.unit == 0 {
+                milliseconds as i32
+            } else {
+                global.from_physical(milliseconds as f64 / 1000.0)
+            };
+            self.assignments.push((PID_PAGE, usage as u32, logical));
+        }
+        self
+    }
+
+    //hundredths of a degree, a unitless field spreads a full turn over its logical range
+    fn angle(&mut self, page: u32, usage: u32, hundredths: u16) -> &mut Self {
+        if let Some(global) = self.global(page, usage) {
+            let logical = if global.unit == 0 {
+                let span = global.logical_maximum as i64 - global.logical_minimum as i64 + 1;
+                global.logical_minimum + (hundredths as i64 % 36000 * span / 36000) as i32
+            } else {
+                global.from_physical(hundredths as f64 / 100.0)
+            };
+            self.assignments.push((page, usage, logical));
+        }
+        self
+    }
+
+    //PID 1.0 5.1: a duration of 0xFFFF (-1) plays until stopped and a trigger button of 0xFF
+    //means none, whatever the declared range
+    fn all_ones(&mut self, usage: Pid) -> &mut Self {
+        if let Some(location) = self.report.locate(PID_PAGE, usage as u32) {
+            self.all_ones.push(location);
+        }
+        self
+    }
+
+    fn encode(&self) -> Result<Vec<u8>, ReportDescriptorError> {
+        let mut data = self.report.encode(&self.assignments)?;
+        let prefix = self.report.report_id.is_some() as usize;
+        for (field, element) in &self.all_ones {
+            let field = &self.report.fields[*field];
+            let size = field.report_size();
+            write_bits(
+                &mut data[prefix..],
+                field.bit_offset + element * size,
+                size,
+                u32::MAX,
+            );
+        }
+        Ok(data)
+    }
+}
+
+pub struct PidDevice {
+    //PID report collection usage -> index into `ReportDescriptor::reports`
+    pub reports: BTreeMap<u16, usize>,
+}
+
+impl PidDevice {
+    //None unless the descriptor declares at least a Set Effect report
+    pub fn from_descriptor(descriptor: &ReportDescriptor) -> Option<Self> {
+        let mut reports = BTreeMap::new();
+        for (index, collection) in descriptor.collections.iter().enumerate() {
+            if collection.usage >> 16 != PID_PAGE
+                || reports.contains_key(&(collection.usage as u16))
+            {
+                continue;
+            }
+            if let Some((report, _)) = descriptor.collection_fields(index).first() {
+                reports.insert(collection.usage as u16, *report);
+            }
+        }
+        reports
+            .contains_key(&(Pid::SetEffectReport as u16))
+            .then_some(Self { reports })
+    }
+
+    fn report<'a>(
+        &self,
+        descriptor: &'a ReportDescriptor,
+        usage: Pid,
+    ) -> Result<&'a Report, ReportDescriptorError> {
+        self.reports
+            .get(&(usage as u16))
+            .map(|index| &descriptor.reports[*index])
+            .ok_or(ReportDescriptorError::UndeclaredUsage(
+                PID_PAGE,
+                usage as u32,
+            ))
+    }
+
+    pub fn supports(&self, descriptor: &ReportDescriptor, effect_type: Pid) -> bool {
+        self.report(descriptor, Pid::SetEffectReport)
+            .is_ok_and(|report| report.declares(PID_PAGE, effect_type as u32))
+    }
+
+    //SET_REPORT(Feature) asking a device managed pool for a new effect block, the answer is
+    //read back with GET_REPORT(Feature) on the block load report
+    pub fn create_new_effect(
+        &self,
+        descriptor: &ReportDescriptor,
+        kind: &EffectKind,
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        ReportWriter::new(self.report(descriptor, Pid::CreateNewEffectReport)?)
+            .raw(PID_PAGE, kind.effect_type() as u32, 1)
+            .encode()
+    }
+
+    //output reports to send in order: envelope, type specific block(s), then Set Effect
+    pub fn encode_effect(
+        &self,
+        descriptor: &ReportDescriptor,
+        effect_block_index: u8,
+        effect: &Effect,
+    ) -> Result<Vec<Vec<u8>>, ReportDescriptorError> {
+        let block = effect_block_index as i32;
+        let mut reports = Vec::new();
+
+        if let Some(envelope) = &effect.envelope {
+            let mut writer = ReportWriter::new(self.report(descriptor, Pid::SetEnvelopeReport)?);
+            writer
+                .raw(PID_PAGE, Pid::EffectBlockIndex as u32, block)
+                .scaled(Pid::AttackLevel, envelope.attack_level as i32)
+                .time(Pid::AttackTime, envelope.attack_time_ms as u32)
+                .scaled(Pid::FadeLevel, envelope.fade_level as i32)
+                .time(Pid::FadeTime, envelope.fade_time_ms as u32);
+            reports.push(writer.encode()?);
+        }
+
+        match &effect.kind {
+            EffectKind::Constant { magnitude } => {
+                let mut writer =
+                    ReportWriter::new(self.report(descriptor, Pid::SetConstantForceReport)?);
+                writer
+                    .raw(PID_PAGE, Pid::EffectBlockIndex as u32, block)
+                    .scaled(Pid::Magnitude, *magnitude as i32);
+                reports.push(writer.encode()?);
+            }
+            EffectKind::Ramp { start, end } => {
+                let mut writer =
+                    ReportWriter::new(self.report(descriptor, Pid::SetRampForceReport)?);
+                writer
+                    .raw(PID_PAGE, Pid::EffectBlockIndex as u32, block)
+                    .scaled(Pid::RampStart, *start as i32)
+                    .scaled(Pid::RampEnd, *end as i32);
+                reports.push(writer.encode()?);
+            }
+            EffectKind::Periodic {
+                magnitude,
+                offset,
+                phase,
+                period_ms,
+                ..
+            } => {
+                let mut writer =
+                    ReportWriter::new(self.report(descriptor, Pid::SetPeriodicReport)?);
+                writer
+                    .raw(PID_PAGE, Pid::EffectBlockIndex as u32, block)
+                    .scaled(Pid::Magnitude, *magnitude as i32)
+                    .scaled(Pid::Offset, *offset as i32)
+                    .angle(PID_PAGE, Pid::Phase as u32, *phase)
+                    .time(Pid::Period, *period_ms as u32);
+                reports.push(writer.encode()?);
+            }
+            EffectKind::Condition { axes, .. } => {
+                let report = self.report(descriptor, Pid::SetConditionReport)?;
+                for (axis, condition) in axes.iter().enumerate() {
+                    let mut writer = ReportWriter::new(report);
+                    writer
+                        .raw(PID_PAGE, Pid::EffectBlockIndex as u32, block)
+                        .raw(PID_PAGE, Pid::ParameterBlockOffset as u32, axis as i32)
+                        .scaled(Pid::CenterPointOffset, condition.center_point_offset as i32)
+                        .scaled(
+                            Pid::PositiveCoefficient,
+                            condition.positive_coefficient as i32,
+                        )
+                        .scaled(
+                            Pid::NegativeCoefficient,
+                            condition.negative_coefficient as i32,
+                        )
+                        .scaled(
+                            Pid::PositiveSaturation,
+                            condition.positive_saturation as i32,
+                        )
+                        .scaled(
+                            Pid::NegativeSaturation,
+                            condition.negative_saturation as i32,
+                        )
+                        .scaled(Pid::DeadBand, condition.dead_band as i32);
+                    reports.push(writer.encode()?);
+                }
+            }
+        }
+
+        let mut writer = ReportWriter::new(self.report(descriptor, Pid::SetEffectReport)?);
+        if effect.duration_ms.is_none() {
+            writer.all_ones(Pid::Duration);
+        }
+        match effect.trigger_button {
+            Some(button) => writer.raw(PID_PAGE, Pid::TriggerButton as u32, button as i32),
+            None => writer.all_ones(Pid::TriggerButton),
+        };
+        writer
+            .raw(PID_PAGE, Pid::EffectBlockIndex as u32, block)
+            .raw(PID_PAGE, effect.kind.effect_type() as u32, 1)
+            .time(Pid::Duration, effect.duration_ms.unwrap_or(0) as u32)
+            .time(
+                Pid::TriggerRepeatInterval,
+                effect.trigger_repeat_interval_ms as u32,
+            )
+            .time(Pid::SamplePeriod, effect.sample_period_ms as u32)
+            .time(Pid::StartDelay, effect.start_delay_ms as u32)
+            .scaled(Pid::Gain, effect.gain as i32)
+            .raw(PID_PAGE, Pid::DirectionEnable as u32, 1)
+            .raw(GENERIC_DESKTOP_PAGE, 0x30, 1)
+            .raw(GENERIC_DESKTOP_PAGE, 0x31, 1)
+            .angle(ORDINAL_PAGE, 1, effect.direction);
+        reports.push(writer.encode()?);
+        Ok(reports)
+    }
+
+    pub fn effect_operation(
+        &self,
+        descriptor: &ReportDescriptor,
+        effect_block_index: u8,
+        operation: EffectOperation,
+        loop_count: u8,
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        let operation = match operation {
+            EffectOperation::Start => Pid::OpEffectStart,
+            EffectOperation::StartSolo => Pid::OpEffectStartSolo,
+            EffectOperation::Stop => Pid::OpEffectStop,
+        };
+        ReportWriter::new(self.report(descriptor, Pid::EffectOperationReport)?)
+            .raw(
+                PID_PAGE,
+                Pid::EffectBlockIndex as u32,
+                effect_block_index as i32,
+            )
+            .raw(PID_PAGE, operation as u32, 1)
+            .raw(PID_PAGE, Pid::LoopCount as u32, loop_count as i32)
+            .encode()
+    }
+
+    pub fn block_free(
+        &self,
+        descriptor: &ReportDescriptor,
+        effect_block_index: u8,
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        ReportWriter::new(self.report(descriptor, Pid::PidBlockFreeReport)?)
+            .raw(
+                PID_PAGE,
+                Pid::EffectBlockIndex as u32,
+                effect_block_index as i32,
+            )
+            .encode()
+    }
+
+    pub fn device_control(
+        &self,
+        descriptor: &ReportDescriptor,
+        control: DeviceControl,
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        let control = match control {
+            DeviceControl::EnableActuators => Pid::DcEnableActuators,
+            DeviceControl::DisableActuators => Pid::DcDisableActuators,
+            DeviceControl::StopAllEffects => Pid::DcStopAllEffects,
+            DeviceControl::Reset => Pid::DcDeviceReset,
+            DeviceControl::Pause => Pid::DcDevicePause,
+            DeviceControl::Continue => Pid::DcDeviceContinue,
+        };
+        ReportWriter::new(self.report(descriptor, Pid::PidDeviceControlReport)?)
+            .raw(PID_PAGE, control as u32, 1)
+            .encode()
+    }
+
+    pub fn device_gain(
+        &self,
+        descriptor: &ReportDescriptor,
+        gain: u16,
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        ReportWriter::new(self.report(descriptor, Pid::DeviceGainReport)?)
+            .scaled(Pid::DeviceGain, gain as i32)
+            .encode()
+    }
+
+    //payload of `usage`'s report, None when `data` belongs to another report
+    fn payload<'a>(
+        &self,
+        descriptor: &'a ReportDescriptor,
+        usage: Pid,
+        report_type: ReportType,
+        data: &'a [u8],
+    ) -> Option<(&'a Report, &'a [u8])> {
+        let (index, payload) = descriptor.split_report(report_type, data).ok()?;
+        (self.reports.get(&(usage as u16)) == Some(&index))
+            .then(|| (&descriptor.reports[index], payload))
+    }
+
+    //GET_REPORT(Feature) answer to Create New Effect
+    pub fn block_load(&self, descriptor: &ReportDescriptor, data: &[u8]) -> Option<BlockLoad> {
+        let (report, payload) = self.payload(
+            descriptor,
+            Pid::PidBlockLoadReport,
+            ReportType::Feature,
+            data,
+        )?;
+        let status = match selected_usage(
+            report,
+            payload,
+            &[
+                Pid::BlockLoadSuccess,
+                Pid::BlockLoadFull,
+                Pid::BlockLoadError,
+            ],
+        )? {
+            Pid::BlockLoadSuccess => BlockLoadStatus::Success,
+            Pid::BlockLoadFull => BlockLoadStatus::Full,
+            _ => BlockLoadStatus::Error,
+        };
+        Some(BlockLoad {
+            effect_block_index: report.value(payload, PID_PAGE, Pid::EffectBlockIndex as u32)?
+                as u8,
+            status,
+            ram_pool_available: report
+                .value(payload, PID_PAGE, Pid::RamPoolAvailable as u32)
+                .map(|available| available as u32),
+        })
+    }
+
+    pub fn pool(&self, descriptor: &ReportDescriptor, data: &[u8]) -> Option<PidPool> {
+        let (report, payload) =
+            self.payload(descriptor, Pid::PidPoolReport, ReportType::Feature, data)?;
+        let value = |usage: Pid| report.value(payload, PID_PAGE, usage as u32);
+        Some(PidPool {
+            ram_pool_size: value(Pid::RamPoolSize).map(|size| size as u32),
+            simultaneous_effects_max: value(Pid::SimultaneousEffectsMax).map(|max| max as u32),
+            device_managed_pool: value(Pid::DeviceManagedPool).unwrap_or(0) != 0,
+            shared_parameter_blocks: value(Pid::SharedParameterBlocks).unwrap_or(0) != 0,
+        })
+    }
+
+    pub fn state(&self, descriptor: &ReportDescriptor, data: &[u8]) -> Option<PidState> {
+        let (report, payload) =
+            self.payload(descriptor, Pid::PidStateReport, ReportType::Input, data)?;
+        let flag = |usage: Pid| report.value(payload, PID_PAGE, usage as u32).unwrap_or(0) != 0;
+        Some(PidState {
+            effect_block_index: report
+                .value(payload, PID_PAGE, Pid::EffectBlockIndex as u32)
+                .map(|index| index as u8),
+            effect_playing: flag(Pid::EffectPlaying),
+            device_paused: flag(Pid::DevicePaused),
+            actuators_enabled: flag(Pid::ActuatorsEnabled),
+            safety_switch: flag(Pid::SafetySwitch),
+            actuator_override_switch: flag(Pid::ActuatorOverrideSwitch),
+            actuator_power: flag(Pid::ActuatorPower),
+        })
+    }
+}
+
+//usage selected in the first slot of the array field offering `candidates`
+fn selected_usage(report: &Report, payload: &[u8], candidates: &[Pid]) -> Option<Pid> {
+    let extended = |usage: &Pid| (PID_PAGE << 16) | *usage as u32;
+    let field = report.fields.iter().find(|field| {
+        !field.flags.is_variable()
+            && candidates
+                .iter()
+                .any(|usage| field.local_state.contains(extended(usage)))
+    })?;
+    let selector = field.read(payload, 0) - field.global_state.logical_minimum;
+    if selector < 0 || selector as u32 >= field.local_state.usage_count() {
+        return None;
+    }
+    let usage = field.usage(selector as usize)?;
+    candidates
+        .iter()
+        .find(|candidate| extended(candidate) == usage)
+        .copied()
+}
@@ -0,0 +1,612 @@
+//HID Sensor Usages (page 0x20): one collection per sensor, data fields in the input report and
+//properties in the feature report, data field usages may carry a modifier in bits 12..=15
+use alloc::{vec, vec::Vec};
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+use super::{
+    desc_report::{
+        write_bits, CollectionType, GlobalItemsState, Report, ReportDescriptor,
+        ReportDescriptorError, ReportField, ReportType,
+    },
+    hid_usage::Sensors,
+};
+
+const SENSORS_PAGE: u32 = 0x20;
+
+fn extended(id: u16) -> u32 {
+    (SENSORS_PAGE << 16) | id as u32
+}
+
+//HID Sensor Usages 1.0 table 4: what a modified data field usage describes
+#[derive(FromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum SensorModifier {
+    None = 0x0,
+    ChangeSensitivityAbsolute = 0x1,
+    Maximum = 0x2,
+    Minimum = 0x3,
+    Accuracy = 0x4,
+    Resolution = 0x5,
+    ThresholdHigh = 0x6,
+    ThresholdLow = 0x7,
+    CalibrationOffset = 0x8,
+    CalibrationMultiplier = 0x9,
+    ReportInterval = 0xA,
+    FrequencyMax = 0xB,
+    PeriodMax = 0xC,
+    ChangeSensitivityPercentOfRange = 0xD,
+    ChangeSensitivityPercentRelative = 0xE,
+    VendorReserved = 0xF,
+}
+
+impl SensorModifier {
+    pub fn split(usage: u16) -> (u16, SensorModifier) {
+        (
+            usage & 0x0FFF,
+            SensorModifier::from_u16(usage >> 12).unwrap_or(SensorModifier::None),
+        )
+    }
+
+    pub fn apply(&self, usage: u16) -> u16 {
+        (usage & 0x0FFF) | (*self as u16) << 12
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct FieldRef {
+    pub report: usize,
+    pub field: usize,
+    pub element: usize,
+}
+
+impl FieldRef {
+    fn field<'a>(&self, descriptor: &'a ReportDescriptor) -> &'a ReportField {
+        descriptor.field((self.report, self.field))
+    }
+}
+
+//one value of the input report
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct DataField {
+    //data field usage with the modifier bits cleared
+    pub usage: u16,
+    pub location: FieldRef,
+    pub unit: u32,
+    pub unit_exponent: i32,
+    //feature report element holding this field's Change Sensitivity Absolute, if declared
+    pub sensitivity: Option<FieldRef>,
+}
+
+impl DataField {
+    pub fn name(&self) -> Option<&'static str> {
+        Sensors::from_u16(self.usage).map(|usage| usage.name())
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PowerState {
+    Undefined,
+    D0FullPower,
+    D1LowPower,
+    D2StandbyWithWakeup,
+    D3SleepWithWakeup,
+    D4PowerOff,
+}
+
+impl PowerState {
+    const SELECTORS: [Sensors; 6] = [
+        Sensors::PowerStateUndefined,
+        Sensors::PowerStateD0FullPower,
+        Sensors::PowerStateD1LowPower,
+        Sensors::PowerStateD2StandbyPowerWithWakeup,
+        Sensors::PowerStateD3SleepWithWakeup,
+        Sensors::PowerStateD4PowerOff,
+    ];
+    const ALL: [PowerState; 6] = [
+        PowerState::Undefined,
+        PowerState::D0FullPower,
+        PowerState::D1LowPower,
+        PowerState::D2StandbyWithWakeup,
+        PowerState::D3SleepWithWakeup,
+        PowerState::D4PowerOff,
+    ];
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReportingState {
+    NoEvents,
+    AllEvents,
+    ThresholdEvents,
+    WakeOnNoEvents,
+    WakeOnAllEvents,
+    WakeOnThresholdEvents,
+}
+
+impl ReportingState {
+    const SELECTORS: [Sensors; 6] = [
+        Sensors::ReportingStateReportNoEvents,
+        Sensors::ReportingStateReportAllEvents,
+        Sensors::ReportingStateReportThresholdEvents,
+        Sensors::ReportingStateWakeOnNoEvents,
+        Sensors::ReportingStateWakeOnAllEvents,
+        Sensors::ReportingStateWakeOnThresholdEvents,
+    ];
+    const ALL: [ReportingState; 6] = [
+        ReportingState::NoEvents,
+        ReportingState::AllEvents,
+        ReportingState::ThresholdEvents,
+        ReportingState::WakeOnNoEvents,
+        ReportingState::WakeOnAllEvents,
+        ReportingState::WakeOnThresholdEvents,
+    ];
+}
+
+//writable properties, sensitivity is in the data field's physical units
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum SensorProperty {
+    ReportInterval { milliseconds: u32 },
+    Sensitivity { data_field: u16, value: f64 },
+    PowerState(PowerState),
+    ReportingState(ReportingState),
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SensorProperties {
+    pub report_interval_ms: Option<u32>,
+    pub minimum_report_interval_ms: Option<u32>,
+    pub power_state: Option<PowerState>,
+    pub reporting_state: Option<ReportingState>,
+    //(data field usage, sensitivity in its physical units)
+    pub sensitivity: Vec<(u16, f64)>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum SensorReading {
+    //g
+    Accelerometer {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    //degrees per second
+    Gyrometer {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    //milligauss
+    Compass {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    //degrees
+    Inclinometer {
+        x: f64,
+        y: f64,
+        z: f64,
+    },
+    Orientation {
+        quaternion: [f64; 4],
+    },
+    //lux and kelvin
+    AmbientLight {
+        illuminance: f64,
+        color_temperature: Option<f64>,
+    },
+    //degrees Celsius
+    Temperature(f64),
+    //percent
+    Humidity(f64),
+    //bar
+    AtmosphericPressure(f64),
+    HumanPresence(bool),
+    //data fields of sensors without a typed reading, or with fields missing
+    Other(Vec<(u16, f64)>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct SensorSample {
+    pub state: Option<Sensors>,
+    pub event: Option<Sensors>,
+    pub reading: SensorReading,
+}
+
+#[derive(Clone, Debug)]
+pub struct Sensor {
+    //sensor type usage, e.g. Motion: Accelerometer 3D
+    pub usage: u16,
+    pub collection: usize,
+    pub input_report: Option<usize>,
+    pub feature_report: Option<usize>,
+    pub data_fields: Vec<DataField>,
+}
+
+impl Sensor {
+    pub fn kind(&self) -> Option<Sensors> {
+        Sensors::from_u16(self.usage)
+    }
+
+    //every sensor collection of the descriptor, the generic Sensor collection only groups them
+    pub fn discover(descriptor: &ReportDescriptor) -> Vec<Sensor> {
+        descriptor
+            .collections
+            .iter()
+            .enumerate()
+            .filter(|(_, collection)| {
+                collection.usage >> 16 == SENSORS_PAGE
+                    && (0x02..=0xFF).contains(&(collection.usage & 0xffff))
+                    && matches!(
+                        collection.collection_type,
+                        CollectionType::Application | CollectionType::Physical
+                    )
+            })
+            .map(|(index, collection)| Self::new(descriptor, index, collection.usage as u16))
+            .collect()
+    }
+
+    fn new(descriptor: &ReportDescriptor, collection: usize, usage: u16) -> Self {
+        let fields = descriptor.collection_fields(collection);
+        let first = |report_type: ReportType| {
+            fields
+                .iter()
+                .map(|(report, _)| *report)
+                .find(|report| descriptor.reports[*report].report_type == report_type)
+        };
+        let input_report = first(ReportType::Input);
+        let feature_report = first(ReportType::Feature);
+
+        let mut data_fields = Vec::new();
+        for (report, field_index) in fields {
+            let field = descriptor.field((report, field_index));
+            if Some(report) != input_report || field.is_padding() || !field.flags.is_variable() {
+                continue;
+            }
+            for element in 0..field.report_count() {
+                let Some(usage) = field.usage(element) else {
+                    break;
+                };
+                let (id, modifier) = SensorModifier::split(usage as u16);
+                if usage >> 16 != SENSORS_PAGE
+                    || modifier != SensorModifier::None
+                    || !(0x400..0x800).contains(&id)
+                {
+                    continue;
+                }
+                let sensitivity = feature_report.and_then(|report| {
+                    let modified = |id| SensorModifier::ChangeSensitivityAbsolute.apply(id);
+                    Self::locate(descriptor, report, modified(id)).or_else(|| {
+                        Self::locate(descriptor, report, modified(parent_data_field(id)?))
+                    })
+                });
+                data_fields.push(DataField {
+                    usage: id,
+                    location: FieldRef {
+                        report,
+                        field: field_index,
+                        element,
+                    },
+                    unit: field.global_state.unit,
+                    unit_exponent: field.global_state.unit_exponent,
+                    sensitivity,
+                });
+            }
+        }
+        Self {
+            usage,
+            collection,
+            input_report,
+            feature_report,
+            data_fields,
+        }
+    }
+
+    fn locate(descriptor: &ReportDescriptor, report: usize, usage: u16) -> Option<FieldRef> {
+        descriptor.reports[report]
+            .locate(SENSORS_PAGE, usage as u32)
+            .map(|(field, element)| FieldRef {
+                report,
+                field,
+                element,
+            })
+    }
+
+    //array field offering `selectors`, e.g. the Power State named array
+    fn selector_field<'a>(report: &'a Report, selectors: &[Sensors]) -> Option<&'a ReportField> {
+        report.fields.iter().find(|field| {
+            !field.flags.is_variable()
+                && selectors
+                    .iter()
+                    .any(|selector| field.local_state.contains(extended(*selector as u16)))
+        })
+    }
+
+    fn read_selector(report: &Report, payload: &[u8], selectors: &[Sensors]) -> Option<usize> {
+        let field = Self::selector_field(report, selectors)?;
+        let index = field.read(payload, 0) - field.global_state.logical_minimum;
+        let usage = field.usage(usize::try_from(index).ok()?)?;
+        selectors
+            .iter()
+            .position(|selector| extended(*selector as u16) == usage)
+    }
+
+    //decode an input report, None if it belongs to another sensor
+    pub fn sample(&self, descriptor: &ReportDescriptor, data: &[u8]) -> Option<SensorSample> {
+        let (report_index, payload) = descriptor.split_report(ReportType::Input, data).ok()?;
+        if Some(report_index) != self.input_report {
+            return None;
+        }
+        let report = &descriptor.reports[report_index];
+        let selected = |candidates: core::ops::RangeInclusive<u16>| {
+            let selectors: Vec<_> = candidates.filter_map(Sensors::from_u16).collect();
+            Self::read_selector(report, payload, &selectors).map(|index| selectors[index])
+        };
+        let values: Vec<(u16, f64)> = self
+            .data_fields
+            .iter()
+            .map(|data_field| {
+                let field = data_field.location.field(descriptor);
+                let raw = field.read(payload, data_field.location.element);
+                (data_field.usage, field.global_state.to_physical(raw))
+            })
+            .collect();
+        Some(SensorSample {
+            state: selected(0x800..=0x806),
+            event: selected(0x810..=0x820),
+            reading: self.reading(values),
+        })
+    }
+
+    fn reading(&self, values: Vec<(u16, f64)>) -> SensorReading {
+        let value = |usage: Sensors| {
+            values
+                .iter()
+                .find(|(id, _)| *id == usage as u16)
+                .map(|(_, value)| *value)
+        };
+        let axes = |x: Sensors, y: Sensors, z: Sensors| Some((value(x)?, value(y)?, value(z)?));
+        let reading = match self.kind() {
+            Some(Sensors::MotionAccelerometer3D) => axes(
+                Sensors::DataFieldAccelerationAxisX,
+                Sensors::DataFieldAccelerationAxisY,
+                Sensors::DataFieldAccelerationAxisZ,
+            )
+            .map(|(x, y, z)| SensorReading::Accelerometer { x, y, z }),
+            Some(Sensors::MotionGyrometer3D) => axes(
+                Sensors::DataFieldAngularVelocityAboutXAxis,
+                Sensors::DataFieldAngularVelocityAboutYAxis,
+                Sensors::DataFieldAngularVelocityAboutZAxis,
+            )
+            .map(|(x, y, z)| SensorReading::Gyrometer { x, y, z }),
+            Some(Sensors::OrientationCompass3D) => axes(
+                Sensors::DataFieldMagneticFluxXAxis,
+                Sensors::DataFieldMagneticFluxYAxis,
+                Sensors::DataFieldMagneticFluxZAxis,
+            )
+            .map(|(x, y, z)| SensorReading::Compass { x, y, z }),
+            Some(Sensors::OrientationInclinometer3D) => axes(
+                Sensors::DataFieldTiltXAxis,
+                Sensors::DataFieldTiltYAxis,
+                Sensors::DataFieldTiltZAxis,
+            )
+            .map(|(x, y, z)| SensorReading::Inclinometer { x, y, z }),
+            //the quaternion is one usage with a report count of 4
+            Some(Sensors::OrientationDeviceOrientation) => {
+                let quaternion: Vec<f64> = values
+                    .iter()
+                    .filter(|(id, _)| *id == Sensors::DataFieldQuaternion as u16)
+                    .map(|(_, value)| *value)
+                    .collect();
+                quaternion
+                    .try_into()
+                    .ok()
+                    .map(|quaternion| SensorReading::Orientation { quaternion })
+            }
+            Some(Sensors::LightAmbientLight) => {
+                value(Sensors::DataFieldIlluminance).map(|illuminance| {
+                    SensorReading::AmbientLight {
+                        illuminance,
+                        color_temperature: value(Sensors::DataFieldColorTemperature),
+                    }
+                })
+            }
+            Some(Sensors::EnvironmentalTemperature) => {
+                value(Sensors::DataFieldTemperature).map(SensorReading::Temperature)
+            }
+            Some(Sensors::EnvironmentalHumidity) => {
+                value(Sensors::DataFieldRelativeHumidity).map(SensorReading::Humidity)
+            }
+            Some(Sensors::EnvironmentalAtmosphericPressure) => {
+                value(Sensors::DataFieldAtmosphericPressure).map(SensorReading::AtmosphericPressure)
+            }
+            Some(Sensors::BiometricHumanPresence) => value(Sensors::DataFieldHumanPresence)
+                .map(|present| SensorReading::HumanPresence(present != 0.0)),
+            _ => None,
+        };
+        reading.unwrap_or(SensorReading::Other(values))
+    }
+
+    fn feature<'a>(&self, descriptor: &'a ReportDescriptor) -> Option<&'a Report> {
+        self.feature_report
+            .map(|report| &descriptor.reports[report])
+    }
+
+    //decode a GET_REPORT(Feature) response
+    pub fn properties(
+        &self,
+        descriptor: &ReportDescriptor,
+        data: &[u8],
+    ) -> Option<SensorProperties> {
+        let (report_index, payload) = descriptor.split_report(ReportType::Feature, data).ok()?;
+        if Some(report_index) != self.feature_report {
+            return None;
+        }
+        let report = &descriptor.reports[report_index];
+        let interval = |usage: Sensors| {
+            let (field, element) = report.locate(SENSORS_PAGE, usage as u32)?;
+            let field = &report.fields[field];
+            Some(milliseconds_from(
+                &field.global_state,
+                field.read(payload, element),
+            ))
+        };
+        Some(SensorProperties {
+            report_interval_ms: interval(Sensors::PropertyReportInterval),
+            minimum_report_interval_ms: interval(Sensors::PropertyMinimumReportInterval),
+            power_state: Self::read_selector(report, payload, &PowerState::SELECTORS)
+                .map(|index| PowerState::ALL[index]),
+            reporting_state: Self::read_selector(report, payload, &ReportingState::SELECTORS)
+                .map(|index| ReportingState::ALL[index]),
+            sensitivity: self
+                .data_fields
+                .iter()
+                .filter_map(|data_field| {
+                    let location = data_field.sensitivity?;
+                    let field = location.field(descriptor);
+                    let raw = field.read(payload, location.element);
+                    Some((data_field.usage, field.global_state.to_physical(raw)))
+                })
+                .collect(),
+        })
+    }
+
+    //SET_REPORT(Feature) payload, starting from `current` (a GET_REPORT response) so that
+    //properties not being written keep their values
+    pub fn set_properties(
+        &self,
+        descriptor: &ReportDescriptor,
+        current: Option<&[u8]>,
+        properties: &[SensorProperty],
+    ) -> Result<Vec<u8>, ReportDescriptorError> {
+        let report = self
+            .feature(descriptor)
+            .ok_or(ReportDescriptorError::UndeclaredUsage(
+                SENSORS_PAGE,
+                Sensors::Property as u32,
+            ))?;
+        let prefix = report.report_id.is_some() as usize;
+        let mut data = match current {
+            Some(current) if current.len() == report.byte_length() => current.to_vec(),
+            _ => {
+                let mut data = vec![0u8; report.byte_length()];
+                if let Some(id) = report.report_id {
+                    data[0] = id;
+                }
+                data
+            }
+        };
+
+        for property in properties {
+            let (field, element, value) = self.property_value(report, property)?;
+            let size = field.report_size();
+            write_bits(
+                &mut data[prefix..],
+                field.bit_offset + element * size,
+                size,
+                value as u32,
+            );
+        }
+        Ok(data)
+    }
+
+    fn property_value<'a>(
+        &self,
+        report: &'a Report,
+        property: &SensorProperty,
+    ) -> Result<(&'a ReportField, usize, i32), ReportDescriptorError> {
+        let undeclared =
+            |usage: u16| ReportDescriptorError::UndeclaredUsage(SENSORS_PAGE, usage as u32);
+        let variable = |usage: u16| {
+            report
+                .locate(SENSORS_PAGE, usage as u32)
+                .map(|(field, element)| (&report.fields[field], element))
+                .ok_or(undeclared(usage))
+        };
+        let selector = |selectors: &[Sensors], index: usize| {
+            let usage = extended(selectors[index] as u16);
+            let field = Self::selector_field(report, selectors)
+                .ok_or(undeclared(selectors[index] as u16))?;
+            let position = (0..field.local_state.usage_count() as usize)
+                .position(|selector| field.usage(selector) == Some(usage))
+                .ok_or(undeclared(selectors[index] as u16))?;
+            Ok((
+                field,
+                0,
+                field.global_state.logical_minimum + position as i32,
+            ))
+        };
+        match *property {
+            SensorProperty::ReportInterval { milliseconds } => {
+                let (field, element) = variable(Sensors::PropertyReportInterval as u16)?;
+                Ok((
+                    field,
+                    element,
+                    milliseconds_to(&field.global_state, milliseconds),
+                ))
+            }
+            SensorProperty::Sensitivity { data_field, value } => {
+                //per field sensitivity first, then the one of its group, then the sensor wide
+                //property
+                let modified = |id| SensorModifier::ChangeSensitivityAbsolute.apply(id);
+                let (field, element) = variable(modified(data_field))
+                    .or_else(|error| {
+                        parent_data_field(data_field)
+                            .ok_or(error)
+                            .and_then(|parent| variable(modified(parent)))
+                    })
+                    .or_else(|_| variable(Sensors::PropertyChangeSensitivityAbsolute as u16))?;
+                Ok((field, element, field.global_state.from_physical(value)))
+            }
+            SensorProperty::PowerState(state) => {
+                let index = PowerState::ALL
+                    .iter()
+                    .position(|all| *all == state)
+                    .unwrap();
+                selector(&PowerState::SELECTORS, index)
+            }
+            SensorProperty::ReportingState(state) => {
+                let index = ReportingState::ALL
+                    .iter()
+                    .position(|all| *all == state)
+                    .unwrap();
+                selector(&ReportingState::SELECTORS, index)
+            }
+        }
+    }
+}
+
+//axis data fields share the properties of the vector usage before them, sensors usually
+//declare Change Sensitivity Absolute once for Acceleration rather than for each axis
+fn parent_data_field(usage: u16) -> Option<u16> {
+    match usage {
+        0x453..=0x455 => Some(Sensors::DataFieldAcceleration as u16),
+        0x457..=0x459 => Some(Sensors::DataFieldAngularVelocity as u16),
+        0x45B..=0x45D => Some(Sensors::DataFieldAngularPosition as u16),
+        0x472..=0x474 => Some(Sensors::DataFieldHeading as u16),
+        0x47A..=0x47C => Some(Sensors::DataFieldDistance as u16),
+        0x47F..=0x481 => Some(Sensors::DataFieldTilt as u16),
+        0x485..=0x487 => Some(Sensors::DataFieldMagneticFlux as u16),
+        0x4D4..=0x4D5 => Some(Sensors::DataFieldChromaticity as u16),
+        _ => None,
+    }
+}
+
+//report intervals are declared in seconds with an exponent, or as plain milliseconds
+fn milliseconds_from(global: &GlobalItemsState, logical: i32) -> u32 {
+    if global.unit == 0 {
+        logical as u32
+    } else {
+        let milliseconds = global.to_physical(logical) * 1000.0;
+        (milliseconds + 0.5) as u32
+    }
+}
+
+fn milliseconds_to(global: &GlobalItemsState, milliseconds: u32) -> i32 {
+    if global.unit == 0 {
+        milliseconds as i32
+    } else {
+        global.from_physical(milliseconds as f64 / 1000.0)
+    }
+}
@@ -0,0 +1,1628 @@
+//HID Usage Tables 1.4, only the pages the decoders use are spelled out
+use core::fmt;
+
+use num_derive::FromPrimitive;
+use num_traits::FromPrimitive;
+
+//HUT 3.4 usage types
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UsageType {
+    LinearControl,
+    OnOffControl,
+    MomentaryControl,
+    OneShotControl,
+    ReTriggerControl,
+    Selector,
+    StaticValue,
+    StaticFlag,
+    DynamicValue,
+    DynamicFlag,
+    NamedArray,
+    CollectionApplication,
+    CollectionLogical,
+    CollectionPhysical,
+    UsageSwitch,
+    UsageModifier,
+}
+
+impl UsageType {
+    pub fn abbreviation(&self) -> &'static str {
+        match self {
+            UsageType::LinearControl => "LC",
+            UsageType::OnOffControl => "OOC",
+            UsageType::MomentaryControl => "MC",
+            UsageType::OneShotControl => "OSC",
+            UsageType::ReTriggerControl => "RTC",
+            UsageType::Selector => "Sel",
+            UsageType::StaticValue => "SV",
+            UsageType::StaticFlag => "SF",
+            UsageType::DynamicValue => "DV",
+            UsageType::DynamicFlag => "DF",
+            UsageType::NamedArray => "NAry",
+            UsageType::CollectionApplication => "CA",
+            UsageType::CollectionLogical => "CL",
+            UsageType::CollectionPhysical => "CP",
+            UsageType::UsageSwitch => "US",
+            UsageType::UsageModifier => "UM",
+        }
+    }
+}
+
+macro_rules! usage_table {
+    ($name:ident { $($variant:ident = $value:literal, $text:literal, $($ty:ident)|+;)* }) => {
+        #[derive(FromPrimitive, Copy, Clone, Debug, PartialEq, Eq)]
+        #[repr(u16)]
+        pub enum $name {
+            $($variant = $value,)*
+        }
+
+        impl $name {
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $($name::$variant => $text,)*
+                }
+            }
+
+            pub fn usage_types(&self) -> &'static [UsageType] {
+                use UsageType::*;
+                match self {
+                    $($name::$variant => &[$(usage_type!($ty)),+],)*
+                }
+            }
+        }
+    };
+}
+
+macro_rules! usage_type {
+    (LC) => {
+        LinearControl
+    };
+    (OOC) => {
+        OnOffControl
+    };
+    (MC) => {
+        MomentaryControl
+    };
+    (OSC) => {
+        OneShotControl
+    };
+    (RTC) => {
+        ReTriggerControl
+    };
+    (Sel) => {
+        Selector
+    };
+    (SV) => {
+        StaticValue
+    };
+    (SF) => {
+        StaticFlag
+    };
+    (DV) => {
+        DynamicValue
+    };
+    (DF) => {
+        DynamicFlag
+    };
+    (NAry) => {
+        NamedArray
+    };
+    (CA) => {
+        CollectionApplication
+    };
+    (CL) => {
+        CollectionLogical
+    };
+    (CP) => {
+        CollectionPhysical
+    };
+    (US) => {
+        UsageSwitch
+    };
+    (UM) => {
+        UsageModifier
+    };
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UsagePage {
+    GenericDesktop,
+    SimulationControls,
+    VrControls,
+    SportControls,
+    GameControls,
+    GenericDeviceControls,
+    Keyboard,
+    Led,
+    Button,
+    Ordinal,
+    Telephony,
+    Consumer,
+    Digitizer,
+    Haptics,
+    PhysicalInputDevice,
+    Unicode,
+    Soc,
+    EyeAndHeadTrackers,
+    AuxiliaryDisplay,
+    Sensors,
+    MedicalInstrument,
+    BrailleDisplay,
+    LightingAndIllumination,
+    Monitor,
+    MonitorEnumerated,
+    VesaVirtualControls,
+    PowerDevice,
+    BatterySystem,
+    BarcodeScanner,
+    Scales,
+    MagneticStripeReader,
+    CameraControl,
+    Arcade,
+    GamingDevice,
+    FidoAlliance,
+    Vendor(u16),
+    Reserved(u16),
+}
+
+impl From<u16> for UsagePage {
+    fn from(value: u16) -> Self {
+        match value {
+            0x01 => UsagePage::GenericDesktop,
+            0x02 => UsagePage::SimulationControls,
+            0x03 => UsagePage::VrControls,
+            0x04 => UsagePage::SportControls,
+            0x05 => UsagePage::GameControls,
+            0x06 => UsagePage::GenericDeviceControls,
+            0x07 => UsagePage::Keyboard,
+            0x08 => UsagePage::Led,
+            0x09 => UsagePage::Button,
+            0x0A => UsagePage::Ordinal,
+            0x0B => UsagePage::Telephony,
+            0x0C => UsagePage::Consumer,
+            0x0D => UsagePage::Digitizer,
+            0x0E => UsagePage::Haptics,
+            0x0F => UsagePage::PhysicalInputDevice,
+            0x10 => UsagePage::Unicode,
+            0x11 => UsagePage::Soc,
+            0x12 => UsagePage::EyeAndHeadTrackers,
+            0x14 => UsagePage::AuxiliaryDisplay,
+            0x20 => UsagePage::Sensors,
+            0x40 => UsagePage::MedicalInstrument,
+            0x41 => UsagePage::BrailleDisplay,
+            0x59 => UsagePage::LightingAndIllumination,
+            0x80 => UsagePage::Monitor,
+            0x81 => UsagePage::MonitorEnumerated,
+            0x82 => UsagePage::VesaVirtualControls,
+            0x84 => UsagePage::PowerDevice,
+            0x85 => UsagePage::BatterySystem,
+            0x8C => UsagePage::BarcodeScanner,
+            0x8D => UsagePage::Scales,
+            0x8E => UsagePage::MagneticStripeReader,
+            0x90 => UsagePage::CameraControl,
+            0x91 => UsagePage::Arcade,
+            0x92 => UsagePage::GamingDevice,
+            0xF1D0 => UsagePage::FidoAlliance,
+            vendor @ 0xFF00..=0xFFFF => UsagePage::Vendor(vendor),
+            reserved => UsagePage::Reserved(reserved),
+        }
+    }
+}
+
+impl From<UsagePage> for u16 {
+    fn from(value: UsagePage) -> Self {
+        match value {
+            UsagePage::GenericDesktop => 0x01,
+            UsagePage::SimulationControls => 0x02,
+            UsagePage::VrControls => 0x03,
+            UsagePage::SportControls => 0x04,
+            UsagePage::GameControls => 0x05,
+            UsagePage::GenericDeviceControls => 0x06,
+            UsagePage::Keyboard => 0x07,
+            UsagePage::Led => 0x08,
+            UsagePage::Button => 0x09,
+            UsagePage::Ordinal => 0x0A,
+            UsagePage::Telephony => 0x0B,
+            UsagePage::Consumer => 0x0C,
+            UsagePage::Digitizer => 0x0D,
+            UsagePage::Haptics => 0x0E,
+            UsagePage::PhysicalInputDevice => 0x0F,
+            UsagePage::Unicode => 0x10,
+            UsagePage::Soc => 0x11,
+            UsagePage::EyeAndHeadTrackers => 0x12,
+            UsagePage::AuxiliaryDisplay => 0x14,
+            UsagePage::Sensors => 0x20,
+            UsagePage::MedicalInstrument => 0x40,
+            UsagePage::BrailleDisplay => 0x41,
+            UsagePage::LightingAndIllumination => 0x59,
+            UsagePage::Monitor => 0x80,
+            UsagePage::MonitorEnumerated => 0x81,
+            UsagePage::VesaVirtualControls => 0x82,
+            UsagePage::PowerDevice => 0x84,
+            UsagePage::BatterySystem => 0x85,
+            UsagePage::BarcodeScanner => 0x8C,
+            UsagePage::Scales => 0x8D,
+            UsagePage::MagneticStripeReader => 0x8E,
+            UsagePage::CameraControl => 0x90,
+            UsagePage::Arcade => 0x91,
+            UsagePage::GamingDevice => 0x92,
+            UsagePage::FidoAlliance => 0xF1D0,
+            UsagePage::Vendor(page) | UsagePage::Reserved(page) => page,
+        }
+    }
+}
+
+impl UsagePage {
+    pub fn name(&self) -> &'static str {
+        match self {
+            UsagePage::GenericDesktop => "Generic Desktop",
+            UsagePage::SimulationControls => "Simulation Controls",
+            UsagePage::VrControls => "VR Controls",
+            UsagePage::SportControls => "Sport Controls",
+            UsagePage::GameControls => "Game Controls",
+            UsagePage::GenericDeviceControls => "Generic Device Controls",
+            UsagePage::Keyboard => "Keyboard",
+            UsagePage::Led => "LED",
+            UsagePage::Button => "Button",
+            UsagePage::Ordinal => "Ordinal",
+            UsagePage::Telephony => "Telephony",
+            UsagePage::Consumer => "Consumer",
+            UsagePage::Digitizer => "Digitizers",
+            UsagePage::Haptics => "Haptics",
+            UsagePage::PhysicalInputDevice => "Physical Input Device",
+            UsagePage::Unicode => "Unicode",
+            UsagePage::Soc => "SoC",
+            UsagePage::EyeAndHeadTrackers => "Eye and Head Trackers",
+            UsagePage::AuxiliaryDisplay => "Auxiliary Display",
+            UsagePage::Sensors => "Sensors",
+            UsagePage::MedicalInstrument => "Medical Instrument",
+            UsagePage::BrailleDisplay => "Braille Display",
+            UsagePage::LightingAndIllumination => "Lighting And Illumination",
+            UsagePage::Monitor => "Monitor",
+            UsagePage::MonitorEnumerated => "Monitor Enumerated",
+            UsagePage::VesaVirtualControls => "VESA Virtual Controls",
+            UsagePage::PowerDevice => "Power Device",
+            UsagePage::BatterySystem => "Battery System",
+            UsagePage::BarcodeScanner => "Barcode Scanner",
+            UsagePage::Scales => "Scales",
+            UsagePage::MagneticStripeReader => "Magnetic Stripe Reader",
+            UsagePage::CameraControl => "Camera Control",
+            UsagePage::Arcade => "Arcade",
+            UsagePage::GamingDevice => "Gaming Device",
+            UsagePage::FidoAlliance => "FIDO Alliance",
+            UsagePage::Vendor(_) => "Vendor Defined",
+            UsagePage::Reserved(_) => "Reserved",
+        }
+    }
+}
+
+impl fmt::Display for UsagePage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UsagePage::Vendor(page) => write!(f, "Vendor Defined Page 0x{:04X}", page),
+            UsagePage::Reserved(page) => write!(f, "Reserved Page 0x{:04X}", page),
+            page => f.write_str(page.name()),
+        }
+    }
+}
+
+#[rustfmt::skip]
+usage_table!(GenericDesktop {
+    Pointer = 0x01, "Pointer", CP;
+    Mouse = 0x02, "Mouse", CA;
+    Joystick = 0x04, "Joystick", CA;
+    Gamepad = 0x05, "Gamepad", CA;
+    Keyboard = 0x06, "Keyboard", CA;
+    Keypad = 0x07, "Keypad", CA;
+    MultiAxisController = 0x08, "Multi-axis Controller", CA;
+    TabletPcSystemControls = 0x09, "Tablet PC System Controls", CA;
+    WaterCoolingDevice = 0x0A, "Water Cooling Device", CA;
+    ComputerChassisDevice = 0x0B, "Computer Chassis Device", CA;
+    WirelessRadioControls = 0x0C, "Wireless Radio Controls", CA;
+    PortableDeviceControl = 0x0D, "Portable Device Control", CA;
+    SystemMultiAxisController = 0x0E, "System Multi-Axis Controller", CA;
+    SpatialController = 0x0F, "Spatial Controller", CA;
+    AssistiveControl = 0x10, "Assistive Control", CA;
+    DeviceDock = 0x11, "Device Dock", CA;
+    DockableDevice = 0x12, "Dockable Device", CA;
+    CallStateManagementControl = 0x13, "Call State Management Control", CA;
+    X = 0x30, "X", DV;
+    Y = 0x31, "Y", DV;
+    Z = 0x32, "Z", DV;
+    Rx = 0x33, "Rx", DV;
+    Ry = 0x34, "Ry", DV;
+    Rz = 0x35, "Rz", DV;
+    Slider = 0x36, "Slider", DV;
+    Dial = 0x37, "Dial", DV;
+    Wheel = 0x38, "Wheel", DV;
+    HatSwitch = 0x39, "Hat Switch", DV;
+    CountedBuffer = 0x3A, "Counted Buffer", CL;
+    ByteCount = 0x3B, "Byte Count", DV;
+    MotionWakeup = 0x3C, "Motion Wakeup", OSC | DF;
+    Start = 0x3D, "Start", OOC;
+    Select = 0x3E, "Select", OOC;
+    Vx = 0x40, "Vx", DV;
+    Vy = 0x41, "Vy", DV;
+    Vz = 0x42, "Vz", DV;
+    Vbrx = 0x43, "Vbrx", DV;
+    Vbry = 0x44, "Vbry", DV;
+    Vbrz = 0x45, "Vbrz", DV;
+    Vno = 0x46, "Vno", DV;
+    FeatureNotification = 0x47, "Feature Notification", DV | DF;
+    ResolutionMultiplier = 0x48, "Resolution Multiplier", DV;
+    Qx = 0x49, "Qx", DV;
+    Qy = 0x4A, "Qy", DV;
+    Qz = 0x4B, "Qz", DV;
+    Qw = 0x4C, "Qw", DV;
+    SystemControl = 0x80, "System Control", CA;
+    SystemPowerDown = 0x81, "System Power Down", OSC;
+    SystemSleep = 0x82, "System Sleep", OSC;
+    SystemWakeUp = 0x83, "System Wake Up", OSC;
+    SystemContextMenu = 0x84, "System Context Menu", OSC;
+    SystemMainMenu = 0x85, "System Main Menu", OSC;
+    SystemAppMenu = 0x86, "System App Menu", OSC;
+    SystemMenuHelp = 0x87, "System Menu Help", OSC;
+    SystemMenuExit = 0x88, "System Menu Exit", OSC;
+    SystemMenuSelect = 0x89, "System Menu Select", OSC;
+    SystemMenuRight = 0x8A, "System Menu Right", RTC;
+    SystemMenuLeft = 0x8B, "System Menu Left", RTC;
+    SystemMenuUp = 0x8C, "System Menu Up", RTC;
+    SystemMenuDown = 0x8D, "System Menu Down", RTC;
+    SystemColdRestart = 0x8E, "System Cold Restart", OSC;
+    SystemWarmRestart = 0x8F, "System Warm Restart", OSC;
+    DpadUp = 0x90, "D-pad Up", OOC;
+    DpadDown = 0x91, "D-pad Down", OOC;
+    DpadRight = 0x92, "D-pad Right", OOC;
+    DpadLeft = 0x93, "D-pad Left", OOC;
+    IndexTrigger = 0x94, "Index Trigger", MC | DV;
+    PalmTrigger = 0x95, "Palm Trigger", MC | DV;
+    Thumbstick = 0x96, "Thumbstick", CP;
+    SystemFunctionShift = 0x97, "System Function Shift", MC;
+    SystemFunctionShiftLock = 0x98, "System Function Shift Lock", OOC;
+    SystemFunctionShiftLockIndicator = 0x99, "System Function Shift Lock Indicator", DV;
+    SystemDismissNotification = 0x9A, "System Dismiss Notification", OSC;
+    SystemDoNotDisturb = 0x9B, "System Do Not Disturb", OOC;
+    SystemDock = 0xA0, "System Dock", OSC;
+    SystemUndock = 0xA1, "System Undock", OSC;
+    SystemSetup = 0xA2, "System Setup", OSC;
+    SystemBreak = 0xA3, "System Break", OSC;
+    SystemDebuggerBreak = 0xA4, "System Debugger Break", OSC;
+    ApplicationBreak = 0xA5, "Application Break", OSC;
+    ApplicationDebuggerBreak = 0xA6, "Application Debugger Break", OSC;
+    SystemSpeakerMute = 0xA7, "System Speaker Mute", OSC;
+    SystemHibernate = 0xA8, "System Hibernate", OSC;
+    SystemDisplayInvert = 0xB0, "System Display Invert", OSC;
+    SystemDisplayInternal = 0xB1, "System Display Internal", OSC;
+    SystemDisplayExternal = 0xB2, "System Display External", OSC;
+    SystemDisplayBoth = 0xB3, "System Display Both", OSC;
+    SystemDisplayDual = 0xB4, "System Display Dual", OSC;
+    SystemDisplayToggleIntExtMode = 0xB5, "System Display Toggle Int/Ext Mode", OSC;
+    SystemDisplaySwapPrimarySecondary = 0xB6, "System Display Swap Primary/Secondary", OSC;
+    SystemDisplayToggleLcdAutoscale = 0xB7, "System Display Toggle LCD Autoscale", OSC;
+    SensorZone = 0xC0, "Sensor Zone", CL;
+    Rpm = 0xC1, "RPM", DV;
+    CoolantLevel = 0xC2, "Coolant Level", DV;
+    CoolantCriticalLevel = 0xC3, "Coolant Critical Level", SV;
+    CoolantPump = 0xC4, "Coolant Pump", US;
+    ChassisEnclosure = 0xC5, "Chassis Enclosure", CL;
+    WirelessRadioButton = 0xC6, "Wireless Radio Button", OOC;
+    WirelessRadioLed = 0xC7, "Wireless Radio LED", OOC;
+    WirelessRadioSliderSwitch = 0xC8, "Wireless Radio Slider Switch", OOC;
+    SystemDisplayRotationLockButton = 0xC9, "System Display Rotation Lock Button", OOC;
+    SystemDisplayRotationLockSliderSwitch = 0xCA, "System Display Rotation Lock Slider Switch", OOC;
+    ControlEnable = 0xCB, "Control Enable", DF;
+});
+
+#[rustfmt::skip]
+usage_table!(KeyboardUsage {
+    ErrorRollOver = 0x01, "Keyboard ErrorRollOver", Sel;
+    PostFail = 0x02, "Keyboard POSTFail", Sel;
+    ErrorUndefined = 0x03, "Keyboard ErrorUndefined", Sel;
+    A = 0x04, "Keyboard a and A", Sel;
+    B = 0x05, "Keyboard b and B", Sel;
+    C = 0x06, "Keyboard c and C", Sel;
+    D = 0x07, "Keyboard d and D", Sel;
+    E = 0x08, "Keyboard e and E", Sel;
+    F = 0x09, "Keyboard f and F", Sel;
+    G = 0x0A, "Keyboard g and G", Sel;
+    H = 0x0B, "Keyboard h and H", Sel;
+    I = 0x0C, "Keyboard i and I", Sel;
+    J = 0x0D, "Keyboard j and J", Sel;
+    K = 0x0E, "Keyboard k and K", Sel;
+    L = 0x0F, "Keyboard l and L", Sel;
+    M = 0x10, "Keyboard m and M", Sel;
+    N = 0x11, "Keyboard n and N", Sel;
+    O = 0x12, "Keyboard o and O", Sel;
+    P = 0x13, "Keyboard p and P", Sel;
+    Q = 0x14, "Keyboard q and Q", Sel;
+    R = 0x15, "Keyboard r and R", Sel;
+    S = 0x16, "Keyboard s and S", Sel;
+    T = 0x17, "Keyboard t and T", Sel;
+    U = 0x18, "Keyboard u and U", Sel;
+    V = 0x19, "Keyboard v and V", Sel;
+    W = 0x1A, "Keyboard w and W", Sel;
+    X = 0x1B, "Keyboard x and X", Sel;
+    Y = 0x1C, "Keyboard y and Y", Sel;
+    Z = 0x1D, "Keyboard z and Z", Sel;
+    Key1 = 0x1E, "Keyboard 1 and !", Sel;
+    Key2 = 0x1F, "Keyboard 2 and @", Sel;
+    Key3 = 0x20, "Keyboard 3 and #", Sel;
+    Key4 = 0x21, "Keyboard 4 and $", Sel;
+    Key5 = 0x22, "Keyboard 5 and %", Sel;
+    Key6 = 0x23, "Keyboard 6 and ^", Sel;
+    Key7 = 0x24, "Keyboard 7 and &", Sel;
+    Key8 = 0x25, "Keyboard 8 and *", Sel;
+    Key9 = 0x26, "Keyboard 9 and (", Sel;
+    Key0 = 0x27, "Keyboard 0 and )", Sel;
+    Enter = 0x28, "Keyboard Return (ENTER)", Sel;
+    Escape = 0x29, "Keyboard ESCAPE", Sel;
+    Backspace = 0x2A, "Keyboard DELETE (Backspace)", Sel;
+    Tab = 0x2B, "Keyboard Tab", Sel;
+    Spacebar = 0x2C, "Keyboard Spacebar", Sel;
+    Minus = 0x2D, "Keyboard - and (underscore)", Sel;
+    Equal = 0x2E, "Keyboard = and +", Sel;
+    LeftBracket = 0x2F, "Keyboard [ and {", Sel;
+    RightBracket = 0x30, "Keyboard ] and }", Sel;
+    Backslash = 0x31, "Keyboard \\ and |", Sel;
+    NonUsHash = 0x32, "Keyboard Non-US # and ~", Sel;
+    Semicolon = 0x33, "Keyboard ; and :", Sel;
+    Apostrophe = 0x34, "Keyboard ' and \"", Sel;
+    Grave = 0x35, "Keyboard Grave Accent and Tilde", Sel;
+    Comma = 0x36, "Keyboard , and <", Sel;
+    Period = 0x37, "Keyboard . and >", Sel;
+    Slash = 0x38, "Keyboard / and ?", Sel;
+    CapsLock = 0x39, "Keyboard Caps Lock", Sel;
+    F1 = 0x3A, "Keyboard F1", Sel;
+    F2 = 0x3B, "Keyboard F2", Sel;
+    F3 = 0x3C, "Keyboard F3", Sel;
+    F4 = 0x3D, "Keyboard F4", Sel;
+    F5 = 0x3E, "Keyboard F5", Sel;
+    F6 = 0x3F, "Keyboard F6", Sel;
+    F7 = 0x40, "Keyboard F7", Sel;
+    F8 = 0x41, "Keyboard F8", Sel;
+    F9 = 0x42, "Keyboard F9", Sel;
+    F10 = 0x43, "Keyboard F10", Sel;
+    F11 = 0x44, "Keyboard F11", Sel;
+    F12 = 0x45, "Keyboard F12", Sel;
+    PrintScreen = 0x46, "Keyboard PrintScreen", Sel;
+    ScrollLock = 0x47, "Keyboard Scroll Lock", Sel;
+    Pause = 0x48, "Keyboard Pause", Sel;
+    Insert = 0x49, "Keyboard Insert", Sel;
+    Home = 0x4A, "Keyboard Home", Sel;
+    PageUp = 0x4B, "Keyboard PageUp", Sel;
+    Delete = 0x4C, "Keyboard Delete Forward", Sel;
+    End = 0x4D, "Keyboard End", Sel;
+    PageDown = 0x4E, "Keyboard PageDown", Sel;
+    RightArrow = 0x4F, "Keyboard RightArrow", Sel;
+    LeftArrow = 0x50, "Keyboard LeftArrow", Sel;
+    DownArrow = 0x51, "Keyboard DownArrow", Sel;
+    UpArrow = 0x52, "Keyboard UpArrow", Sel;
+    NumLock = 0x53, "Keypad Num Lock and Clear", Sel;
+    KeypadSlash = 0x54, "Keypad /", Sel;
+    KeypadAsterisk = 0x55, "Keypad *", Sel;
+    KeypadMinus = 0x56, "Keypad -", Sel;
+    KeypadPlus = 0x57, "Keypad +", Sel;
+    KeypadEnter = 0x58, "Keypad ENTER", Sel;
+    Keypad1 = 0x59, "Keypad 1 and End", Sel;
+    Keypad2 = 0x5A, "Keypad 2 and Down Arrow", Sel;
+    Keypad3 = 0x5B, "Keypad 3 and PageDn", Sel;
+    Keypad4 = 0x5C, "Keypad 4 and Left Arrow", Sel;
+    Keypad5 = 0x5D, "Keypad 5", Sel;
+    Keypad6 = 0x5E, "Keypad 6 and Right Arrow", Sel;
+    Keypad7 = 0x5F, "Keypad 7 and Home", Sel;
+    Keypad8 = 0x60, "Keypad 8 and Up Arrow", Sel;
+    Keypad9 = 0x61, "Keypad 9 and PageUp", Sel;
+    Keypad0 = 0x62, "Keypad 0 and Insert", Sel;
+    KeypadPeriod = 0x63, "Keypad . and Delete", Sel;
+    NonUsBackslash = 0x64, "Keyboard Non-US \\ and |", Sel;
+    Application = 0x65, "Keyboard Application", Sel;
+    Power = 0x66, "Keyboard Power", Sel;
+    KeypadEqual = 0x67, "Keypad =", Sel;
+    F13 = 0x68, "Keyboard F13", Sel;
+    F14 = 0x69, "Keyboard F14", Sel;
+    F15 = 0x6A, "Keyboard F15", Sel;
+    F16 = 0x6B, "Keyboard F16", Sel;
+    F17 = 0x6C, "Keyboard F17", Sel;
+    F18 = 0x6D, "Keyboard F18", Sel;
+    F19 = 0x6E, "Keyboard F19", Sel;
+    F20 = 0x6F, "Keyboard F20", Sel;
+    F21 = 0x70, "Keyboard F21", Sel;
+    F22 = 0x71, "Keyboard F22", Sel;
+    F23 = 0x72, "Keyboard F23", Sel;
+    F24 = 0x73, "Keyboard F24", Sel;
+    Execute = 0x74, "Keyboard Execute", Sel;
+    Help = 0x75, "Keyboard Help", Sel;
+    Menu = 0x76, "Keyboard Menu", Sel;
+    Select = 0x77, "Keyboard Select", Sel;
+    Stop = 0x78, "Keyboard Stop", Sel;
+    Again = 0x79, "Keyboard Again", Sel;
+    Undo = 0x7A, "Keyboard Undo", Sel;
+    Cut = 0x7B, "Keyboard Cut", Sel;
+    Copy = 0x7C, "Keyboard Copy", Sel;
+    Paste = 0x7D, "Keyboard Paste", Sel;
+    Find = 0x7E, "Keyboard Find", Sel;
+    Mute = 0x7F, "Keyboard Mute", Sel;
+    VolumeUp = 0x80, "Keyboard Volume Up", Sel;
+    VolumeDown = 0x81, "Keyboard Volume Down", Sel;
+    LockingCapsLock = 0x82, "Keyboard Locking Caps Lock", Sel;
+    LockingNumLock = 0x83, "Keyboard Locking Num Lock", Sel;
+    LockingScrollLock = 0x84, "Keyboard Locking Scroll Lock", Sel;
+    KeypadComma = 0x85, "Keypad Comma", Sel;
+    KeypadEqualSign = 0x86, "Keypad Equal Sign", Sel;
+    International1 = 0x87, "Keyboard International1", Sel;
+    International2 = 0x88, "Keyboard International2", Sel;
+    International3 = 0x89, "Keyboard International3", Sel;
+    International4 = 0x8A, "Keyboard International4", Sel;
+    International5 = 0x8B, "Keyboard International5", Sel;
+    International6 = 0x8C, "Keyboard International6", Sel;
+    International7 = 0x8D, "Keyboard International7", Sel;
+    International8 = 0x8E, "Keyboard International8", Sel;
+    International9 = 0x8F, "Keyboard International9", Sel;
+    Lang1 = 0x90, "Keyboard LANG1", Sel;
+    Lang2 = 0x91, "Keyboard LANG2", Sel;
+    Lang3 = 0x92, "Keyboard LANG3", Sel;
+    Lang4 = 0x93, "Keyboard LANG4", Sel;
+    Lang5 = 0x94, "Keyboard LANG5", Sel;
+    Lang6 = 0x95, "Keyboard LANG6", Sel;
+    Lang7 = 0x96, "Keyboard LANG7", Sel;
+    Lang8 = 0x97, "Keyboard LANG8", Sel;
+    Lang9 = 0x98, "Keyboard LANG9", Sel;
+    AlternateErase = 0x99, "Keyboard Alternate Erase", Sel;
+    SysReq = 0x9A, "Keyboard SysReq/Attention", Sel;
+    Cancel = 0x9B, "Keyboard Cancel", Sel;
+    Clear = 0x9C, "Keyboard Clear", Sel;
+    Prior = 0x9D, "Keyboard Prior", Sel;
+    Return = 0x9E, "Keyboard Return", Sel;
+    Separator = 0x9F, "Keyboard Separator", Sel;
+    Out = 0xA0, "Keyboard Out", Sel;
+    Oper = 0xA1, "Keyboard Oper", Sel;
+    ClearAgain = 0xA2, "Keyboard Clear/Again", Sel;
+    CrSel = 0xA3, "Keyboard CrSel/Props", Sel;
+    ExSel = 0xA4, "Keyboard ExSel", Sel;
+    LeftControl = 0xE0, "Keyboard LeftControl", DV;
+    LeftShift = 0xE1, "Keyboard LeftShift", DV;
+    LeftAlt = 0xE2, "Keyboard LeftAlt", DV;
+    LeftGui = 0xE3, "Keyboard Left GUI", DV;
+    RightControl = 0xE4, "Keyboard RightControl", DV;
+    RightShift = 0xE5, "Keyboard RightShift", DV;
+    RightAlt = 0xE6, "Keyboard RightAlt", DV;
+    RightGui = 0xE7, "Keyboard Right GUI", DV;
+});
+
+#[rustfmt::skip]
+usage_table!(Led {
+    NumLock = 0x01, "Num Lock", OOC;
+    CapsLock = 0x02, "Caps Lock", OOC;
+    ScrollLock = 0x03, "Scroll Lock", OOC;
+    Compose = 0x04, "Compose", OOC;
+    Kana = 0x05, "Kana", OOC;
+    Power = 0x06, "Power", OOC;
+    Shift = 0x07, "Shift", OOC;
+    DoNotDisturb = 0x08, "Do Not Disturb", OOC;
+    Mute = 0x09, "Mute", OOC;
+    ToneEnable = 0x0A, "Tone Enable", OOC;
+    HighCutFilter = 0x0B, "High Cut Filter", OOC;
+    LowCutFilter = 0x0C, "Low Cut Filter", OOC;
+    EqualizerEnable = 0x0D, "Equalizer Enable", OOC;
+    SoundFieldOn = 0x0E, "Sound Field On", OOC;
+    SurroundOn = 0x0F, "Surround On", OOC;
+    Repeat = 0x10, "Repeat", OOC;
+    Stereo = 0x11, "Stereo", OOC;
+    SamplingRateDetect = 0x12, "Sampling Rate Detect", OOC;
+    Spinning = 0x13, "Spinning", OOC;
+    Cav = 0x14, "CAV", OOC;
+    Clv = 0x15, "CLV", OOC;
+    RecordingFormatDetect = 0x16, "Recording Format Detect", OOC;
+    OffHook = 0x17, "Off-Hook", OOC;
+    Ring = 0x18, "Ring", OOC;
+    MessageWaiting = 0x19, "Message Waiting", OOC;
+    DataMode = 0x1A, "Data Mode", OOC;
+    BatteryOperation = 0x1B, "Battery Operation", OOC;
+    BatteryOk = 0x1C, "Battery OK", OOC;
+    BatteryLow = 0x1D, "Battery Low", OOC;
+    Speaker = 0x1E, "Speaker", OOC;
+    HeadSet = 0x1F, "Head Set", OOC;
+    Hold = 0x20, "Hold", OOC;
+    Microphone = 0x21, "Microphone", OOC;
+    Coverage = 0x22, "Coverage", OOC;
+    NightMode = 0x23, "Night Mode", OOC;
+    SendCalls = 0x24, "Send Calls", OOC;
+    CallPickup = 0x25, "Call Pickup", OOC;
+    Conference = 0x26, "Conference", OOC;
+    StandBy = 0x27, "Stand-by", OOC;
+    CameraOn = 0x28, "Camera On", OOC;
+    CameraOff = 0x29, "Camera Off", OOC;
+    OnLine = 0x2A, "On-Line", OOC;
+    OffLine = 0x2B, "Off-Line", OOC;
+    Busy = 0x2C, "Busy", OOC;
+    Ready = 0x2D, "Ready", OOC;
+    PaperOut = 0x2E, "Paper-Out", OOC;
+    PaperJam = 0x2F, "Paper-Jam", OOC;
+    Remote = 0x30, "Remote", OOC;
+    Forward = 0x31, "Forward", OOC;
+    Reverse = 0x32, "Reverse", OOC;
+    Stop = 0x33, "Stop", OOC;
+    Rewind = 0x34, "Rewind", OOC;
+    FastForward = 0x35, "Fast Forward", OOC;
+    Play = 0x36, "Play", OOC;
+    Pause = 0x37, "Pause", OOC;
+    Record = 0x38, "Record", OOC;
+    Error = 0x39, "Error", OOC;
+    UsageSelectedIndicator = 0x3A, "Usage Selected Indicator", US;
+    UsageInUseIndicator = 0x3B, "Usage In Use Indicator", US;
+    UsageMultiModeIndicator = 0x3C, "Usage Multi Mode Indicator", UM;
+    IndicatorOn = 0x3D, "Indicator On", Sel;
+    IndicatorFlash = 0x3E, "Indicator Flash", Sel;
+    IndicatorSlowBlink = 0x3F, "Indicator Slow Blink", Sel;
+    IndicatorFastBlink = 0x40, "Indicator Fast Blink", Sel;
+    IndicatorOff = 0x41, "Indicator Off", Sel;
+    FlashOnTime = 0x42, "Flash On Time", DV;
+    SlowBlinkOnTime = 0x43, "Slow Blink On Time", DV;
+    SlowBlinkOffTime = 0x44, "Slow Blink Off Time", DV;
+    FastBlinkOnTime = 0x45, "Fast Blink On Time", DV;
+    FastBlinkOffTime = 0x46, "Fast Blink Off Time", DV;
+    UsageIndicatorColor = 0x47, "Usage Indicator Color", UM;
+    IndicatorRed = 0x48, "Indicator Red", Sel;
+    IndicatorGreen = 0x49, "Indicator Green", Sel;
+    IndicatorAmber = 0x4A, "Indicator Amber", Sel;
+    GenericIndicator = 0x4B, "Generic Indicator", OOC;
+    SystemSuspend = 0x4C, "System Suspend", OOC;
+    ExternalPowerConnected = 0x4D, "External Power Connected", OOC;
+});
+
+#[rustfmt::skip]
+usage_table!(Consumer {
+    ConsumerControl = 0x01, "Consumer Control", CA;
+    NumericKeyPad = 0x02, "Numeric Key Pad", NAry;
+    ProgrammableButtons = 0x03, "Programmable Buttons", NAry;
+    Microphone = 0x04, "Microphone", CA;
+    Headphone = 0x05, "Headphone", CA;
+    GraphicEqualizer = 0x06, "Graphic Equalizer", CA;
+    Plus10 = 0x20, "+10", OSC;
+    Plus100 = 0x21, "+100", OSC;
+    AmPm = 0x22, "AM/PM", OSC;
+    Power = 0x30, "Power", OOC;
+    Reset = 0x31, "Reset", OSC;
+    Sleep = 0x32, "Sleep", OSC;
+    SleepAfter = 0x33, "Sleep After", OSC;
+    SleepMode = 0x34, "Sleep Mode", RTC;
+    Illumination = 0x35, "Illumination", OOC;
+    FunctionButtons = 0x36, "Function Buttons", NAry;
+    Menu = 0x40, "Menu", OOC;
+    MenuPick = 0x41, "Menu Pick", OSC;
+    MenuUp = 0x42, "Menu Up", OSC;
+    MenuDown = 0x43, "Menu Down", OSC;
+    MenuLeft = 0x44, "Menu Left", OSC;
+    MenuRight = 0x45, "Menu Right", OSC;
+    MenuEscape = 0x46, "Menu Escape", OSC;
+    MenuValueIncrease = 0x47, "Menu Value Increase", OSC;
+    MenuValueDecrease = 0x48, "Menu Value Decrease", OSC;
+    DataOnScreen = 0x60, "Data On Screen", OOC;
+    ClosedCaption = 0x61, "Closed Caption", OOC;
+    ClosedCaptionSelect = 0x62, "Closed Caption Select", OSC;
+    VcrTv = 0x63, "VCR/TV", OOC;
+    BroadcastMode = 0x64, "Broadcast Mode", OSC;
+    Snapshot = 0x65, "Snapshot", OSC;
+    Still = 0x66, "Still", OSC;
+    PictureInPictureToggle = 0x67, "Picture-in-Picture Toggle", OSC;
+    PictureInPictureSwap = 0x68, "Picture-in-Picture Swap", OSC;
+    RedMenuButton = 0x69, "Red Menu Button", MC;
+    GreenMenuButton = 0x6A, "Green Menu Button", MC;
+    BlueMenuButton = 0x6B, "Blue Menu Button", MC;
+    YellowMenuButton = 0x6C, "Yellow Menu Button", MC;
+    Aspect = 0x6D, "Aspect", OSC;
+    ThreeDModeSelect = 0x6E, "3D Mode Select", OSC;
+    DisplayBrightnessIncrement = 0x6F, "Display Brightness Increment", RTC;
+    DisplayBrightnessDecrement = 0x70, "Display Brightness Decrement", RTC;
+    DisplayBrightness = 0x71, "Display Brightness", LC;
+    DisplayBacklightToggle = 0x72, "Display Backlight Toggle", OOC;
+    DisplaySetBrightnessToMinimum = 0x73, "Display Set Brightness to Minimum", OSC;
+    DisplaySetBrightnessToMaximum = 0x74, "Display Set Brightness to Maximum", OSC;
+    DisplaySetAutoBrightness = 0x75, "Display Set Auto Brightness", OOC;
+    Selection = 0x80, "Selection", NAry;
+    AssignSelection = 0x81, "Assign Selection", OSC;
+    ModeStep = 0x82, "Mode Step", OSC;
+    RecallLast = 0x83, "Recall Last", OSC;
+    EnterChannel = 0x84, "Enter Channel", OSC;
+    OrderMovie = 0x85, "Order Movie", OSC;
+    Channel = 0x86, "Channel", LC;
+    MediaSelection = 0x87, "Media Selection", NAry;
+    Quit = 0x94, "Quit", OSC;
+    Help = 0x95, "Help", OOC;
+    ChannelIncrement = 0x9C, "Channel Increment", OSC;
+    ChannelDecrement = 0x9D, "Channel Decrement", OSC;
+    Play = 0xB0, "Play", OOC;
+    Pause = 0xB1, "Pause", OOC;
+    Record = 0xB2, "Record", OOC;
+    FastForward = 0xB3, "Fast Forward", OOC;
+    Rewind = 0xB4, "Rewind", OOC;
+    ScanNextTrack = 0xB5, "Scan Next Track", OSC;
+    ScanPreviousTrack = 0xB6, "Scan Previous Track", OSC;
+    Stop = 0xB7, "Stop", OSC;
+    Eject = 0xB8, "Eject", OSC;
+    RandomPlay = 0xB9, "Random Play", OOC;
+    SelectDisc = 0xBA, "Select Disc", NAry;
+    EnterDisc = 0xBB, "Enter Disc", MC;
+    Repeat = 0xBC, "Repeat", OSC;
+    Tracking = 0xBD, "Tracking", LC;
+    TrackNormal = 0xBE, "Track Normal", OSC;
+    SlowTracking = 0xBF, "Slow Tracking", LC;
+    FrameForward = 0xC0, "Frame Forward", RTC;
+    FrameBack = 0xC1, "Frame Back", RTC;
+    Mark = 0xC2, "Mark", OSC;
+    ClearMark = 0xC3, "Clear Mark", OSC;
+    RepeatFromMark = 0xC4, "Repeat From Mark", OOC;
+    ReturnToMark = 0xC5, "Return To Mark", OSC;
+    SearchMarkForward = 0xC6, "Search Mark Forward", OSC;
+    SearchMarkBackwards = 0xC7, "Search Mark Backwards", OSC;
+    CounterReset = 0xC8, "Counter Reset", OSC;
+    ShowCounter = 0xC9, "Show Counter", OSC;
+    TrackingIncrement = 0xCA, "Tracking Increment", RTC;
+    TrackingDecrement = 0xCB, "Tracking Decrement", RTC;
+    StopEject = 0xCC, "Stop/Eject", OSC;
+    PlayPause = 0xCD, "Play/Pause", OSC;
+    PlaySkip = 0xCE, "Play/Skip", OSC;
+    VoiceCommand = 0xCF, "Voice Command", OSC;
+    InvokeCaptureInterface = 0xD0, "Invoke Capture Interface", Sel;
+    StartOrStopGameRecording = 0xD1, "Start or Stop Game Recording", Sel;
+    HistoricalGameCapture = 0xD2, "Historical Game Capture", Sel;
+    CaptureGameScreenshot = 0xD3, "Capture Game Screenshot", Sel;
+    ShowOrHideRecordingIndicator = 0xD4, "Show or Hide Recording Indicator", Sel;
+    StartOrStopMicrophoneCapture = 0xD5, "Start or Stop Microphone Capture", Sel;
+    StartOrStopCameraCapture = 0xD6, "Start or Stop Camera Capture", Sel;
+    StartOrStopGameBroadcast = 0xD7, "Start or Stop Game Broadcast", Sel;
+    StartOrStopVoiceDictationSession = 0xD8, "Start or Stop Voice Dictation Session", OOC;
+    InvokeDismissEmojiPicker = 0xD9, "Invoke/Dismiss Emoji Picker", OOC;
+    Volume = 0xE0, "Volume", LC;
+    Balance = 0xE1, "Balance", LC;
+    Mute = 0xE2, "Mute", OOC;
+    Bass = 0xE3, "Bass", LC;
+    Treble = 0xE4, "Treble", LC;
+    BassBoost = 0xE5, "Bass Boost", OOC;
+    SurroundMode = 0xE6, "Surround Mode", OSC;
+    Loudness = 0xE7, "Loudness", OOC;
+    Mpx = 0xE8, "MPX", OOC;
+    VolumeIncrement = 0xE9, "Volume Increment", RTC;
+    VolumeDecrement = 0xEA, "Volume Decrement", RTC;
+    SpeedSelect = 0xF0, "Speed Select", OSC;
+    PlaybackSpeed = 0xF1, "Playback Speed", NAry;
+    StandardPlay = 0xF2, "Standard Play", Sel;
+    LongPlay = 0xF3, "Long Play", Sel;
+    ExtendedPlay = 0xF4, "Extended Play", Sel;
+    Slow = 0xF5, "Slow", OSC;
+    FanEnable = 0x100, "Fan Enable", OOC;
+    FanSpeed = 0x101, "Fan Speed", LC;
+    LightEnable = 0x102, "Light Enable", OOC;
+    LightIlluminationLevel = 0x103, "Light Illumination Level", LC;
+    ClimateControlEnable = 0x104, "Climate Control Enable", OOC;
+    RoomTemperature = 0x105, "Room Temperature", LC;
+    SecurityEnable = 0x106, "Security Enable", OOC;
+    FireAlarm = 0x107, "Fire Alarm", OSC;
+    PoliceAlarm = 0x108, "Police Alarm", OSC;
+    Proximity = 0x109, "Proximity", LC;
+    Motion = 0x10A, "Motion", OSC;
+    DuressAlarm = 0x10B, "Duress Alarm", OSC;
+    HoldupAlarm = 0x10C, "Holdup Alarm", OSC;
+    MedicalAlarm = 0x10D, "Medical Alarm", OSC;
+    BalanceRight = 0x150, "Balance Right", RTC;
+    BalanceLeft = 0x151, "Balance Left", RTC;
+    BassIncrement = 0x152, "Bass Increment", RTC;
+    BassDecrement = 0x153, "Bass Decrement", RTC;
+    TrebleIncrement = 0x154, "Treble Increment", RTC;
+    TrebleDecrement = 0x155, "Treble Decrement", RTC;
+    ApplicationLaunchButtons = 0x180, "Application Launch Buttons", NAry;
+    AlLaunchButtonConfigurationTool = 0x181, "AL Launch Button Configuration Tool", Sel;
+    AlProgrammableButtonConfiguration = 0x182, "AL Programmable Button Configuration", Sel;
+    AlConsumerControlConfiguration = 0x183, "AL Consumer Control Configuration", Sel;
+    AlWordProcessor = 0x184, "AL Word Processor", Sel;
+    AlTextEditor = 0x185, "AL Text Editor", Sel;
+    AlSpreadsheet = 0x186, "AL Spreadsheet", Sel;
+    AlGraphicsEditor = 0x187, "AL Graphics Editor", Sel;
+    AlPresentationApp = 0x188, "AL Presentation App", Sel;
+    AlDatabaseApp = 0x189, "AL Database App", Sel;
+    AlEmailReader = 0x18A, "AL Email Reader", Sel;
+    AlNewsreader = 0x18B, "AL Newsreader", Sel;
+    AlVoicemail = 0x18C, "AL Voicemail", Sel;
+    AlContactsAddressBook = 0x18D, "AL Contacts/Address Book", Sel;
+    AlCalendarSchedule = 0x18E, "AL Calendar/Schedule", Sel;
+    AlTaskProjectManager = 0x18F, "AL Task/Project Manager", Sel;
+    AlLogJournalTimecard = 0x190, "AL Log/Journal/Timecard", Sel;
+    AlCheckbookFinance = 0x191, "AL Checkbook/Finance", Sel;
+    AlCalculator = 0x192, "AL Calculator", Sel;
+    AlAvCapturePlayback = 0x193, "AL A/V Capture/Playback", Sel;
+    AlLocalMachineBrowser = 0x194, "AL Local Machine Browser", Sel;
+    AlLanWanBrowser = 0x195, "AL LAN/WAN Browser", Sel;
+    AlInternetBrowser = 0x196, "AL Internet Browser", Sel;
+    AlRemoteNetworkingIspConnect = 0x197, "AL Remote Networking/ISP Connect", Sel;
+    AlNetworkConference = 0x198, "AL Network Conference", Sel;
+    AlNetworkChat = 0x199, "AL Network Chat", Sel;
+    AlTelephonyDialer = 0x19A, "AL Telephony/Dialer", Sel;
+    AlLogon = 0x19B, "AL Logon", Sel;
+    AlLogoff = 0x19C, "AL Logoff", Sel;
+    AlLogonLogoff = 0x19D, "AL Logon/Logoff", Sel;
+    AlTerminalLockScreensaver = 0x19E, "AL Terminal Lock/Screensaver", Sel;
+    AlControlPanel = 0x19F, "AL Control Panel", Sel;
+    AlCommandLineProcessorRun = 0x1A0, "AL Command Line Processor/Run", Sel;
+    AlProcessTaskManager = 0x1A1, "AL Process/Task Manager", Sel;
+    AlSelectTaskApplication = 0x1A2, "AL Select Task/Application", Sel;
+    AlNextTaskApplication = 0x1A3, "AL Next Task/Application", Sel;
+    AlPreviousTaskApplication = 0x1A4, "AL Previous Task/Application", Sel;
+    AlPreemptiveHaltTaskApplication = 0x1A5, "AL Preemptive Halt Task/Application", Sel;
+    AlIntegratedHelpCenter = 0x1A6, "AL Integrated Help Center", Sel;
+    AlDocuments = 0x1A7, "AL Documents", Sel;
+    AlThesaurus = 0x1A8, "AL Thesaurus", Sel;
+    AlDictionary = 0x1A9, "AL Dictionary", Sel;
+    AlDesktop = 0x1AA, "AL Desktop", Sel;
+    AlSpellCheck = 0x1AB, "AL Spell Check", Sel;
+    AlGrammarCheck = 0x1AC, "AL Grammar Check", Sel;
+    AlWirelessStatus = 0x1AD, "AL Wireless Status", Sel;
+    AlKeyboardLayout = 0x1AE, "AL Keyboard Layout", Sel;
+    AlVirusProtection = 0x1AF, "AL Virus Protection", Sel;
+    AlEncryption = 0x1B0, "AL Encryption", Sel;
+    AlScreenSaver = 0x1B1, "AL Screen Saver", Sel;
+    AlAlarms = 0x1B2, "AL Alarms", Sel;
+    AlClock = 0x1B3, "AL Clock", Sel;
+    AlFileBrowser = 0x1B4, "AL File Browser", Sel;
+    AlPowerStatus = 0x1B5, "AL Power Status", Sel;
+    AlImageBrowser = 0x1B6, "AL Image Browser", Sel;
+    AlAudioBrowser = 0x1B7, "AL Audio Browser", Sel;
+    AlMovieBrowser = 0x1B8, "AL Movie Browser", Sel;
+    AlDigitalRightsManager = 0x1B9, "AL Digital Rights Manager", Sel;
+    AlDigitalWallet = 0x1BA, "AL Digital Wallet", Sel;
+    AlInstantMessaging = 0x1BC, "AL Instant Messaging", Sel;
+    AlOemFeaturesTipsTutorialBrowser = 0x1BD, "AL OEM Features/ Tips/Tutorial Browser", Sel;
+    AlOemHelp = 0x1BE, "AL OEM Help", Sel;
+    AlOnlineCommunity = 0x1BF, "AL Online Community", Sel;
+    GenericGuiApplicationControls = 0x200, "Generic GUI Application Controls", NAry;
+    AcNew = 0x201, "AC New", Sel;
+    AcOpen = 0x202, "AC Open", Sel;
+    AcClose = 0x203, "AC Close", Sel;
+    AcExit = 0x204, "AC Exit", Sel;
+    AcMaximize = 0x205, "AC Maximize", Sel;
+    AcMinimize = 0x206, "AC Minimize", Sel;
+    AcSave = 0x207, "AC Save", Sel;
+    AcPrint = 0x208, "AC Print", Sel;
+    AcProperties = 0x209, "AC Properties", Sel;
+    AcUndo = 0x21A, "AC Undo", Sel;
+    AcCopy = 0x21B, "AC Copy", Sel;
+    AcCut = 0x21C, "AC Cut", Sel;
+    AcPaste = 0x21D, "AC Paste", Sel;
+    AcSelectAll = 0x21E, "AC Select All", Sel;
+    AcFind = 0x21F, "AC Find", Sel;
+    AcFindAndReplace = 0x220, "AC Find and Replace", Sel;
+    AcSearch = 0x221, "AC Search", Sel;
+    AcGoTo = 0x222, "AC Go To", Sel;
+    AcHome = 0x223, "AC Home", Sel;
+    AcBack = 0x224, "AC Back", Sel;
+    AcForward = 0x225, "AC Forward", Sel;
+    AcStop = 0x226, "AC Stop", Sel;
+    AcRefresh = 0x227, "AC Refresh", Sel;
+    AcPreviousLink = 0x228, "AC Previous Link", Sel;
+    AcNextLink = 0x229, "AC Next Link", Sel;
+    AcBookmarks = 0x22A, "AC Bookmarks", Sel;
+    AcHistory = 0x22B, "AC History", Sel;
+    AcSubscriptions = 0x22C, "AC Subscriptions", Sel;
+    AcZoomIn = 0x22D, "AC Zoom In", Sel;
+    AcZoomOut = 0x22E, "AC Zoom Out", Sel;
+    AcZoom = 0x22F, "AC Zoom", LC;
+    AcFullScreenView = 0x230, "AC Full Screen View", Sel;
+    AcNormalView = 0x231, "AC Normal View", Sel;
+    AcViewToggle = 0x232, "AC View Toggle", Sel;
+    AcScrollUp = 0x233, "AC Scroll Up", Sel;
+    AcScrollDown = 0x234, "AC Scroll Down", Sel;
+    AcScroll = 0x235, "AC Scroll", LC;
+    AcPanLeft = 0x236, "AC Pan Left", Sel;
+    AcPanRight = 0x237, "AC Pan Right", Sel;
+    AcPan = 0x238, "AC Pan", LC;
+});
+
+#[rustfmt::skip]
+usage_table!(Digitizer {
+    Digitizer = 0x01, "Digitizer", CA;
+    Pen = 0x02, "Pen", CA;
+    LightPen = 0x03, "Light Pen", CA;
+    TouchScreen = 0x04, "Touch Screen", CA;
+    TouchPad = 0x05, "Touch Pad", CA;
+    Whiteboard = 0x06, "Whiteboard", CA;
+    CoordinateMeasuringMachine = 0x07, "Coordinate Measuring Machine", CA;
+    ThreeDDigitizer = 0x08, "3D Digitizer", CA;
+    StereoPlotter = 0x09, "Stereo Plotter", CA;
+    ArticulatedArm = 0x0A, "Articulated Arm", CA;
+    Armature = 0x0B, "Armature", CA;
+    MultiplePointDigitizer = 0x0C, "Multiple Point Digitizer", CA;
+    FreeSpaceWand = 0x0D, "Free Space Wand", CA;
+    DeviceConfiguration = 0x0E, "Device Configuration", CA;
+    CapacitiveHeatMapDigitizer = 0x0F, "Capacitive Heat Map Digitizer", CA;
+    Stylus = 0x20, "Stylus", CA | CL;
+    Puck = 0x21, "Puck", CL;
+    Finger = 0x22, "Finger", CL;
+    DeviceSettings = 0x23, "Device settings", CL;
+    CharacterGesture = 0x24, "Character Gesture", CL;
+    TipPressure = 0x30, "Tip Pressure", DV;
+    BarrelPressure = 0x31, "Barrel Pressure", DV;
+    InRange = 0x32, "In Range", MC;
+    Touch = 0x33, "Touch", MC;
+    Untouch = 0x34, "Untouch", OSC;
+    Tap = 0x35, "Tap", OSC;
+    Quality = 0x36, "Quality", DV;
+    DataValid = 0x37, "Data Valid", MC;
+    TransducerIndex = 0x38, "Transducer Index", DV;
+    TabletFunctionKeys = 0x39, "Tablet Function Keys", CL;
+    ProgramChangeKeys = 0x3A, "Program Change Keys", CL;
+    BatteryStrength = 0x3B, "Battery Strength", DV;
+    Invert = 0x3C, "Invert", MC;
+    XTilt = 0x3D, "X Tilt", DV;
+    YTilt = 0x3E, "Y Tilt", DV;
+    Azimuth = 0x3F, "Azimuth", DV;
+    Altitude = 0x40, "Altitude", DV;
+    Twist = 0x41, "Twist", DV;
+    TipSwitch = 0x42, "Tip Switch", MC;
+    SecondaryTipSwitch = 0x43, "Secondary Tip Switch", MC;
+    BarrelSwitch = 0x44, "Barrel Switch", MC;
+    Eraser = 0x45, "Eraser", MC;
+    TabletPick = 0x46, "Tablet Pick", MC;
+    TouchValid = 0x47, "Touch Valid", MC;
+    Width = 0x48, "Width", DV;
+    Height = 0x49, "Height", DV;
+    ContactIdentifier = 0x51, "Contact Identifier", DV;
+    DeviceMode = 0x52, "Device Mode", DV;
+    DeviceIdentifier = 0x53, "Device Identifier", DV | SV;
+    ContactCount = 0x54, "Contact Count", DV;
+    ContactCountMaximum = 0x55, "Contact Count Maximum", SV;
+    ScanTime = 0x56, "Scan Time", DV;
+    SurfaceSwitch = 0x57, "Surface Switch", DF;
+    ButtonSwitch = 0x58, "Button Switch", DF;
+    PadType = 0x59, "Pad Type", SF;
+    SecondaryBarrelSwitch = 0x5A, "Secondary Barrel Switch", MC;
+    TransducerSerialNumber = 0x5B, "Transducer Serial Number", SV;
+    PreferredColor = 0x5C, "Preferred Color", DV;
+    PreferredColorIsLocked = 0x5D, "Preferred Color is Locked", MC;
+    PreferredLineWidth = 0x5E, "Preferred Line Width", DV;
+    PreferredLineWidthIsLocked = 0x5F, "Preferred Line Width is Locked", MC;
+    LatencyMode = 0x60, "Latency Mode", DF;
+    GestureCharacterQuality = 0x61, "Gesture Character Quality", DV;
+    CharacterGestureDataLength = 0x62, "Character Gesture Data Length", DV;
+    CharacterGestureData = 0x63, "Character Gesture Data", DV;
+    GestureCharacterEncoding = 0x64, "Gesture Character Encoding", NAry;
+    PreferredLineStyle = 0x70, "Preferred Line Style", NAry;
+    TransducerSoftwareInfo = 0x90, "Transducer Software Info", CL;
+    TransducerVendorId = 0x91, "Transducer Vendor Id", SV;
+    TransducerProductId = 0x92, "Transducer Product Id", SV;
+    DeviceSupportedProtocols = 0x93, "Device Supported Protocols", NAry | CL;
+    TransducerSupportedProtocols = 0x94, "Transducer Supported Protocols", NAry | CL;
+    NoProtocol = 0x95, "No Protocol", Sel;
+    WacomAesProtocol = 0x96, "Wacom AES Protocol", Sel;
+    HidProtocol = 0x97, "HID Protocol", Sel;
+    MicrosoftPenProtocol = 0x98, "Microsoft Pen Protocol", Sel;
+    SupportedReportRates = 0xA0, "Supported Report Rates", CL;
+    ReportRate = 0xA1, "Report Rate", DV;
+    TransducerConnected = 0xA2, "Transducer Connected", SF;
+    SwitchDisabled = 0xA3, "Switch Disabled", Sel;
+    SwitchUnimplemented = 0xA4, "Switch Unimplemented", Sel;
+    TransducerSwitches = 0xA5, "Transducer Switches", CL;
+    TransducerIndexSelector = 0xA6, "Transducer Index Selector", DV;
+    ButtonPressThreshold = 0xB0, "Button Press Threshold", DV;
+});
+
+#[rustfmt::skip]
+usage_table!(PhysicalInputDevice {
+    PhysicalInputDevice = 0x01, "Physical Input Device", CA;
+    Normal = 0x20, "Normal", DV;
+    SetEffectReport = 0x21, "Set Effect Report", CL;
+    EffectBlockIndex = 0x22, "Effect Block Index", DV;
+    ParameterBlockOffset = 0x23, "Parameter Block Offset", DV;
+    RomFlag = 0x24, "ROM Flag", DF;
+    EffectType = 0x25, "Effect Type", NAry;
+    EtConstantForce = 0x26, "ET Constant Force", Sel;
+    EtRamp = 0x27, "ET Ramp", Sel;
+    EtCustomForceData = 0x28, "ET Custom Force Data", Sel;
+    EtSquare = 0x30, "ET Square", Sel;
+    EtSine = 0x31, "ET Sine", Sel;
+    EtTriangle = 0x32, "ET Triangle", Sel;
+    EtSawtoothUp = 0x33, "ET Sawtooth Up", Sel;
+    EtSawtoothDown = 0x34, "ET Sawtooth Down", Sel;
+    EtSpring = 0x40, "ET Spring", Sel;
+    EtDamper = 0x41, "ET Damper", Sel;
+    EtInertia = 0x42, "ET Inertia", Sel;
+    EtFriction = 0x43, "ET Friction", Sel;
+    Duration = 0x50, "Duration", DV;
+    SamplePeriod = 0x51, "Sample Period", DV;
+    Gain = 0x52, "Gain", DV;
+    TriggerButton = 0x53, "Trigger Button", DV;
+    TriggerRepeatInterval = 0x54, "Trigger Repeat Interval", DV;
+    AxesEnable = 0x55, "Axes Enable", US;
+    DirectionEnable = 0x56, "Direction Enable", DF;
+    Direction = 0x57, "Direction", CL;
+    TypeSpecificBlockOffset = 0x58, "Type Specific Block Offset", CL;
+    BlockType = 0x59, "Block Type", NAry;
+    SetEnvelopeReport = 0x5A, "Set Envelope Report", CL | SV;
+    AttackLevel = 0x5B, "Attack Level", DV;
+    AttackTime = 0x5C, "Attack Time", DV;
+    FadeLevel = 0x5D, "Fade Level", DV;
+    FadeTime = 0x5E, "Fade Time", DV;
+    SetConditionReport = 0x5F, "Set Condition Report", CL;
+    CenterPointOffset = 0x60, "Center-Point Offset", DV;
+    PositiveCoefficient = 0x61, "Positive Coefficient", DV;
+    NegativeCoefficient = 0x62, "Negative Coefficient", DV;
+    PositiveSaturation = 0x63, "Positive Saturation", DV;
+    NegativeSaturation = 0x64, "Negative Saturation", DV;
+    DeadBand = 0x65, "Dead Band", DV;
+    DownloadForceSample = 0x66, "Download Force Sample", CL;
+    IsochCustomForceEnable = 0x67, "Isoch Custom Force Enable", DF;
+    CustomForceDataReport = 0x68, "Custom Force Data Report", CL;
+    CustomForceData = 0x69, "Custom Force Data", DV;
+    CustomForceVendorDefinedData = 0x6A, "Custom Force Vendor Defined Data", DV;
+    SetCustomForceReport = 0x6B, "Set Custom Force Report", CL;
+    CustomForceDataOffset = 0x6C, "Custom Force Data Offset", DV;
+    SampleCount = 0x6D, "Sample Count", DV;
+    SetPeriodicReport = 0x6E, "Set Periodic Report", CL;
+    Offset = 0x6F, "Offset", DV;
+    Magnitude = 0x70, "Magnitude", DV;
+    Phase = 0x71, "Phase", DV;
+    Period = 0x72, "Period", DV;
+    SetConstantForceReport = 0x73, "Set Constant Force Report", CL;
+    SetRampForceReport = 0x74, "Set Ramp Force Report", CL;
+    RampStart = 0x75, "Ramp Start", DV;
+    RampEnd = 0x76, "Ramp End", DV;
+    EffectOperationReport = 0x77, "Effect Operation Report", CL;
+    EffectOperation = 0x78, "Effect Operation", NAry;
+    OpEffectStart = 0x79, "Op Effect Start", Sel;
+    OpEffectStartSolo = 0x7A, "Op Effect Start Solo", Sel;
+    OpEffectStop = 0x7B, "Op Effect Stop", Sel;
+    LoopCount = 0x7C, "Loop Count", DV;
+    DeviceGainReport = 0x7D, "Device Gain Report", CL;
+    DeviceGain = 0x7E, "Device Gain", DV;
+    PidPoolReport = 0x7F, "PID Pool Report", CL;
+    RamPoolSize = 0x80, "RAM Pool Size", DV;
+    RomPoolSize = 0x81, "ROM Pool Size", SV;
+    RomEffectBlockCount = 0x82, "ROM Effect Block Count", SV;
+    SimultaneousEffectsMax = 0x83, "Simultaneous Effects Max", SV;
+    PoolAlignment = 0x84, "Pool Alignment", SV;
+    PidPoolMoveReport = 0x85, "PID Pool Move Report", CL;
+    MoveSource = 0x86, "Move Source", DV;
+    MoveDestination = 0x87, "Move Destination", DV;
+    MoveLength = 0x88, "Move Length", DV;
+    PidBlockLoadReport = 0x89, "PID Block Load Report", CL;
+    BlockLoadStatus = 0x8B, "Block Load Status", NAry;
+    BlockLoadSuccess = 0x8C, "Block Load Success", Sel;
+    BlockLoadFull = 0x8D, "Block Load Full", Sel;
+    BlockLoadError = 0x8E, "Block Load Error", Sel;
+    BlockHandle = 0x8F, "Block Handle", DV;
+    PidBlockFreeReport = 0x90, "PID Block Free Report", CL;
+    TypeSpecificBlockHandle = 0x91, "Type Specific Block Handle", CL;
+    PidStateReport = 0x92, "PID State Report", CL;
+    EffectPlaying = 0x94, "Effect Playing", DF;
+    PidDeviceControlReport = 0x95, "PID Device Control Report", CL;
+    PidDeviceControl = 0x96, "PID Device Control", NAry;
+    DcEnableActuators = 0x97, "DC Enable Actuators", Sel;
+    DcDisableActuators = 0x98, "DC Disable Actuators", Sel;
+    DcStopAllEffects = 0x99, "DC Stop All Effects", Sel;
+    DcDeviceReset = 0x9A, "DC Device Reset", Sel;
+    DcDevicePause = 0x9B, "DC Device Pause", Sel;
+    DcDeviceContinue = 0x9C, "DC Device Continue", Sel;
+    DevicePaused = 0x9F, "Device Paused", DF;
+    ActuatorsEnabled = 0xA0, "Actuators Enabled", DF;
+    SafetySwitch = 0xA4, "Safety Switch", DF;
+    ActuatorOverrideSwitch = 0xA5, "Actuator Override Switch", DF;
+    ActuatorPower = 0xA6, "Actuator Power", DF;
+    StartDelay = 0xA7, "Start Delay", DV;
+    ParameterBlockSize = 0xA8, "Parameter Block Size", CL;
+    DeviceManagedPool = 0xA9, "Device-Managed Pool", SF;
+    SharedParameterBlocks = 0xAA, "Shared Parameter Blocks", SF;
+    CreateNewEffectReport = 0xAB, "Create New Effect Report", CL;
+    RamPoolAvailable = 0xAC, "RAM Pool Available", DV;
+});
+
+#[rustfmt::skip]
+usage_table!(Sensors {
+    Sensor = 0x01, "Sensor", CA | CP;
+    Biometric = 0x10, "Biometric", CA | CP;
+    BiometricHumanPresence = 0x11, "Biometric: Human Presence", CA | CP;
+    BiometricHumanProximity = 0x12, "Biometric: Human Proximity", CA | CP;
+    BiometricHumanTouch = 0x13, "Biometric: Human Touch", CA | CP;
+    BiometricBloodPressure = 0x14, "Biometric: Blood Pressure", CA | CP;
+    BiometricBodyTemperature = 0x15, "Biometric: Body Temperature", CA | CP;
+    BiometricHeartRate = 0x16, "Biometric: Heart Rate", CA | CP;
+    Electrical = 0x20, "Electrical", CA | CP;
+    ElectricalCapacitance = 0x21, "Electrical: Capacitance", CA | CP;
+    ElectricalCurrent = 0x22, "Electrical: Current", CA | CP;
+    ElectricalPower = 0x23, "Electrical: Power", CA | CP;
+    ElectricalInductance = 0x24, "Electrical: Inductance", CA | CP;
+    ElectricalResistance = 0x25, "Electrical: Resistance", CA | CP;
+    ElectricalVoltage = 0x26, "Electrical: Voltage", CA | CP;
+    ElectricalPotentiometer = 0x27, "Electrical: Potentiometer", CA | CP;
+    ElectricalFrequency = 0x28, "Electrical: Frequency", CA | CP;
+    ElectricalPeriod = 0x29, "Electrical: Period", CA | CP;
+    Environmental = 0x30, "Environmental", CA | CP;
+    EnvironmentalAtmosphericPressure = 0x31, "Environmental: Atmospheric Pressure", CA | CP;
+    EnvironmentalHumidity = 0x32, "Environmental: Humidity", CA | CP;
+    EnvironmentalTemperature = 0x33, "Environmental: Temperature", CA | CP;
+    EnvironmentalWindDirection = 0x34, "Environmental: Wind Direction", CA | CP;
+    EnvironmentalWindSpeed = 0x35, "Environmental: Wind Speed", CA | CP;
+    EnvironmentalAirQuality = 0x36, "Environmental: Air Quality", CA | CP;
+    EnvironmentalHeatIndex = 0x37, "Environmental: Heat Index", CA | CP;
+    EnvironmentalSurfaceTemperature = 0x38, "Environmental: Surface Temperature", CA | CP;
+    EnvironmentalVolatileOrganicCompounds = 0x39, "Environmental: Volatile Organic Compounds", CA | CP;
+    EnvironmentalObjectPresence = 0x3A, "Environmental: Object Presence", CA | CP;
+    EnvironmentalObjectProximity = 0x3B, "Environmental: Object Proximity", CA | CP;
+    Light = 0x40, "Light", CA | CP;
+    LightAmbientLight = 0x41, "Light: Ambient Light", CA | CP;
+    LightConsumerInfrared = 0x42, "Light: Consumer Infrared", CA | CP;
+    LightInfraredLight = 0x43, "Light: Infrared Light", CA | CP;
+    LightVisibleLight = 0x44, "Light: Visible Light", CA | CP;
+    LightUltravioletLight = 0x45, "Light: Ultraviolet Light", CA | CP;
+    Location = 0x50, "Location", CA | CP;
+    LocationBroadcast = 0x51, "Location: Broadcast", CA | CP;
+    LocationDeadReckoning = 0x52, "Location: Dead Reckoning", CA | CP;
+    LocationGps = 0x53, "Location: GPS (Global Positioning System)", CA | CP;
+    LocationLookup = 0x54, "Location: Lookup", CA | CP;
+    LocationOther = 0x55, "Location: Other", CA | CP;
+    LocationStatic = 0x56, "Location: Static", CA | CP;
+    LocationTriangulation = 0x57, "Location: Triangulation", CA | CP;
+    Mechanical = 0x60, "Mechanical", CA | CP;
+    MechanicalBooleanSwitch = 0x61, "Mechanical: Boolean Switch", CA | CP;
+    MechanicalBooleanSwitchArray = 0x62, "Mechanical: Boolean Switch Array", CA | CP;
+    MechanicalMultivalueSwitch = 0x63, "Mechanical: Multivalue Switch", CA | CP;
+    MechanicalForce = 0x64, "Mechanical: Force", CA | CP;
+    MechanicalPressure = 0x65, "Mechanical: Pressure", CA | CP;
+    MechanicalStrain = 0x66, "Mechanical: Strain", CA | CP;
+    MechanicalWeight = 0x67, "Mechanical: Weight", CA | CP;
+    MechanicalHapticVibrator = 0x68, "Mechanical: Haptic Vibrator", CA | CP;
+    MechanicalHallEffectSwitch = 0x69, "Mechanical: Hall Effect Switch", CA | CP;
+    Motion = 0x70, "Motion", CA | CP;
+    MotionAccelerometer1D = 0x71, "Motion: Accelerometer 1D", CA | CP;
+    MotionAccelerometer2D = 0x72, "Motion: Accelerometer 2D", CA | CP;
+    MotionAccelerometer3D = 0x73, "Motion: Accelerometer 3D", CA | CP;
+    MotionGyrometer1D = 0x74, "Motion: Gyrometer 1D", CA | CP;
+    MotionGyrometer2D = 0x75, "Motion: Gyrometer 2D", CA | CP;
+    MotionGyrometer3D = 0x76, "Motion: Gyrometer 3D", CA | CP;
+    MotionMotionDetector = 0x77, "Motion: Motion Detector", CA | CP;
+    MotionSpeedometer = 0x78, "Motion: Speedometer", CA | CP;
+    MotionAccelerometer = 0x79, "Motion: Accelerometer", CA | CP;
+    MotionGyrometer = 0x7A, "Motion: Gyrometer", CA | CP;
+    MotionGravityVector = 0x7B, "Motion: Gravity Vector", CA | CP;
+    MotionLinearAccelerometer = 0x7C, "Motion: Linear Accelerometer", CA | CP;
+    Orientation = 0x80, "Orientation", CA | CP;
+    OrientationCompass1D = 0x81, "Orientation: Compass 1D", CA | CP;
+    OrientationCompass2D = 0x82, "Orientation: Compass 2D", CA | CP;
+    OrientationCompass3D = 0x83, "Orientation: Compass 3D", CA | CP;
+    OrientationInclinometer1D = 0x84, "Orientation: Inclinometer 1D", CA | CP;
+    OrientationInclinometer2D = 0x85, "Orientation: Inclinometer 2D", CA | CP;
+    OrientationInclinometer3D = 0x86, "Orientation: Inclinometer 3D", CA | CP;
+    OrientationDistance1D = 0x87, "Orientation: Distance 1D", CA | CP;
+    OrientationDistance2D = 0x88, "Orientation: Distance 2D", CA | CP;
+    OrientationDistance3D = 0x89, "Orientation: Distance 3D", CA | CP;
+    OrientationDeviceOrientation = 0x8A, "Orientation: Device Orientation", CA | CP;
+    OrientationCompass = 0x8B, "Orientation: Compass", CA | CP;
+    OrientationInclinometer = 0x8C, "Orientation: Inclinometer", CA | CP;
+    OrientationDistance = 0x8D, "Orientation: Distance", CA | CP;
+    OrientationRelativeOrientation = 0x8E, "Orientation: Relative Orientation", CA | CP;
+    OrientationSimpleOrientation = 0x8F, "Orientation: Simple Orientation", CA | CP;
+    Scanner = 0x90, "Scanner", CA | CP;
+    ScannerBarcode = 0x91, "Scanner: Barcode", CA | CP;
+    ScannerRfid = 0x92, "Scanner: RFID", CA | CP;
+    ScannerNfc = 0x93, "Scanner: NFC", CA | CP;
+    Time = 0xA0, "Time", CA | CP;
+    TimeAlarmTimer = 0xA1, "Time: Alarm Timer", CA | CP;
+    TimeRealTimeClock = 0xA2, "Time: Real Time Clock", CA | CP;
+    PersonalActivity = 0xB0, "Personal Activity", CA | CP;
+    PersonalActivityActivityDetection = 0xB1, "Personal Activity: Activity Detection", CA | CP;
+    PersonalActivityDevicePosition = 0xB2, "Personal Activity: Device Position", CA | CP;
+    PersonalActivityFloorTracker = 0xB3, "Personal Activity: Floor Tracker", CA | CP;
+    PersonalActivityPedometer = 0xB4, "Personal Activity: Pedometer", CA | CP;
+    PersonalActivityStepDetection = 0xB5, "Personal Activity: Step Detection", CA | CP;
+    OrientationExtended = 0xC0, "Orientation Extended", CA | CP;
+    OrientationExtendedGeomagneticOrientation = 0xC1, "Orientation Extended: Geomagnetic Orientation", CA | CP;
+    OrientationExtendedMagnetometer = 0xC2, "Orientation Extended: Magnetometer", CA | CP;
+    Gesture = 0xD0, "Gesture", CA | CP;
+    GestureChassisFlipGesture = 0xD1, "Gesture: Chassis Flip Gesture", CA | CP;
+    GestureHingeFoldGesture = 0xD2, "Gesture: Hinge Fold Gesture", CA | CP;
+    Other = 0xE0, "Other", CA | CP;
+    OtherCustom = 0xE1, "Other: Custom", CA | CP;
+    OtherGeneric = 0xE2, "Other: Generic", CA | CP;
+    OtherGenericEnumerator = 0xE3, "Other: Generic Enumerator", CA | CP;
+    OtherHingeAngle = 0xE4, "Other: Hinge Angle", CA | CP;
+    Event = 0x200, "Event", DV;
+    EventSensorState = 0x201, "Event: Sensor State", NAry;
+    EventSensorEvent = 0x202, "Event: Sensor Event", NAry;
+    Property = 0x300, "Property", DV;
+    PropertyFriendlyName = 0x301, "Property: Friendly Name", SV;
+    PropertyPersistentUniqueId = 0x302, "Property: Persistent Unique ID", DV;
+    PropertySensorStatus = 0x303, "Property: Sensor Status", DV;
+    PropertyMinimumReportInterval = 0x304, "Property: Minimum Report Interval", SV;
+    PropertySensorManufacturer = 0x305, "Property: Sensor Manufacturer", SV;
+    PropertySensorModel = 0x306, "Property: Sensor Model", SV;
+    PropertySensorSerialNumber = 0x307, "Property: Sensor Serial Number", SV;
+    PropertySensorDescription = 0x308, "Property: Sensor Description", SV;
+    PropertySensorConnectionType = 0x309, "Property: Sensor Connection Type", NAry;
+    PropertySensorDevicePath = 0x30A, "Property: Sensor Device Path", DV;
+    PropertyHardwareRevision = 0x30B, "Property: Hardware Revision", SV;
+    PropertyFirmwareVersion = 0x30C, "Property: Firmware Version", SV;
+    PropertyReleaseDate = 0x30D, "Property: Release Date", SV;
+    PropertyReportInterval = 0x30E, "Property: Report Interval", DV;
+    PropertyChangeSensitivityAbsolute = 0x30F, "Property: Change Sensitivity Absolute", DV;
+    PropertyChangeSensitivityPercentOfRange = 0x310, "Property: Change Sensitivity Percent of Range", DV;
+    PropertyChangeSensitivityPercentRelative = 0x311, "Property: Change Sensitivity Percent Relative", DV;
+    PropertyAccuracy = 0x312, "Property: Accuracy", DV;
+    PropertyResolution = 0x313, "Property: Resolution", DV;
+    PropertyMaximum = 0x314, "Property: Maximum", DV;
+    PropertyMinimum = 0x315, "Property: Minimum", DV;
+    PropertyReportingState = 0x316, "Property: Reporting State", NAry;
+    PropertySamplingRate = 0x317, "Property: Sampling Rate", DV;
+    PropertyResponseCurve = 0x318, "Property: Response Curve", DV;
+    PropertyPowerState = 0x319, "Property: Power State", NAry;
+    PropertyMaximumFifoEvents = 0x31A, "Property: Maximum FIFO Events", SV;
+    PropertyReportLatency = 0x31B, "Property: Report Latency", DV;
+    PropertyFlushFifoEvents = 0x31C, "Property: Flush FIFO Events", DF;
+    PropertyMaximumPowerConsumption = 0x31D, "Property: Maximum Power Consumption", DV;
+    PropertyIsPrimary = 0x31E, "Property: Is Primary", DF;
+    PropertyHumanPresenceDetectionType = 0x31F, "Property: Human Presence Detection Type", NAry;
+    DataFieldLocation = 0x400, "Data Field: Location", DV;
+    DataFieldAltitudeAntennaSeaLevel = 0x402, "Data Field: Altitude Antenna Sea Level", SV;
+    DataFieldDifferentialReferenceStationId = 0x403, "Data Field: Differential Reference Station ID", SV;
+    DataFieldAltitudeEllipsoidError = 0x404, "Data Field: Altitude Ellipsoid Error", SV;
+    DataFieldAltitudeEllipsoid = 0x405, "Data Field: Altitude Ellipsoid", SV;
+    DataFieldAltitudeSeaLevelError = 0x406, "Data Field: Altitude Sea Level Error", SV;
+    DataFieldAltitudeSeaLevel = 0x407, "Data Field: Altitude Sea Level", SV;
+    DataFieldDifferentialGpsDataAge = 0x408, "Data Field: Differential GPS Data Age", SV;
+    DataFieldErrorRadius = 0x409, "Data Field: Error Radius", SV;
+    DataFieldFixQuality = 0x40A, "Data Field: Fix Quality", NAry;
+    DataFieldFixType = 0x40B, "Data Field: Fix Type", NAry;
+    DataFieldGeoidalSeparation = 0x40C, "Data Field: Geoidal Separation", SV;
+    DataFieldGpsOperationMode = 0x40D, "Data Field: GPS Operation Mode", NAry;
+    DataFieldGpsSelectionMode = 0x40E, "Data Field: GPS Selection Mode", NAry;
+    DataFieldGpsStatus = 0x40F, "Data Field: GPS Status", NAry;
+    DataFieldPositionDilutionOfPrecision = 0x410, "Data Field: Position Dilution of Precision", SV;
+    DataFieldHorizontalDilutionOfPrecision = 0x411, "Data Field: Horizontal Dilution of Precision", SV;
+    DataFieldVerticalDilutionOfPrecision = 0x412, "Data Field: Vertical Dilution of Precision", SV;
+    DataFieldLatitude = 0x413, "Data Field: Latitude", SV;
+    DataFieldLongitude = 0x414, "Data Field: Longitude", SV;
+    DataFieldTrueHeading = 0x415, "Data Field: True Heading", SV;
+    DataFieldMagneticHeading = 0x416, "Data Field: Magnetic Heading", SV;
+    DataFieldMagneticVariation = 0x417, "Data Field: Magnetic Variation", SV;
+    DataFieldSpeed = 0x418, "Data Field: Speed", SV;
+    DataFieldEnvironmental = 0x430, "Data Field: Environmental", DV;
+    DataFieldAtmosphericPressure = 0x431, "Data Field: Atmospheric Pressure", SV;
+    DataFieldRelativeHumidity = 0x433, "Data Field: Relative Humidity", SV;
+    DataFieldTemperature = 0x434, "Data Field: Temperature", SV;
+    DataFieldWindDirection = 0x435, "Data Field: Wind Direction", SV;
+    DataFieldWindSpeed = 0x436, "Data Field: Wind Speed", SV;
+    DataFieldAirQualityIndex = 0x437, "Data Field: Air Quality Index", SV;
+    DataFieldEquivalentCo2 = 0x438, "Data Field: Equivalent CO2", SV;
+    DataFieldVolatileOrganicCompoundConcentration = 0x439, "Data Field: Volatile Organic Compound Concentration", SV;
+    DataFieldObjectPresence = 0x43A, "Data Field: Object Presence", SF;
+    DataFieldObjectProximityRange = 0x43B, "Data Field: Object Proximity Range", SV;
+    DataFieldObjectProximityOutOfRange = 0x43C, "Data Field: Object Proximity Out of Range", SF;
+    DataFieldMotion = 0x450, "Data Field: Motion", DV;
+    DataFieldMotionState = 0x451, "Data Field: Motion State", SF;
+    DataFieldAcceleration = 0x452, "Data Field: Acceleration", SV;
+    DataFieldAccelerationAxisX = 0x453, "Data Field: Acceleration Axis X", SV;
+    DataFieldAccelerationAxisY = 0x454, "Data Field: Acceleration Axis Y", SV;
+    DataFieldAccelerationAxisZ = 0x455, "Data Field: Acceleration Axis Z", SV;
+    DataFieldAngularVelocity = 0x456, "Data Field: Angular Velocity", SV;
+    DataFieldAngularVelocityAboutXAxis = 0x457, "Data Field: Angular Velocity about X Axis", SV;
+    DataFieldAngularVelocityAboutYAxis = 0x458, "Data Field: Angular Velocity about Y Axis", SV;
+    DataFieldAngularVelocityAboutZAxis = 0x459, "Data Field: Angular Velocity about Z Axis", SV;
+    DataFieldAngularPosition = 0x45A, "Data Field: Angular Position", SV;
+    DataFieldAngularPositionAboutXAxis = 0x45B, "Data Field: Angular Position about X Axis", SV;
+    DataFieldAngularPositionAboutYAxis = 0x45C, "Data Field: Angular Position about Y Axis", SV;
+    DataFieldAngularPositionAboutZAxis = 0x45D, "Data Field: Angular Position about Z Axis", SV;
+    DataFieldMotionSpeed = 0x45E, "Data Field: Motion Speed", SV;
+    DataFieldMotionIntensity = 0x45F, "Data Field: Motion Intensity", SV;
+    DataFieldOrientation = 0x470, "Data Field: Orientation", DV;
+    DataFieldHeading = 0x471, "Data Field: Heading", SV;
+    DataFieldHeadingXAxis = 0x472, "Data Field: Heading X Axis", SV;
+    DataFieldHeadingYAxis = 0x473, "Data Field: Heading Y Axis", SV;
+    DataFieldHeadingZAxis = 0x474, "Data Field: Heading Z Axis", SV;
+    DataFieldHeadingCompensatedMagneticNorth = 0x475, "Data Field: Heading Compensated Magnetic North", SV;
+    DataFieldHeadingCompensatedTrueNorth = 0x476, "Data Field: Heading Compensated True North", SV;
+    DataFieldHeadingMagneticNorth = 0x477, "Data Field: Heading Magnetic North", SV;
+    DataFieldHeadingTrueNorth = 0x478, "Data Field: Heading True North", SV;
+    DataFieldDistance = 0x479, "Data Field: Distance", SV;
+    DataFieldDistanceXAxis = 0x47A, "Data Field: Distance X Axis", SV;
+    DataFieldDistanceYAxis = 0x47B, "Data Field: Distance Y Axis", SV;
+    DataFieldDistanceZAxis = 0x47C, "Data Field: Distance Z Axis", SV;
+    DataFieldDistanceOutOfRange = 0x47D, "Data Field: Distance Out-of-Range", SF;
+    DataFieldTilt = 0x47E, "Data Field: Tilt", SV;
+    DataFieldTiltXAxis = 0x47F, "Data Field: Tilt X Axis", SV;
+    DataFieldTiltYAxis = 0x480, "Data Field: Tilt Y Axis", SV;
+    DataFieldTiltZAxis = 0x481, "Data Field: Tilt Z Axis", SV;
+    DataFieldRotationMatrix = 0x482, "Data Field: Rotation Matrix", SV;
+    DataFieldQuaternion = 0x483, "Data Field: Quaternion", SV;
+    DataFieldMagneticFlux = 0x484, "Data Field: Magnetic Flux", SV;
+    DataFieldMagneticFluxXAxis = 0x485, "Data Field: Magnetic Flux X Axis", SV;
+    DataFieldMagneticFluxYAxis = 0x486, "Data Field: Magnetic Flux Y Axis", SV;
+    DataFieldMagneticFluxZAxis = 0x487, "Data Field: Magnetic Flux Z Axis", SV;
+    DataFieldMagnetometerAccuracy = 0x488, "Data Field: Magnetometer Accuracy", NAry;
+    DataFieldSimpleOrientationDirection = 0x489, "Data Field: Simple Orientation Direction", NAry;
+    DataFieldMechanical = 0x4A0, "Data Field: Mechanical", DV;
+    DataFieldBooleanSwitchState = 0x4A1, "Data Field: Boolean Switch State", SF;
+    DataFieldBooleanSwitchArrayStates = 0x4A2, "Data Field: Boolean Switch Array States", SV;
+    DataFieldMultivalueSwitchValue = 0x4A3, "Data Field: Multivalue Switch Value", SV;
+    DataFieldForce = 0x4A4, "Data Field: Force", SV;
+    DataFieldAbsolutePressure = 0x4A5, "Data Field: Absolute Pressure", SV;
+    DataFieldGaugePressure = 0x4A6, "Data Field: Gauge Pressure", SV;
+    DataFieldStrain = 0x4A7, "Data Field: Strain", SV;
+    DataFieldWeight = 0x4A8, "Data Field: Weight", SV;
+    DataFieldBiometric = 0x4B0, "Data Field: Biometric", DV;
+    DataFieldHumanPresence = 0x4B1, "Data Field: Human Presence", SF;
+    DataFieldHumanProximityRange = 0x4B2, "Data Field: Human Proximity Range", SV;
+    DataFieldHumanProximityOutOfRange = 0x4B3, "Data Field: Human Proximity Out of Range", SF;
+    DataFieldHumanTouchState = 0x4B4, "Data Field: Human Touch State", SF;
+    DataFieldLight = 0x4D0, "Data Field: Light", DV;
+    DataFieldIlluminance = 0x4D1, "Data Field: Illuminance", SV;
+    DataFieldColorTemperature = 0x4D2, "Data Field: Color Temperature", SV;
+    DataFieldChromaticity = 0x4D3, "Data Field: Chromaticity", SV;
+    DataFieldChromaticityX = 0x4D4, "Data Field: Chromaticity X", SV;
+    DataFieldChromaticityY = 0x4D5, "Data Field: Chromaticity Y", SV;
+    DataFieldConsumerIrSentenceReceive = 0x4D6, "Data Field: Consumer IR Sentence Receive", SV;
+    DataFieldInfraredLight = 0x4D7, "Data Field: Infrared Light", SV;
+    DataFieldRedLight = 0x4D8, "Data Field: Red Light", SV;
+    DataFieldGreenLight = 0x4D9, "Data Field: Green Light", SV;
+    DataFieldBlueLight = 0x4DA, "Data Field: Blue Light", SV;
+    DataFieldUltravioletALight = 0x4DB, "Data Field: Ultraviolet A Light", SV;
+    DataFieldUltravioletBLight = 0x4DC, "Data Field: Ultraviolet B Light", SV;
+    DataFieldUltravioletIndex = 0x4DD, "Data Field: Ultraviolet Index", SV;
+    SensorStateUndefined = 0x800, "Sensor State: Undefined", Sel;
+    SensorStateReady = 0x801, "Sensor State: Ready", Sel;
+    SensorStateNotAvailable = 0x802, "Sensor State: Not Available", Sel;
+    SensorStateNoData = 0x803, "Sensor State: No Data", Sel;
+    SensorStateInitializing = 0x804, "Sensor State: Initializing", Sel;
+    SensorStateAccessDenied = 0x805, "Sensor State: Access Denied", Sel;
+    SensorStateError = 0x806, "Sensor State: Error", Sel;
+    SensorEventUnknown = 0x810, "Sensor Event: Unknown", Sel;
+    SensorEventStateChanged = 0x811, "Sensor Event: State Changed", Sel;
+    SensorEventPropertyChanged = 0x812, "Sensor Event: Property Changed", Sel;
+    SensorEventDataUpdated = 0x813, "Sensor Event: Data Updated", Sel;
+    SensorEventPollResponse = 0x814, "Sensor Event: Poll Response", Sel;
+    SensorEventChangeSensitivity = 0x815, "Sensor Event: Change Sensitivity", Sel;
+    SensorEventRangeMaximumReached = 0x816, "Sensor Event: Range Maximum Reached", Sel;
+    SensorEventRangeMinimumReached = 0x817, "Sensor Event: Range Minimum Reached", Sel;
+    SensorEventHighThresholdCrossUpward = 0x818, "Sensor Event: High Threshold Cross Upward", Sel;
+    SensorEventHighThresholdCrossDownward = 0x819, "Sensor Event: High Threshold Cross Downward", Sel;
+    SensorEventLowThresholdCrossUpward = 0x81A, "Sensor Event: Low Threshold Cross Upward", Sel;
+    SensorEventLowThresholdCrossDownward = 0x81B, "Sensor Event: Low Threshold Cross Downward", Sel;
+    SensorEventZeroThresholdCrossUpward = 0x81C, "Sensor Event: Zero Threshold Cross Upward", Sel;
+    SensorEventZeroThresholdCrossDownward = 0x81D, "Sensor Event: Zero Threshold Cross Downward", Sel;
+    SensorEventPeriodExceeded = 0x81E, "Sensor Event: Period Exceeded", Sel;
+    SensorEventFrequencyExceeded = 0x81F, "Sensor Event: Frequency Exceeded", Sel;
+    SensorEventComplexTrigger = 0x820, "Sensor Event: Complex Trigger", Sel;
+    ConnectionTypePcIntegrated = 0x830, "Connection Type: PC Integrated", Sel;
+    ConnectionTypePcAttached = 0x831, "Connection Type: PC Attached", Sel;
+    ConnectionTypePcExternal = 0x832, "Connection Type: PC External", Sel;
+    ReportingStateReportNoEvents = 0x840, "Reporting State: Report No Events", Sel;
+    ReportingStateReportAllEvents = 0x841, "Reporting State: Report All Events", Sel;
+    ReportingStateReportThresholdEvents = 0x842, "Reporting State: Report Threshold Events", Sel;
+    ReportingStateWakeOnNoEvents = 0x843, "Reporting State: Wake On No Events", Sel;
+    ReportingStateWakeOnAllEvents = 0x844, "Reporting State: Wake On All Events", Sel;
+    ReportingStateWakeOnThresholdEvents = 0x845, "Reporting State: Wake On Threshold Events", Sel;
+    PowerStateUndefined = 0x850, "Power State: Undefined", Sel;
+    PowerStateD0FullPower = 0x851, "Power State: D0 Full Power", Sel;
+    PowerStateD1LowPower = 0x852, "Power State: D1 Low Power", Sel;
+    PowerStateD2StandbyPowerWithWakeup = 0x853, "Power State: D2 Standby Power with Wakeup", Sel;
+    PowerStateD3SleepWithWakeup = 0x854, "Power State: D3 Sleep with Wakeup", Sel;
+    PowerStateD4PowerOff = 0x855, "Power State: D4 Power Off", Sel;
+});
+
+#[rustfmt::skip]
+usage_table!(BatterySystem {
+    SmartBatteryBatteryMode = 0x01, "Smart Battery Battery Mode", CL;
+    SmartBatteryBatteryStatus = 0x02, "Smart Battery Battery Status", NAry;
+    SmartBatteryAlarmWarning = 0x03, "Smart Battery Alarm Warning", NAry;
+    SmartBatteryChargerMode = 0x04, "Smart Battery Charger Mode", CL;
+    SmartBatteryChargerStatus = 0x05, "Smart Battery Charger Status", CL;
+    SmartBatteryChargerSpecInfo = 0x06, "Smart Battery Charger Spec Info", CL;
+    SmartBatterySelectorState = 0x07, "Smart Battery Selector State", CL;
+    SmartBatterySelectorPresets = 0x08, "Smart Battery Selector Presets", CL;
+    SmartBatterySelectorInfo = 0x09, "Smart Battery Selector Info", CL;
+    OptionalMfgFunction1 = 0x10, "Optional Mfg Function 1", DV;
+    OptionalMfgFunction2 = 0x11, "Optional Mfg Function 2", DV;
+    OptionalMfgFunction3 = 0x12, "Optional Mfg Function 3", DV;
+    OptionalMfgFunction4 = 0x13, "Optional Mfg Function 4", DV;
+    OptionalMfgFunction5 = 0x14, "Optional Mfg Function 5", DV;
+    ConnectionToSmBus = 0x15, "Connection To SM Bus", DF;
+    OutputConnection = 0x16, "Output Connection", DF;
+    ChargerConnection = 0x17, "Charger Connection", DF;
+    BatteryInsertion = 0x18, "Battery Insertion", DF;
+    UseNext = 0x19, "Use Next", DF;
+    OkToUse = 0x1A, "OK To Use", DF;
+    BatterySupported = 0x1B, "Battery Supported", DF;
+    SelectorRevision = 0x1C, "Selector Revision", DF;
+    ChargingIndicator = 0x1D, "Charging Indicator", DF;
+    ManufacturerAccess = 0x28, "Manufacturer Access", DV;
+    RemainingCapacityLimit = 0x29, "Remaining Capacity Limit", DV;
+    RemainingTimeLimit = 0x2A, "Remaining Time Limit", DV;
+    AtRate = 0x2B, "At Rate", DV;
+    CapacityMode = 0x2C, "Capacity Mode", DV;
+    BroadcastToCharger = 0x2D, "Broadcast To Charger", DV;
+    PrimaryBattery = 0x2E, "Primary Battery", DV;
+    ChargeController = 0x2F, "Charge Controller", DV;
+    TerminateCharge = 0x40, "Terminate Charge", Sel;
+    TerminateDischarge = 0x41, "Terminate Discharge", Sel;
+    BelowRemainingCapacityLimit = 0x42, "Below Remaining Capacity Limit", Sel;
+    RemainingTimeLimitExpired = 0x43, "Remaining Time Limit Expired", Sel;
+    Charging = 0x44, "Charging", Sel;
+    Discharging = 0x45, "Discharging", Sel;
+    FullyCharged = 0x46, "Fully Charged", Sel;
+    FullyDischarged = 0x47, "Fully Discharged", Sel;
+    ConditioningFlag = 0x48, "Conditioning Flag", DV;
+    AtRateOk = 0x49, "At Rate OK", DV;
+    SmartBatteryErrorCode = 0x4A, "Smart Battery Error Code", DV;
+    NeedReplacement = 0x4B, "Need Replacement", DF;
+    AtRateTimeToFull = 0x60, "At Rate Time To Full", DV;
+    AtRateTimeToEmpty = 0x61, "At Rate Time To Empty", DV;
+    AverageCurrent = 0x62, "Average Current", DV;
+    MaxError = 0x63, "Max Error", DV;
+    RelativeStateOfCharge = 0x64, "Relative State Of Charge", DV;
+    AbsoluteStateOfCharge = 0x65, "Absolute State Of Charge", DV;
+    RemainingCapacity = 0x66, "Remaining Capacity", DV;
+    FullChargeCapacity = 0x67, "Full Charge Capacity", DV;
+    RunTimeToEmpty = 0x68, "Run Time To Empty", DV;
+    AverageTimeToEmpty = 0x69, "Average Time To Empty", DV;
+    AverageTimeToFull = 0x6A, "Average Time To Full", DV;
+    CycleCount = 0x6B, "Cycle Count", DV;
+    BatteryPackModelLevel = 0x80, "Battery Pack Model Level", SV;
+    InternalChargeController = 0x81, "Internal Charge Controller", SF;
+    PrimaryBatterySupport = 0x82, "Primary Battery Support", SF;
+    DesignCapacity = 0x83, "Design Capacity", SV;
+    SpecificationInfo = 0x84, "Specification Info", SV;
+    ManufactureDate = 0x85, "Manufacture Date", SV;
+    SerialNumber = 0x86, "Serial Number", SV;
+    IManufacturerName = 0x87, "iManufacturer Name", SV;
+    IDeviceName = 0x88, "iDevice Name", SV;
+    IDeviceChemistry = 0x89, "iDevice Chemistry", SV;
+    ManufacturerData = 0x8A, "Manufacturer Data", SV;
+    Rechargable = 0x8B, "Rechargable", SV;
+    WarningCapacityLimit = 0x8C, "Warning Capacity Limit", SV;
+    CapacityGranularity1 = 0x8D, "Capacity Granularity 1", SV;
+    CapacityGranularity2 = 0x8E, "Capacity Granularity 2", SV;
+    IOemInformation = 0x8F, "iOEM Information", SV;
+    InhibitCharge = 0xC0, "Inhibit Charge", DF;
+    EnablePolling = 0xC1, "Enable Polling", DF;
+    ResetToZero = 0xC2, "Reset To Zero", DF;
+    AcPresent = 0xD0, "AC Present", DV;
+    BatteryPresent = 0xD1, "Battery Present", DV;
+    PowerFail = 0xD2, "Power Fail", DV;
+    AlarmInhibited = 0xD3, "Alarm Inhibited", DV;
+    ThermistorUnderRange = 0xD4, "Thermistor Under Range", DV;
+    ThermistorHot = 0xD5, "Thermistor Hot", DV;
+    ThermistorCold = 0xD6, "Thermistor Cold", DV;
+    ThermistorOverRange = 0xD7, "Thermistor Over Range", DV;
+    VoltageOutOfRange = 0xD8, "Voltage Out Of Range", DV;
+    CurrentOutOfRange = 0xD9, "Current Out Of Range", DV;
+    CurrentNotRegulated = 0xDA, "Current Not Regulated", DV;
+    VoltageNotRegulated = 0xDB, "Voltage Not Regulated", DV;
+    MasterMode = 0xDC, "Master Mode", DV;
+    ChargerSelectorSupport = 0xF0, "Charger Selector Support", SF;
+    ChargerSpec = 0xF1, "Charger Spec", SV;
+    Level2 = 0xF2, "Level 2", SF;
+    Level3 = 0xF3, "Level 3", SF;
+});
+
+//a (page, id) pair resolved against the tables above
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Usage {
+    GenericDesktop(GenericDesktop),
+    Keyboard(KeyboardUsage),
+    Led(Led),
+    Button(u16),
+    Ordinal(u16),
+    Consumer(Consumer),
+    Digitizer(Digitizer),
+    PhysicalInputDevice(PhysicalInputDevice),
+    Sensors(Sensors),
+    BatterySystem(BatterySystem),
+    Vendor { page: u16, id: u16 },
+    Unknown { page: u16, id: u16 },
+}
+
+impl Usage {
+    pub fn new(page: u16, id: u16) -> Self {
+        let known = match UsagePage::from(page) {
+            UsagePage::GenericDesktop => GenericDesktop::from_u16(id).map(Usage::GenericDesktop),
+            UsagePage::Keyboard => KeyboardUsage::from_u16(id).map(Usage::Keyboard),
+            UsagePage::Led => Led::from_u16(id).map(Usage::Led),
+            UsagePage::Button => Some(Usage::Button(id)),
+            UsagePage::Ordinal => Some(Usage::Ordinal(id)),
+            UsagePage::Consumer => Consumer::from_u16(id).map(Usage::Consumer),
+            UsagePage::Digitizer => Digitizer::from_u16(id).map(Usage::Digitizer),
+            UsagePage::PhysicalInputDevice => {
+                PhysicalInputDevice::from_u16(id).map(Usage::PhysicalInputDevice)
+            }
+            UsagePage::Sensors => Sensors::from_u16(id).map(Usage::Sensors),
+            UsagePage::BatterySystem => BatterySystem::from_u16(id).map(Usage::BatterySystem),
+            UsagePage::Vendor(_) => Some(Usage::Vendor { page, id }),
+            _ => None,
+        };
+        known.unwrap_or(Usage::Unknown { page, id })
+    }
+
+    //extended usage as used by the report parser, page in the upper 16 bits
+    pub fn from_extended(usage: u32) -> Self {
+        Self::new((usage >> 16) as u16, usage as u16)
+    }
+
+    pub fn page(&self) -> UsagePage {
+        match self {
+            Usage::GenericDesktop(_) => UsagePage::GenericDesktop,
+            Usage::Keyboard(_) => UsagePage::Keyboard,
+            Usage::Led(_) => UsagePage::Led,
+            Usage::Button(_) => UsagePage::Button,
+            Usage::Ordinal(_) => UsagePage::Ordinal,
+            Usage::Consumer(_) => UsagePage::Consumer,
+            Usage::Digitizer(_) => UsagePage::Digitizer,
+            Usage::PhysicalInputDevice(_) => UsagePage::PhysicalInputDevice,
+            Usage::Sensors(_) => UsagePage::Sensors,
+            Usage::BatterySystem(_) => UsagePage::BatterySystem,
+            Usage::Vendor { page, .. } | Usage::Unknown { page, .. } => UsagePage::from(*page),
+        }
+    }
+
+    pub fn id(&self) -> u16 {
+        match self {
+            Usage::GenericDesktop(usage) => *usage as u16,
+            Usage::Keyboard(usage) => *usage as u16,
+            Usage::Led(usage) => *usage as u16,
+            Usage::Button(id) | Usage::Ordinal(id) => *id,
+            Usage::Consumer(usage) => *usage as u16,
+            Usage::Digitizer(usage) => *usage as u16,
+            Usage::PhysicalInputDevice(usage) => *usage as u16,
+            Usage::Sensors(usage) => *usage as u16,
+            Usage::BatterySystem(usage) => *usage as u16,
+            Usage::Vendor { id, .. } | Usage::Unknown { id, .. } => *id,
+        }
+    }
+
+    pub fn extended(&self) -> u32 {
+        ((u16::from(self.page()) as u32) << 16) | self.id() as u32
+    }
+
+    //None for numbered and unknown usages, Display spells those out
+    pub fn name(&self) -> Option<&'static str> {
+        match self {
+            Usage::GenericDesktop(usage) => Some(usage.name()),
+            Usage::Keyboard(usage) => Some(usage.name()),
+            Usage::Led(usage) => Some(usage.name()),
+            Usage::Consumer(usage) => Some(usage.name()),
+            Usage::Digitizer(usage) => Some(usage.name()),
+            Usage::PhysicalInputDevice(usage) => Some(usage.name()),
+            Usage::Sensors(usage) => Some(usage.name()),
+            Usage::BatterySystem(usage) => Some(usage.name()),
+            Usage::Button(0) => Some("No Button Pressed"),
+            _ => None,
+        }
+    }
+
+    pub fn usage_types(&self) -> &'static [UsageType] {
+        match self {
+            Usage::GenericDesktop(usage) => usage.usage_types(),
+            Usage::Keyboard(usage) => usage.usage_types(),
+            Usage::Led(usage) => usage.usage_types(),
+            Usage::Button(_) => &[
+                UsageType::Selector,
+                UsageType::OnOffControl,
+                UsageType::MomentaryControl,
+                UsageType::OneShotControl,
+            ],
+            Usage::Ordinal(_) => &[UsageType::UsageModifier],
+            Usage::Consumer(usage) => usage.usage_types(),
+            Usage::Digitizer(usage) => usage.usage_types(),
+            Usage::PhysicalInputDevice(usage) => usage.usage_types(),
+            Usage::Sensors(usage) => usage.usage_types(),
+            Usage::BatterySystem(usage) => usage.usage_types(),
+            Usage::Vendor { .. } | Usage::Unknown { .. } => &[],
+        }
+    }
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self, self.name()) {
+            (_, Some(name)) => f.write_str(name),
+            (Usage::Button(id), None) => write!(f, "Button {}", id),
+            (Usage::Ordinal(id), None) => write!(f, "Instance {}", id),
+            (Usage::Vendor { id, .. }, None) => write!(f, "Vendor Usage 0x{:04x}", id),
+            (usage, None) => write!(f, "0x{:04x}", usage.id()),
+        }
+    }
+}
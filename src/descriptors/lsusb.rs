@@ -0,0 +1,480 @@
+//`lsusb -v` compatible rendering of the descriptor tree, so the output can be diffed against linux
+use core::fmt::{self, Display};
+
+use alloc::{collections::BTreeMap, string::String, string::ToString};
+
+#[cfg(feature = "usb-ids")]
+use crate::usb_ids::UsbIds;
+
+use super::{
+    desc_configuration::Configuration,
+    desc_device::{DefinedClassCode, Device},
+    desc_endpoint::Endpoint,
+    desc_hid::Hid,
+    desc_interface::{Interface, InterfaceAssociation},
+    topological_desc::{
+        TopologicalUSBDescriptorEndpoint, TopologicalUSBDescriptorFunction,
+        TopologicalUSBDescriptorRoot,
+    },
+    PortSpeed, USBDescriptor,
+};
+
+pub struct LsusbDump<'a> {
+    root: &'a TopologicalUSBDescriptorRoot,
+    strings: Option<&'a BTreeMap<u8, String>>,
+    speed: Option<PortSpeed>,
+    #[cfg(feature = "usb-ids")]
+    usb_ids: Option<&'a UsbIds>,
+}
+
+impl<'a> LsusbDump<'a> {
+    pub fn new(root: &'a TopologicalUSBDescriptorRoot) -> Self {
+        Self {
+            root,
+            strings: None,
+            speed: None,
+            #[cfg(feature = "usb-ids")]
+            usb_ids: None,
+        }
+    }
+
+    //the speed the device runs at, a superspeed capable device on a usb 2 port draws in 2mA units
+    pub fn with_speed(mut self, speed: PortSpeed) -> Self {
+        self.speed = Some(speed);
+        self
+    }
+
+    //string descriptors already fetched from the device, keyed by string index
+    pub fn with_strings(mut self, strings: &'a BTreeMap<u8, String>) -> Self {
+        self.strings = Some(strings);
+        self
+    }
+
+    #[cfg(feature = "usb-ids")]
+    pub fn with_usb_ids(mut self, usb_ids: &'a UsbIds) -> Self {
+        self.usb_ids = Some(usb_ids);
+        self
+    }
+}
+
+impl Display for LsusbDump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut w = LsusbWriter {
+            f,
+            strings: self.strings,
+            #[cfg(feature = "usb-ids")]
+            usb_ids: self.usb_ids,
+            indent: 0,
+            column: LsusbWriter::COLUMN,
+        };
+
+        let device = &self.root.device;
+        dump_device(&mut w, &device.data)?;
+        //without the port speed bcdUSB is the best guess
+        let super_speed = match self.speed {
+            Some(speed) => matches!(speed, PortSpeed::SuperSpeed | PortSpeed::SuperSpeedPlus),
+            None => device.data.version().major() >= 3,
+        };
+        for cfg in &device.child {
+            dump_configuration(&mut w, &cfg.data, super_speed)?;
+            for function in &cfg.child {
+                dump_function(&mut w, function)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Display for TopologicalUSBDescriptorRoot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        LsusbDump::new(self).fmt(f)
+    }
+}
+
+pub(crate) struct LsusbWriter<'a, 'b> {
+    f: &'a mut fmt::Formatter<'b>,
+    strings: Option<&'a BTreeMap<u8, String>>,
+    #[cfg(feature = "usb-ids")]
+    usb_ids: Option<&'a UsbIds>,
+    indent: usize,
+    column: usize,
+}
+
+impl<'a> LsusbWriter<'a, '_> {
+    //values are right aligned to this column (relative to the indent), same as lsusb's "%-20s%5u"
+    pub(crate) const COLUMN: usize = 25;
+    //video streaming descriptors use a wider layout
+    pub(crate) const WIDE_COLUMN: usize = 37;
+
+    pub(crate) fn block(&mut self, indent: usize, column: usize, title: &str) -> fmt::Result {
+        writeln!(self.f, "{:indent$}{}", "", title)?;
+        self.indent = indent + 2;
+        self.column = column;
+        Ok(())
+    }
+
+    pub(crate) fn field(&mut self, name: &str, value: impl Display) -> fmt::Result {
+        self.field_unit(name, value, "")
+    }
+
+    //"%5u %s", the separating space is printed even if the text is empty
+    pub(crate) fn field_text(
+        &mut self,
+        name: &str,
+        value: impl Display,
+        text: impl Display,
+    ) -> fmt::Result {
+        self.field_unit(name, value, format_args!(" {}", text))
+    }
+
+    pub(crate) fn field_unit(
+        &mut self,
+        name: &str,
+        value: impl Display,
+        unit: impl Display,
+    ) -> fmt::Result {
+        let value = value.to_string();
+        let pad = self.column.saturating_sub(name.len() + value.len()).max(1);
+        writeln!(
+            self.f,
+            "{:indent$}{}{:pad$}{}{}",
+            "",
+            name,
+            "",
+            value,
+            unit,
+            indent = self.indent
+        )
+    }
+
+    pub(crate) fn field_string(&mut self, name: &str, index: u8) -> fmt::Result {
+        let text = self.string(index);
+        self.field_text(name, index, text)
+    }
+
+    //a decoded flag or sub item, printed one level deeper than the fields
+    pub(crate) fn note(&mut self, text: impl Display) -> fmt::Result {
+        writeln!(self.f, "{:indent$}{}", "", text, indent = self.indent + 2)
+    }
+
+    pub(crate) fn line(&mut self, text: impl Display) -> fmt::Result {
+        writeln!(self.f, "{:indent$}{}", "", text, indent = self.indent)
+    }
+
+    //"** UNRECOGNIZED:  07 24 01", one " %02x" per byte like lsusb's dump_bytes
+    pub(crate) fn unrecognized(&mut self, indent: usize, raw: &[u8]) -> fmt::Result {
+        write!(self.f, "{:indent$}** UNRECOGNIZED: ", "")?;
+        for byte in raw {
+            write!(self.f, " {:02x}", byte)?;
+        }
+        writeln!(self.f)
+    }
+
+    pub(crate) fn string(&self, index: u8) -> &'a str {
+        match self.strings {
+            Some(strings) if index != 0 => strings.get(&index).map(|s| s.as_str()).unwrap_or(""),
+            _ => "",
+        }
+    }
+
+    pub(crate) fn vendor_name(&self, vendor: u16) -> &'a str {
+        #[cfg(feature = "usb-ids")]
+        if let Some(name) = self.usb_ids.and_then(|ids| ids.vendor_name(vendor)) {
+            return name;
+        }
+        let _ = vendor;
+        ""
+    }
+
+    pub(crate) fn product_name(&self, vendor: u16, product: u16) -> &'a str {
+        #[cfg(feature = "usb-ids")]
+        if let Some(name) = self
+            .usb_ids
+            .and_then(|ids| ids.product_name(vendor, product))
+        {
+            return name;
+        }
+        let _ = (vendor, product);
+        ""
+    }
+
+    pub(crate) fn class_name(&self, class: u8) -> &'a str {
+        #[cfg(feature = "usb-ids")]
+        if let Some(name) = self.usb_ids.and_then(|ids| ids.class_name(class)) {
+            return name;
+        }
+        DefinedClassCode::class_name(class).unwrap_or("")
+    }
+
+    pub(crate) fn subclass_name(&self, class: u8, subclass: u8) -> &'a str {
+        #[cfg(feature = "usb-ids")]
+        if let Some(name) = self
+            .usb_ids
+            .and_then(|ids| ids.subclass_name(class, subclass))
+        {
+            return name;
+        }
+        DefinedClassCode::subclass_name(class, subclass).unwrap_or("")
+    }
+
+    pub(crate) fn protocol_name(&self, class: u8, subclass: u8, protocol: u8) -> &'a str {
+        #[cfg(feature = "usb-ids")]
+        if let Some(name) = self
+            .usb_ids
+            .and_then(|ids| ids.protocol_name(class, subclass, protocol))
+        {
+            return name;
+        }
+        DefinedClassCode::protocol_name(class, subclass, protocol).unwrap_or("")
+    }
+}
+
+//"0x87  EP 7 IN"
+pub(crate) struct EndpointAddressText(pub(crate) u8);
+
+impl Display for EndpointAddressText {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "  EP {} {}",
+            self.0 & 0x0f,
+            if self.0 & 0x80 != 0 { "IN" } else { "OUT" }
+        )
+    }
+}
+
+fn dump_device(w: &mut LsusbWriter, device: &Device) -> fmt::Result {
+    let (vendor, product_id) = (device.vendor, device.product_id);
+    let (class, subclass, protocol) = (device.class, device.subclass, device.protocol);
+
+    w.block(0, LsusbWriter::COLUMN, "Device Descriptor:")?;
+    w.field("bLength", device.len)?;
+    w.field("bDescriptorType", device.descriptor_type)?;
+    w.field("bcdUSB", device.version())?;
+    w.field_text("bDeviceClass", class, w.class_name(class))?;
+    w.field_text(
+        "bDeviceSubClass",
+        subclass,
+        w.subclass_name(class, subclass),
+    )?;
+    w.field_text(
+        "bDeviceProtocol",
+        protocol,
+        w.protocol_name(class, subclass, protocol),
+    )?;
+    w.field("bMaxPacketSize0", device.max_packet_size0)?;
+    w.field_text(
+        "idVendor",
+        format_args!("{:#06x}", vendor),
+        w.vendor_name(vendor),
+    )?;
+    w.field_text(
+        "idProduct",
+        format_args!("{:#06x}", product_id),
+        w.product_name(vendor, product_id),
+    )?;
+    w.field("bcdDevice", device.device_version())?;
+    w.field_string("iManufacturer", device.manufacture)?;
+    w.field_string("iProduct", device.product)?;
+    w.field_string("iSerial", device.serial_number)?;
+    w.field("bNumConfigurations", device.num_configurations)
+}
+
+fn dump_configuration(w: &mut LsusbWriter, cfg: &Configuration, super_speed: bool) -> fmt::Result {
+    let attributes = cfg.attributes();
+
+    w.block(2, LsusbWriter::COLUMN, "Configuration Descriptor:")?;
+    w.field("bLength", cfg.length())?;
+    w.field("bDescriptorType", cfg.ty())?;
+    w.field("wTotalLength", format_args!("{:#06x}", cfg.total_length()))?;
+    w.field("bNumInterfaces", cfg.num_interfaces())?;
+    w.field("bConfigurationValue", cfg.config_val())?;
+    w.field_string("iConfiguration", cfg.config_string())?;
+    w.field("bmAttributes", format_args!("{:#04x}", attributes))?;
+    if attributes & 0x80 == 0 {
+        w.line("(Missing must-be-set bit!)")?;
+    }
+    if attributes & 0x40 != 0 {
+        w.note("Self Powered")?;
+    } else {
+        w.note("(Bus Powered)")?;
+    }
+    if attributes & 0x20 != 0 {
+        w.note("Remote Wakeup")?;
+    }
+    if attributes & 0x10 != 0 {
+        w.note("Battery Powered")?;
+    }
+    //bMaxPower is in 2mA units, 8mA for super speed
+    let unit = if super_speed { 8 } else { 2 };
+    w.field_unit("MaxPower", cfg.max_power() as u32 * unit, "mA")
+}
+
+fn dump_function(w: &mut LsusbWriter, function: &TopologicalUSBDescriptorFunction) -> fmt::Result {
+    match function {
+        TopologicalUSBDescriptorFunction::InterfaceAssociation((association, functions)) => {
+            dump_interface_association(w, association)?;
+            for function in functions {
+                dump_function(w, function)?;
+            }
+            Ok(())
+        }
+        TopologicalUSBDescriptorFunction::Interface(alternates) => {
+            for (interface, additional, endpoints) in alternates {
+                dump_interface(w, interface)?;
+                for descriptor in additional {
+                    dump_class_specific(w, descriptor)?;
+                }
+                for endpoint in endpoints {
+                    match endpoint {
+                        TopologicalUSBDescriptorEndpoint::Standard(endpoint) => {
+                            dump_endpoint(w, endpoint)?
+                        }
+                        TopologicalUSBDescriptorEndpoint::UNVVideoControlInterruptEndpoint(
+                            endpoint,
+                        ) => endpoint.dump_lsusb(w)?,
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+fn dump_interface_association(
+    w: &mut LsusbWriter,
+    association: &InterfaceAssociation,
+) -> fmt::Result {
+    let (class, subclass, protocol) = (
+        association.function_class,
+        association.function_subclass,
+        association.function_protocol,
+    );
+
+    w.block(4, LsusbWriter::COLUMN, "Interface Association:")?;
+    w.field("bLength", association.len)?;
+    w.field("bDescriptorType", association.descriptor_type)?;
+    w.field("bFirstInterface", association.first_interface)?;
+    w.field("bInterfaceCount", association.interface_count)?;
+    w.field_text("bFunctionClass", class, w.class_name(class))?;
+    w.field_text(
+        "bFunctionSubClass",
+        subclass,
+        w.subclass_name(class, subclass),
+    )?;
+    w.field_text(
+        "bFunctionProtocol",
+        protocol,
+        w.protocol_name(class, subclass, protocol),
+    )?;
+    w.field_string("iFunction", association.function)
+}
+
+fn dump_interface(w: &mut LsusbWriter, interface: &Interface) -> fmt::Result {
+    let (class, subclass, protocol) = interface.ty();
+
+    w.block(4, LsusbWriter::COLUMN, "Interface Descriptor:")?;
+    w.field("bLength", interface.len)?;
+    w.field("bDescriptorType", interface.descriptor_type)?;
+    w.field("bInterfaceNumber", interface.interface_number)?;
+    w.field("bAlternateSetting", interface.alternate_setting)?;
+    w.field("bNumEndpoints", interface.num_endpoints)?;
+    w.field_text("bInterfaceClass", class, w.class_name(class))?;
+    w.field_text(
+        "bInterfaceSubClass",
+        subclass,
+        w.subclass_name(class, subclass),
+    )?;
+    w.field_text(
+        "bInterfaceProtocol",
+        protocol,
+        w.protocol_name(class, subclass, protocol),
+    )?;
+    w.field_string("iInterface", interface.interface)
+}
+
+fn dump_class_specific(w: &mut LsusbWriter, descriptor: &USBDescriptor) -> fmt::Result {
+    match descriptor {
+        USBDescriptor::Hid(hid) => dump_hid(w, hid),
+        USBDescriptor::UVCInterface(uvc) => uvc.dump_lsusb(w),
+        USBDescriptor::UVCClassSpecVideoControlInterruptEndpoint(endpoint) => {
+            endpoint.dump_lsusb(w)
+        }
+        USBDescriptor::Unrecognized(raw) => w.unrecognized(6, raw),
+        //the parser keeps nothing else between an interface and its endpoints
+        _ => Ok(()),
+    }
+}
+
+fn dump_hid(w: &mut LsusbWriter, hid: &Hid) -> fmt::Result {
+    let country_code = hid.country_code;
+
+    w.block(8, LsusbWriter::COLUMN, "HID Device Descriptor:")?;
+    w.field("bLength", hid.len)?;
+    w.field("bDescriptorType", hid.descriptor_type)?;
+    w.field("bcdHID", hid.hid_bcd)?;
+    w.field_text(
+        "bCountryCode",
+        country_code,
+        hid.country_name().unwrap_or(""),
+    )?;
+    w.field("bNumDescriptors", hid.num_descriptions)?;
+    for desc in &hid.descriptors {
+        w.field_text(
+            "bDescriptorType",
+            desc.descriptor_type,
+            match desc.descriptor_type {
+                0x22 => "Report",
+                0x23 => "Physical",
+                _ => "",
+            },
+        )?;
+        w.field("wDescriptorLength", desc.length)?;
+    }
+    Ok(())
+}
+
+fn dump_endpoint(w: &mut LsusbWriter, endpoint: &Endpoint) -> fmt::Result {
+    let (address, attributes, max_packet_size, interval) = (
+        endpoint.endpoint_address,
+        endpoint.attributes,
+        endpoint.max_packet_size,
+        endpoint.interval,
+    );
+
+    w.block(6, LsusbWriter::COLUMN, "Endpoint Descriptor:")?;
+    w.field("bLength", endpoint.length())?;
+    w.field("bDescriptorType", endpoint.descriptor_type())?;
+    w.field_unit(
+        "bEndpointAddress",
+        format_args!("{:#04x}", address),
+        EndpointAddressText(address),
+    )?;
+    w.field("bmAttributes", attributes)?;
+    w.note(format_args!(
+        "Transfer Type            {}",
+        ["Control", "Isochronous", "Bulk", "Interrupt"][(attributes & 0x3) as usize]
+    ))?;
+    //synchronisation and usage bits are only defined for isochronous endpoints
+    if attributes & 0x3 == 1 {
+        w.note(format_args!(
+            "Synch Type               {}",
+            ["None", "Asynchronous", "Adaptive", "Synchronous"][((attributes >> 2) & 0x3) as usize]
+        ))?;
+        w.note(format_args!(
+            "Usage Type               {}",
+            ["Data", "Feedback", "Implicit feedback Data", "(reserved)"]
+                [((attributes >> 4) & 0x3) as usize]
+        ))?;
+    }
+    w.field_unit(
+        "wMaxPacketSize",
+        format_args!("{:#06x}", max_packet_size),
+        format_args!(
+            "  {}x {} bytes",
+            ((max_packet_size >> 11) & 0x3) + 1,
+            max_packet_size & 0x7ff
+        ),
+    )?;
+    w.field("bInterval", interval)
+}
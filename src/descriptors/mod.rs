@@ -1,6 +1,7 @@
 //TODO: Restruct code!
 use core::ptr;
 
+use alloc::vec::Vec;
 use desc_configuration::Configuration;
 use desc_device::Device;
 use desc_endpoint::Endpoint;
@@ -19,6 +20,15 @@ use num_derive::{FromPrimitive, ToPrimitive};
 use num_traits::FromPrimitive;
 use parser::{Error, ParserMetaData};
 
+pub mod hid_boot;
+pub mod hid_builder;
+pub mod hid_decode;
+pub mod hid_digitizer;
+pub mod hid_gamepad;
+pub mod hid_pid;
+pub mod hid_sensor;
+pub mod hid_usage;
+pub mod lsusb;
 pub mod parser;
 pub mod topological_desc;
 
@@ -27,6 +37,8 @@ pub mod desc_device;
 pub mod desc_endpoint;
 pub mod desc_hid;
 pub mod desc_interface;
+pub mod desc_physical;
+pub mod desc_report;
 pub mod desc_str;
 pub mod desc_uvc;
 
@@ -65,6 +77,8 @@ pub enum USBDescriptor {
     Hid(Hid),
     UVCInterface(UVCInterface),
     UVCClassSpecVideoControlInterruptEndpoint(UVCVideoControlInterruptEndpoint),
+    //class specific descriptors nothing here decodes, and standard ones found out of place
+    Unrecognized(Vec<u8>),
 }
 
 impl USBDescriptor {
@@ -75,6 +89,7 @@ impl USBDescriptor {
             Ok(okay) => Ok(okay),
             Err(_) if let ParserMetaData::HID = metadata => Self::from_slice_hid(raw),
             Err(_) if let ParserMetaData::UVC(flag) = metadata => Self::from_slice_uvc(raw, flag),
+            Err(Error::UnrecognizedType(_)) => Ok(Self::Unrecognized(raw.to_vec())),
             Err(any) => panic!("unknown situation {:?},{:?}", any, metadata),
         }
     }
@@ -110,12 +125,13 @@ impl USBDescriptor {
     }
 
     pub(crate) fn from_slice_hid(raw: &[u8]) -> Result<Self, Error> {
-        match HIDDescriptorTypes::from_u8(raw[1]).unwrap() {
-            HIDDescriptorTypes::Hid => {
-                Ok(Self::Hid(unsafe { ptr::read((raw as *const [u8]).cast()) }))
+        match HIDDescriptorTypes::from_u8(raw[1]) {
+            Some(HIDDescriptorTypes::Hid) => Hid::from_u8_array(raw).map(Self::Hid),
+            //fetched with GET_DESCRIPTOR and parsed by desc_report/desc_physical
+            Some(HIDDescriptorTypes::HIDReport | HIDDescriptorTypes::HIDPhysical) => {
+                Err(Error::OutOfBandDescriptor(raw[1]))
             }
-            HIDDescriptorTypes::HIDReport => todo!(),
-            HIDDescriptorTypes::HIDPhysical => todo!(),
+            None => Ok(Self::Unrecognized(raw.to_vec())),
         }
     }
 
@@ -126,27 +142,28 @@ impl USBDescriptor {
         );
         match USBStandardDescriptorTypes::from_u8(raw[1]) {
             Some(t) => {
-                let raw: *const [u8] = raw;
+                let ptr: *const [u8] = raw;
                 match t {
                     // SAFETY: This operation is safe because the length of `raw` is equivalent to the
                     // one of the descriptor.
                     USBStandardDescriptorTypes::Device => {
-                        Ok(Self::Device(unsafe { ptr::read(raw.cast()) }))
+                        Ok(Self::Device(unsafe { ptr::read(ptr.cast()) }))
                     }
                     USBStandardDescriptorTypes::Configuration => {
-                        Ok(Self::Configuration(unsafe { ptr::read(raw.cast()) }))
+                        Ok(Self::Configuration(unsafe { ptr::read(ptr.cast()) }))
                     }
                     USBStandardDescriptorTypes::String => {
-                        Ok(Self::Str(unsafe { ptr::read(raw.cast()) }))
+                        Ok(Self::Str(unsafe { ptr::read(ptr.cast()) }))
                     }
                     USBStandardDescriptorTypes::Interface => {
-                        Ok(Self::Interface(unsafe { ptr::read(raw.cast()) }))
+                        Ok(Self::Interface(unsafe { ptr::read(ptr.cast()) }))
                     }
+                    //Endpoint carries the companion as well and is larger than the descriptor
                     USBStandardDescriptorTypes::Endpoint => {
-                        Ok(Self::Endpoint(unsafe { ptr::read(raw.cast()) }))
+                        Ok(Self::Endpoint(Endpoint::from_u8_array(raw)))
                     }
                     USBStandardDescriptorTypes::InterfaceAssociation => {
-                        Ok(Self::InterfaceAssociation(unsafe { ptr::read(raw.cast()) }))
+                        Ok(Self::InterfaceAssociation(unsafe { ptr::read(ptr.cast()) }))
                     }
                     other => {
                         unimplemented!("please implement descriptor type:{:?}", other)
@@ -158,7 +175,7 @@ impl USBDescriptor {
     }
 }
 
-#[derive(Copy, Clone, FromPrimitive)]
+#[derive(Copy, Clone, Debug, PartialEq, FromPrimitive)]
 #[repr(u8)]
 pub enum PortSpeed {
     FullSpeed = 1,
@@ -8,7 +8,8 @@ use num_traits::FromPrimitive;
 use crate::descriptors::USBStandardDescriptorTypes;
 
 use super::{
-    desc_device::StandardUSBDeviceClassCode,
+    desc_device::{DefinedClassCode, DescriptorLevel, StandardUSBDeviceClassCode},
+    desc_endpoint::SuperSpeedCmp,
     desc_interface::{Interface, InterfaceAssociation},
     desc_uvc::{
         uvc_interfaces::{
@@ -32,6 +33,7 @@ pub struct RawDescriptorParser {
     result: Option<TopologicalUSBDescriptorDevice>,
     others: Vec<USBDescriptor>,
     metadata: ParserMetaData,
+    misplaced_class_codes: Vec<MisplacedClassCode>,
     current: usize,
     current_len: usize,
 }
@@ -43,6 +45,10 @@ pub enum Error {
     EndOfDescriptors,
     NotReadyToParse,
     StateSwitch,
+    InvalidMaxPacketSize0(u8),
+    OutOfBandDescriptor(u8),
+    //descriptor type and the number of bytes that were actually there
+    ShortDescriptor(u8, usize),
 }
 
 #[derive(PartialEq, Debug)]
@@ -62,6 +68,15 @@ pub enum ParserMetaData {
     NotDetermined,
 }
 
+//a class code that the usb-if table does not allow at the level it appeared on
+#[derive(Copy, Clone, Debug)]
+pub struct MisplacedClassCode {
+    pub level: DescriptorLevel,
+    pub class: u8,
+    pub subclass: u8,
+    pub protocol: u8,
+}
+
 #[derive(Clone, Debug)]
 pub enum ParserMetaDataUnknownSituation {
     NoSpecial, //treat as standard usb device
@@ -72,18 +87,25 @@ pub enum ParserMetaDataUnknownSituation {
 impl ParserMetaData {
     //refer https://www.usb.org/defined-class-codes
     pub fn determine(class: u8, subclass: u8, protocol: u8) -> Self {
-        trace!("parse metadata! determining");
+        let code = DefinedClassCode::lookup(class, Some(subclass), Some(protocol));
+        trace!(
+            "parse metadata! determining {:?}",
+            code.map(|code| code.name)
+        );
         let result = {
-            match (
-                StandardUSBDeviceClassCode::from_u8(class),
-                subclass,
-                protocol,
-            ) {
-                (Some(StandardUSBDeviceClassCode::Miscellaneous), 0x02, 0x01) => {
+            //the most specific table entry decides, wildcard entries cover a whole class
+            match code.map(|code| (code.class, code.subclass, code.protocol)) {
+                Some((class, Some(0x02), Some(0x01)))
+                    if class == StandardUSBDeviceClassCode::Miscellaneous as u8 =>
+                {
                     return Self::Unknown(ParserMetaDataUnknownSituation::ReferIAC)
                 }
-                (Some(StandardUSBDeviceClassCode::HID), _, _) => return Self::HID,
-                (Some(StandardUSBDeviceClassCode::ReferInterfaceDescriptor), _, _) => {
+                Some((class, _, _)) if class == StandardUSBDeviceClassCode::HID as u8 => {
+                    return Self::HID
+                }
+                Some((class, None, None))
+                    if class == StandardUSBDeviceClassCode::ReferInterfaceDescriptor as u8 =>
+                {
                     return Self::Unknown(ParserMetaDataUnknownSituation::ReferInterface)
                 }
                 _ => {}
@@ -119,6 +141,7 @@ impl RawDescriptorParser {
             result: None,
             others: Vec::new(),
             metadata: ParserMetaData::NotDetermined,
+            misplaced_class_codes: Vec::new(),
         }
     }
 
@@ -145,6 +168,7 @@ impl RawDescriptorParser {
             device: self.result.unwrap(),
             others: self.others,
             metadata: self.metadata,
+            misplaced_class_codes: self.misplaced_class_codes,
         }
     }
 
@@ -216,6 +240,12 @@ impl RawDescriptorParser {
     fn parse_single_device_descriptor(&mut self) -> Result<TopologicalUSBDescriptorDevice, Error> {
         trace!("parse single device desc!");
         if let USBDescriptor::Device(dev) = self.parse_any_descriptor()? {
+            self.check_class_code(
+                DescriptorLevel::Device,
+                dev.class,
+                dev.subclass,
+                dev.protocol,
+            );
             if let ParserMetaData::NotDetermined = self.metadata {
                     self.metadata =
                         ParserMetaData::determine(dev.class, dev.subclass, dev.protocol);
@@ -373,6 +403,12 @@ impl RawDescriptorParser {
     fn parse_interface_association(&mut self) -> Result<InterfaceAssociation, Error> {
         match self.parse_any_descriptor()? {
             USBDescriptor::InterfaceAssociation(interface_association) => {
+                self.check_class_code(
+                    DescriptorLevel::Interface,
+                    interface_association.function_class,
+                    interface_association.function_subclass,
+                    interface_association.function_protocol,
+                );
                 if let ParserMetaData::Unknown(ParserMetaDataUnknownSituation::ReferIAC) =
                     self.metadata
                 {
@@ -450,6 +486,12 @@ impl RawDescriptorParser {
         trace!("parse interfaces,metadata:{:?}", self.metadata);
         match self.parse_any_descriptor()? {
             USBDescriptor::Interface(int) => {
+                self.check_class_code(
+                    DescriptorLevel::Interface,
+                    int.interface_class,
+                    int.interface_subclass,
+                    int.interface_protocol,
+                );
                 match &self.metadata {
                     ParserMetaData::UVC(_) => {
                         self.metadata = ParserMetaData::UVC(int.interface_subclass);
@@ -471,6 +513,17 @@ impl RawDescriptorParser {
         }
     }
 
+    fn check_class_code(&mut self, level: DescriptorLevel, class: u8, subclass: u8, protocol: u8) {
+        if !DefinedClassCode::is_valid_at(level, class, subclass, protocol) {
+            self.misplaced_class_codes.push(MisplacedClassCode {
+                level,
+                class,
+                subclass,
+                protocol,
+            });
+        }
+    }
+
     fn parse_other_descriptors_by_metadata(&mut self) -> Vec<USBDescriptor> {
         trace!(
             "parse additional data for interface with metadata:{:?}",
@@ -486,11 +539,17 @@ impl RawDescriptorParser {
                 ) => break,
                 Some(_) | None => {
                     trace!("parse misc desc!");
-                    vec.push(
-                        self.parse_any_descriptor()
-                            .inspect_err(|e| error!("usb descriptor parse failed:{:?}", e))
-                            .unwrap(),
-                    );
+                    let raw = self.cut_raw_descriptor().unwrap();
+                    let descriptor = USBDescriptor::from_slice(&raw, self.metadata.clone())
+                        .inspect_err(|e| error!("usb descriptor parse failed:{:?}", e))
+                        .unwrap();
+                    //standard descriptors do not belong here, keep their bytes like lsusb does
+                    vec.push(match descriptor {
+                        USBDescriptor::Device(_)
+                        | USBDescriptor::Configuration(_)
+                        | USBDescriptor::Str(_) => USBDescriptor::Unrecognized(raw),
+                        other => other,
+                    });
                     continue;
                 }
             }
@@ -504,7 +563,13 @@ impl RawDescriptorParser {
 
         loop {
             if let Some(USBStandardDescriptorTypes::Endpoint) = self.peek_std_desc_type() {
-                if let USBDescriptor::Endpoint(endpoint) = self.parse_any_descriptor().unwrap() {
+                if let USBDescriptor::Endpoint(mut endpoint) = self.parse_any_descriptor().unwrap() {
+                    if let Some(USBStandardDescriptorTypes::SuperSpeedEndpointCompanion) =
+                        self.peek_std_desc_type()
+                    {
+                        let raw = self.cut_raw_descriptor().unwrap();
+                        endpoint.ssc = Some(SuperSpeedCmp::from_u8_array(&raw));
+                    }
                     trace!("parsed endpoint:{:?}", endpoint);
                     endpoints.push(TopologicalUSBDescriptorEndpoint::Standard(endpoint))
                 }
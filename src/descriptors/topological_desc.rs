@@ -7,7 +7,7 @@ use super::{
     desc_endpoint::Endpoint,
     desc_interface::{Interface, InterfaceAssociation},
     desc_uvc::uvc_endpoints::UVCVideoControlInterruptEndpoint,
-    parser::ParserMetaData,
+    parser::{MisplacedClassCode, ParserMetaData},
     USBDescriptor,
 };
 
@@ -39,6 +39,7 @@ pub struct TopologicalUSBDescriptorRoot {
     pub device: TopologicalUSBDescriptorDevice,
     pub others: Vec<USBDescriptor>,
     pub metadata: ParserMetaData,
+    pub misplaced_class_codes: Vec<MisplacedClassCode>,
 }
 
 #[derive(Clone, Debug)]
@@ -3,5 +3,9 @@
 #![allow(dead_code)]
 
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod descriptors;
+#[cfg(feature = "usb-ids")]
+pub mod usb_ids;
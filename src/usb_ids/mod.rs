@@ -0,0 +1,233 @@
+//offline vendor/product/class names from the usb.ids database, see http://www.linux-usb.org/usb.ids
+//the embedded copy is stored zlib compressed, update-usb-ids.sh next to it regenerates it
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+};
+
+use log::trace;
+
+use crate::descriptors::desc_device::Device;
+
+static EMBEDDED_USB_IDS: &[u8] = include_bytes!("usb.ids.zlib");
+
+#[derive(Debug)]
+pub enum Error {
+    Decompress,
+    #[cfg(feature = "std")]
+    Io(std::io::ErrorKind),
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct UsbIds {
+    vendors: BTreeMap<u16, Vendor>,
+    classes: BTreeMap<u8, Class>,
+    version: Option<String>,
+    date: Option<String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Vendor {
+    pub name: String,
+    pub products: BTreeMap<u16, String>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Class {
+    pub name: String,
+    pub subclasses: BTreeMap<u8, Subclass>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Subclass {
+    pub name: String,
+    pub protocols: BTreeMap<u8, String>,
+}
+
+enum Section {
+    Vendor(u16),
+    Class(u8, Option<u8>),
+    Other,
+}
+
+impl UsbIds {
+    pub fn embedded() -> Self {
+        Self::from_compressed(EMBEDDED_USB_IDS).expect("embedded usb.ids snapshot is corrupted!")
+    }
+
+    pub fn from_compressed(raw: &[u8]) -> Result<Self, Error> {
+        let text =
+            miniz_oxide::inflate::decompress_to_vec_zlib(raw).map_err(|_| Error::Decompress)?;
+        Ok(Self::from_bytes(&text))
+    }
+
+    //usb.ids is mostly ascii but a few names are latin-1, those lines are kept lossy
+    pub fn from_bytes(raw: &[u8]) -> Self {
+        match core::str::from_utf8(raw) {
+            Ok(text) => Self::parse(text),
+            Err(_) => Self::parse(&String::from_utf8_lossy(raw)),
+        }
+    }
+
+    #[cfg(feature = "std")]
+    pub fn from_path<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Error> {
+        let raw = std::fs::read(path).map_err(|e| Error::Io(e.kind()))?;
+        Ok(Self::from_bytes(&raw))
+    }
+
+    pub fn parse(text: &str) -> Self {
+        let mut ids = Self::default();
+        let mut section = Section::Other;
+
+        for line in text.lines() {
+            if let Some(comment) = line.strip_prefix('#') {
+                //upstream snapshots carry "# Version: YYYY.MM.DD" and "# Date: ..." in their header
+                let comment = comment.trim();
+                if let Some(version) = comment.strip_prefix("Version:") {
+                    ids.version
+                        .get_or_insert_with(|| version.trim().to_string());
+                } else if let Some(date) = comment.strip_prefix("Date:") {
+                    ids.date.get_or_insert_with(|| date.trim().to_string());
+                }
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+
+            let depth = line.bytes().take_while(|b| *b == b'\t').count();
+            let line = &line[depth..];
+
+            match (depth, &mut section) {
+                (0, _) if let Some(rest) = line.strip_prefix("C ") => {
+                    section = match split_entry::<u8>(rest) {
+                        Some((class, name)) => {
+                            ids.classes.entry(class).or_default().name = name.to_string();
+                            Section::Class(class, None)
+                        }
+                        None => Section::Other,
+                    }
+                }
+                (0, _) => {
+                    section = match split_entry::<u16>(line) {
+                        Some((vendor, name)) if line.as_bytes().get(4) == Some(&b' ') => {
+                            ids.vendors.entry(vendor).or_default().name = name.to_string();
+                            Section::Vendor(vendor)
+                        }
+                        //AT, HID, R, BIAS, PHY, HUT, L, HCC and VT lists are not used here
+                        _ => Section::Other,
+                    }
+                }
+                (1, Section::Vendor(vendor)) => {
+                    if let Some((product, name)) = split_entry::<u16>(line) {
+                        ids.vendors
+                            .entry(*vendor)
+                            .or_default()
+                            .products
+                            .insert(product, name.to_string());
+                    }
+                }
+                (1, Section::Class(class, current_subclass)) => {
+                    if let Some((subclass, name)) = split_entry::<u8>(line) {
+                        ids.classes
+                            .entry(*class)
+                            .or_default()
+                            .subclasses
+                            .entry(subclass)
+                            .or_default()
+                            .name = name.to_string();
+                        *current_subclass = Some(subclass);
+                    }
+                }
+                (2, Section::Class(class, Some(subclass))) => {
+                    if let Some((protocol, name)) = split_entry::<u8>(line) {
+                        ids.classes
+                            .entry(*class)
+                            .or_default()
+                            .subclasses
+                            .entry(*subclass)
+                            .or_default()
+                            .protocols
+                            .insert(protocol, name.to_string());
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        trace!(
+            "loaded usb.ids with {} vendors, {} classes",
+            ids.vendors.len(),
+            ids.classes.len()
+        );
+        ids
+    }
+
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    pub fn date(&self) -> Option<&str> {
+        self.date.as_deref()
+    }
+
+    pub fn vendor(&self, vendor: u16) -> Option<&Vendor> {
+        self.vendors.get(&vendor)
+    }
+
+    pub fn vendor_name(&self, vendor: u16) -> Option<&str> {
+        self.vendors.get(&vendor).map(|v| v.name.as_str())
+    }
+
+    pub fn product_name(&self, vendor: u16, product: u16) -> Option<&str> {
+        self.vendors
+            .get(&vendor)
+            .and_then(|v| v.products.get(&product))
+            .map(|name| name.as_str())
+    }
+
+    pub fn device_names(&self, device: &Device) -> (Option<&str>, Option<&str>) {
+        let (vendor, product) = (device.vendor, device.product_id);
+        (self.vendor_name(vendor), self.product_name(vendor, product))
+    }
+
+    pub fn class_name(&self, class: u8) -> Option<&str> {
+        self.classes.get(&class).map(|c| c.name.as_str())
+    }
+
+    pub fn subclass_name(&self, class: u8, subclass: u8) -> Option<&str> {
+        self.classes
+            .get(&class)
+            .and_then(|c| c.subclasses.get(&subclass))
+            .map(|s| s.name.as_str())
+    }
+
+    pub fn protocol_name(&self, class: u8, subclass: u8, protocol: u8) -> Option<&str> {
+        self.classes
+            .get(&class)
+            .and_then(|c| c.subclasses.get(&subclass))
+            .and_then(|s| s.protocols.get(&protocol))
+            .map(|name| name.as_str())
+    }
+
+    pub fn class_names(
+        &self,
+        class: u8,
+        subclass: u8,
+        protocol: u8,
+    ) -> (Option<&str>, Option<&str>, Option<&str>) {
+        (
+            self.class_name(class),
+            self.subclass_name(class, subclass),
+            self.protocol_name(class, subclass, protocol),
+        )
+    }
+}
+
+//"xxxx  name", ids are lower case hex separated from the name by two spaces
+fn split_entry<T: num_traits::Num>(line: &str) -> Option<(T, &str)> {
+    let (id, name) = line.split_once(char::is_whitespace)?;
+    T::from_str_radix(id, 16)
+        .ok()
+        .map(|id| (id, name.trim_start()))
+}
@@ -0,0 +1,19 @@
+#!/bin/sh
+# regenerate usb.ids.zlib from the upstream database, or from a local copy such as
+# /usr/share/hwdata/usb.ids when a path is given
+set -eu
+
+cd "$(dirname "$0")"
+url=${USB_IDS_URL:-http://www.linux-usb.org/usb.ids}
+tmp=$(mktemp)
+trap 'rm -f "$tmp"' EXIT
+
+if [ $# -gt 0 ]; then
+    cp "$1" "$tmp"
+else
+    curl -fsSL "$url" -o "$tmp"
+fi
+
+python3 -c "import zlib,sys;sys.stdout.buffer.write(zlib.compress(open(sys.argv[1],'rb').read(),9))" "$tmp" > usb.ids.zlib
+grep -E '^# (Version|Date):' "$tmp" || echo "warning: no Version/Date header in $tmp" >&2
+echo "wrote usb.ids.zlib, $(wc -c < usb.ids.zlib) bytes"
@@ -0,0 +1,176 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_device::{ClassCodeUsage, DefinedClassCode, DescriptorLevel},
+    parser::{ParserMetaData, ParserMetaDataUnknownSituation, RawDescriptorParser},
+};
+
+fn name(class: u8, subclass: u8, protocol: u8) -> Option<&'static str> {
+    DefinedClassCode::lookup(class, Some(subclass), Some(protocol)).map(|code| code.name)
+}
+
+//a device with the given class and one interface of `interface_class`, returns what the
+//parser reported as misplaced
+fn misplaced(device_class: u8, interface_class: u8) -> Vec<(DescriptorLevel, u8)> {
+    let device = vec![
+        18,
+        0x01,
+        0x00,
+        0x02,
+        device_class,
+        0,
+        0,
+        64,
+        0x34,
+        0x12,
+        0x78,
+        0x56,
+        0x00,
+        0x01,
+        0,
+        0,
+        0,
+        1,
+    ];
+    let mut config = vec![
+        9,
+        0x02,
+        0,
+        0,
+        1,
+        1,
+        0,
+        0x80,
+        50, //
+        9,
+        0x04,
+        0,
+        0,
+        1,
+        interface_class,
+        0,
+        0,
+        0, //
+        7,
+        0x05,
+        0x81,
+        0x03,
+        0x08,
+        0x00,
+        12,
+    ];
+    let total_length = config.len() as u16;
+    config[2..4].copy_from_slice(&total_length.to_le_bytes());
+    config.extend_from_slice(&[0, 0]);
+
+    let mut parser = RawDescriptorParser::new(device);
+    parser.single_state_cycle();
+    parser.append_config(config);
+    parser
+        .summarize()
+        .misplaced_class_codes
+        .iter()
+        .map(|code| (code.level, code.class))
+        .collect()
+}
+
+#[test]
+fn lookup_wildcards() {
+    //no subclass entry for 0x00, the class wide entry matches
+    assert_eq!(name(0x03, 0x00, 0x00), Some("Human Interface Device"));
+    //a subclass entry without a protocol matches any protocol
+    assert_eq!(name(0x03, 0x01, 0x05), Some("Boot Interface Subclass"));
+    assert_eq!(name(0x03, 0x01, 0x01), Some("Keyboard"));
+    assert_eq!(name(0x42, 0x00, 0x00), None);
+
+    let any_protocol = DefinedClassCode::lookup(0x0E, Some(0x02), None).unwrap();
+    assert_eq!(
+        (any_protocol.subclass, any_protocol.protocol),
+        (Some(0x02), None)
+    );
+    assert_eq!(DefinedClassCode::class_name(0xEF), Some("Miscellaneous"));
+    assert_eq!(
+        DefinedClassCode::subclass_name(0x0E, 0x01),
+        Some("Video Control")
+    );
+    assert_eq!(DefinedClassCode::protocol_name(0x03, 0x01, 0x05), None);
+    assert_eq!(
+        DefinedClassCode::protocol_name(0xEF, 0x02, 0x01),
+        Some("Interface Association Descriptor")
+    );
+}
+
+#[test]
+fn class_code_usage() {
+    assert!(ClassCodeUsage::Both.allows(DescriptorLevel::Device));
+    assert!(!ClassCodeUsage::Interface.allows(DescriptorLevel::Device));
+    assert!(DefinedClassCode::is_valid_at(
+        DescriptorLevel::Device,
+        0x09,
+        0x00,
+        0x02
+    ));
+    assert!(DefinedClassCode::is_valid_at(
+        DescriptorLevel::Interface,
+        0x09,
+        0x00,
+        0x00
+    ));
+    assert!(!DefinedClassCode::is_valid_at(
+        DescriptorLevel::Device,
+        0x01,
+        0x01,
+        0x00
+    ));
+    //codes the table does not know are not flagged
+    assert!(DefinedClassCode::is_valid_at(
+        DescriptorLevel::Device,
+        0x42,
+        0x00,
+        0x00
+    ));
+}
+
+#[test]
+fn determine_from_table() {
+    assert!(matches!(
+        ParserMetaData::determine(0x03, 0x00, 0x00),
+        ParserMetaData::HID
+    ));
+    assert!(matches!(
+        ParserMetaData::determine(0x03, 0x01, 0x02),
+        ParserMetaData::HID
+    ));
+    assert!(matches!(
+        ParserMetaData::determine(0xEF, 0x02, 0x01),
+        ParserMetaData::Unknown(ParserMetaDataUnknownSituation::ReferIAC)
+    ));
+    assert!(matches!(
+        ParserMetaData::determine(0x00, 0x00, 0x00),
+        ParserMetaData::Unknown(ParserMetaDataUnknownSituation::ReferInterface)
+    ));
+    assert!(matches!(
+        ParserMetaData::determine(0x0E, 0x03, 0x00),
+        ParserMetaData::UVC(0)
+    ));
+    //other miscellaneous codes are not an interface association
+    assert!(matches!(
+        ParserMetaData::determine(0xEF, 0x01, 0x01),
+        ParserMetaData::Unknown(ParserMetaDataUnknownSituation::NoSpecial)
+    ));
+}
+
+#[test]
+fn misplaced_class_codes() {
+    assert_eq!(misplaced(0x00, 0x03), []);
+    //audio is an interface class, billboard a device class
+    assert_eq!(misplaced(0x01, 0xFF), [(DescriptorLevel::Device, 0x01)]);
+    assert_eq!(misplaced(0x00, 0x11), [(DescriptorLevel::Interface, 0x11)]);
+    assert_eq!(
+        misplaced(0x03, 0x11),
+        [
+            (DescriptorLevel::Device, 0x03),
+            (DescriptorLevel::Interface, 0x11)
+        ]
+    );
+    //hubs carry the class on both levels
+    assert_eq!(misplaced(0x09, 0x09), []);
+}
@@ -0,0 +1,56 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_device::{BcdVersion, Device},
+    parser::Error,
+    PortSpeed,
+};
+
+fn device(max_packet_size0: u8) -> Device {
+    Device {
+        max_packet_size0,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn bcd_version() {
+    let usb21 = BcdVersion::new(2, 1, 0).unwrap();
+    assert_eq!(usb21.raw(), 0x0210);
+    assert_eq!((usb21.major(), usb21.minor(), usb21.sub_minor()), (2, 1, 0));
+    assert_eq!(BcdVersion::new(99, 9, 9).unwrap().raw(), 0x9999);
+    assert_eq!(BcdVersion::from_raw(0x1234).major(), 12);
+    assert_eq!(usb21.to_string(), "2.10");
+    assert!(BcdVersion::new(3, 2, 0).unwrap() > usb21);
+
+    //digits that do not fit the bcd nibbles are rejected rather than truncated
+    assert_eq!(BcdVersion::new(100, 0, 0), None);
+    assert_eq!(BcdVersion::new(1, 10, 0), None);
+    assert_eq!(BcdVersion::new(1, 0, 10), None);
+}
+
+#[test]
+fn ep0_max_packet_size_per_speed() {
+    let legal = |speed: PortSpeed| -> Vec<u8> {
+        (0..=u8::MAX)
+            .filter(|&raw| device(raw).max_packet_size(speed).is_ok())
+            .collect()
+    };
+    assert_eq!(legal(PortSpeed::LowSpeed), [8]);
+    assert_eq!(legal(PortSpeed::FullSpeed), [8, 16, 32, 64]);
+    assert_eq!(legal(PortSpeed::HighSpeed), [64]);
+    //an exponent on superspeed, 2^9
+    assert_eq!(legal(PortSpeed::SuperSpeed), [9]);
+    assert_eq!(legal(PortSpeed::SuperSpeedPlus), [9]);
+
+    assert_eq!(
+        device(9).max_packet_size(PortSpeed::SuperSpeed).ok(),
+        Some(512)
+    );
+    assert_eq!(
+        device(64).max_packet_size(PortSpeed::HighSpeed).ok(),
+        Some(64)
+    );
+    assert!(matches!(
+        device(64).max_packet_size(PortSpeed::SuperSpeed),
+        Err(Error::InvalidMaxPacketSize0(64))
+    ));
+}
@@ -0,0 +1,83 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_endpoint::{Endpoint, EndpointInterval},
+    PortSpeed,
+};
+
+fn interval(address: u8, attributes: u8, b_interval: u8, speed: PortSpeed) -> (u32, u8) {
+    let endpoint = Endpoint::from_u8_array(&[7, 0x05, address, attributes, 0x40, 0x00, b_interval]);
+    let EndpointInterval {
+        microframes,
+        xhci_interval,
+        ..
+    } = endpoint.service_interval(speed);
+    (microframes, xhci_interval)
+}
+
+#[test]
+fn full_and_low_speed_interrupt() {
+    //bInterval counts frames, xhci rounds down to a power of two of at least 8 microframes
+    assert_eq!(interval(0x81, 0x03, 10, PortSpeed::FullSpeed), (80, 6));
+    assert_eq!(interval(0x81, 0x03, 1, PortSpeed::FullSpeed), (8, 3));
+    assert_eq!(interval(0x01, 0x03, 255, PortSpeed::LowSpeed), (2040, 10));
+    //0 is out of range and clamped to 1
+    assert_eq!(interval(0x81, 0x03, 0, PortSpeed::LowSpeed), (8, 3));
+}
+
+#[test]
+fn full_speed_isochronous() {
+    //2^(bInterval-1) frames
+    assert_eq!(interval(0x81, 0x01, 1, PortSpeed::FullSpeed), (8, 3));
+    assert_eq!(interval(0x01, 0x01, 4, PortSpeed::FullSpeed), (64, 6));
+    assert_eq!(
+        interval(0x81, 0x01, 12, PortSpeed::FullSpeed),
+        (1 << 14, 14)
+    );
+    //the 4 bit xhci field cannot hold 2^15 frames and more
+    assert_eq!(
+        interval(0x81, 0x01, 13, PortSpeed::FullSpeed),
+        (1 << 15, 15)
+    );
+    assert_eq!(
+        interval(0x81, 0x01, 16, PortSpeed::FullSpeed),
+        (1 << 18, 15)
+    );
+}
+
+#[test]
+fn high_and_super_speed_periodic() {
+    //2^(bInterval-1) microframes
+    assert_eq!(interval(0x81, 0x03, 1, PortSpeed::HighSpeed), (1, 0));
+    assert_eq!(interval(0x81, 0x03, 4, PortSpeed::HighSpeed), (8, 3));
+    assert_eq!(
+        interval(0x81, 0x01, 16, PortSpeed::HighSpeed),
+        (1 << 15, 15)
+    );
+    assert_eq!(interval(0x01, 0x01, 1, PortSpeed::SuperSpeed), (1, 0));
+    assert_eq!(interval(0x81, 0x03, 8, PortSpeed::SuperSpeedPlus), (128, 7));
+    //out of range values are clamped to 1..=16
+    assert_eq!(interval(0x81, 0x03, 0, PortSpeed::HighSpeed), (1, 0));
+    assert_eq!(
+        interval(0x81, 0x03, 20, PortSpeed::SuperSpeed),
+        (1 << 15, 15)
+    );
+}
+
+#[test]
+fn high_speed_nak_rate() {
+    assert_eq!(interval(0x02, 0x02, 8, PortSpeed::HighSpeed), (8, 3));
+    assert_eq!(interval(0x00, 0x00, 255, PortSpeed::HighSpeed), (255, 7));
+    assert_eq!(interval(0x02, 0x02, 0, PortSpeed::HighSpeed), (0, 0));
+    //bulk IN and full speed bulk ignore bInterval
+    assert_eq!(interval(0x82, 0x02, 8, PortSpeed::HighSpeed), (0, 0));
+    assert_eq!(interval(0x02, 0x02, 8, PortSpeed::FullSpeed), (0, 0));
+}
+
+#[test]
+fn interval_in_nanoseconds() {
+    let endpoint = Endpoint::from_u8_array(&[7, 0x05, 0x81, 0x03, 0x08, 0x00, 10]);
+    assert_eq!(
+        endpoint.service_interval(PortSpeed::FullSpeed).nanos,
+        10_000_000
+    );
+    assert_eq!(endpoint.calc_actual_interval(PortSpeed::FullSpeed), 6);
+}
@@ -0,0 +1,179 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_hid::USBHIDProtocolDescriptorType,
+    desc_interface::Interface,
+    hid_boot::{
+        boot_protocol, should_use_boot_protocol, BootKeyboard, BootMouse, BootReportError, KeyCode,
+        ModifierKey, MouseEvent,
+    },
+};
+
+fn report(modifiers: u8, keys: &[u8]) -> [u8; 8] {
+    let mut report = [modifiers, 0, 0, 0, 0, 0, 0, 0];
+    report[2..2 + keys.len()].copy_from_slice(keys);
+    report
+}
+
+fn keys(keyboard: &mut BootKeyboard, modifiers: u8, pressed: &[u8]) -> Vec<(KeyCode, bool)> {
+    keyboard
+        .handle(&report(modifiers, pressed))
+        .unwrap()
+        .iter()
+        .map(|event| (event.key, event.pressed))
+        .collect()
+}
+
+#[test]
+fn boot_interfaces() {
+    let interface = |class, subclass, protocol| Interface {
+        interface_class: class,
+        interface_subclass: subclass,
+        interface_protocol: protocol,
+        ..Default::default()
+    };
+    assert!(matches!(
+        boot_protocol(&interface(3, 1, 1)),
+        Some(USBHIDProtocolDescriptorType::KeyBoard)
+    ));
+    assert!(matches!(
+        boot_protocol(&interface(3, 1, 2)),
+        Some(USBHIDProtocolDescriptorType::Mouse)
+    ));
+    //the protocol only means something on the boot subclass
+    assert!(boot_protocol(&interface(3, 0, 1)).is_none());
+    assert!(boot_protocol(&interface(3, 1, 0)).is_none());
+    assert!(should_use_boot_protocol(&interface(3, 1, 1), false));
+    assert!(!should_use_boot_protocol(&interface(3, 1, 1), true));
+}
+
+#[test]
+fn press_and_release() {
+    let mut keyboard = BootKeyboard::default();
+    //left shift and a
+    assert_eq!(
+        keys(&mut keyboard, 0x02, &[0x04]),
+        [
+            (KeyCode::Modifier(ModifierKey::LeftShift), true),
+            (KeyCode::Char('A'), true)
+        ]
+    );
+    //b joins, a stays down and is not reported again
+    assert_eq!(
+        keys(&mut keyboard, 0x02, &[0x04, 0x05]),
+        [(KeyCode::Char('B'), true)]
+    );
+    //the slot order changing is not a release
+    assert!(keys(&mut keyboard, 0x02, &[0x05, 0x04]).is_empty());
+    assert_eq!(
+        keys(&mut keyboard, 0x00, &[]),
+        [
+            (KeyCode::Modifier(ModifierKey::LeftShift), false),
+            (KeyCode::Char('b'), false),
+            (KeyCode::Char('a'), false)
+        ]
+    );
+    assert_eq!(keyboard.pressed, [0; 6]);
+
+    let events = keyboard.handle(&report(0x11, &[0x1E])).unwrap();
+    assert!(events[2].modifiers.ctrl() && !events[2].modifiers.shift());
+    assert_eq!(events[2].scancode, 0x1E);
+}
+
+#[test]
+fn rollover_keeps_the_previous_keys() {
+    let mut keyboard = BootKeyboard::default();
+    keys(&mut keyboard, 0, &[0x04, 0x05]);
+    //ErrorRollOver in every slot, modifiers are still reported
+    assert_eq!(
+        keys(&mut keyboard, 0x01, &[0x01; 6]),
+        [(KeyCode::Modifier(ModifierKey::LeftCtrl), true)]
+    );
+    assert!(keyboard.rollover);
+    assert_eq!(keyboard.pressed, [0x04, 0x05, 0, 0, 0, 0]);
+    //POSTFail and ErrorUndefined are phantom states as well
+    assert!(keys(&mut keyboard, 0x01, &[0x02; 6]).is_empty());
+    assert!(keys(&mut keyboard, 0x01, &[0x03; 6]).is_empty());
+
+    assert_eq!(
+        keys(&mut keyboard, 0x01, &[0x05]),
+        [(KeyCode::Char('a'), false)]
+    );
+    assert!(!keyboard.rollover);
+}
+
+#[test]
+fn lock_keys_toggle_on_press() {
+    let mut keyboard = BootKeyboard::default();
+    keys(&mut keyboard, 0, &[0x39]);
+    assert!(keyboard.caps_lock);
+    //holding caps lock does not toggle it again
+    keys(&mut keyboard, 0, &[0x39]);
+    assert!(keyboard.caps_lock);
+    keys(&mut keyboard, 0, &[]);
+    assert_eq!(
+        keys(&mut keyboard, 0, &[0x04]),
+        [(KeyCode::Char('A'), true)]
+    );
+    //shift undoes caps lock for letters only
+    assert_eq!(
+        keys(&mut keyboard, 0x20, &[0x04, 0x1E]),
+        [
+            (KeyCode::Modifier(ModifierKey::RightShift), true),
+            (KeyCode::Char('!'), true)
+        ]
+    );
+    keys(&mut keyboard, 0, &[]);
+    assert_eq!(
+        keys(&mut keyboard, 0x20, &[0x04]),
+        [
+            (KeyCode::Modifier(ModifierKey::RightShift), true),
+            (KeyCode::Char('a'), true)
+        ]
+    );
+
+    keys(&mut keyboard, 0, &[0x53, 0x47]);
+    assert!(keyboard.num_lock && keyboard.scroll_lock);
+    assert_eq!(keyboard.led_report(), 0x07);
+    keys(&mut keyboard, 0, &[]);
+    keys(&mut keyboard, 0, &[0x39]);
+    assert_eq!(keyboard.led_report(), 0x05);
+}
+
+#[test]
+fn short_reports() {
+    let mut keyboard = BootKeyboard::default();
+    assert_eq!(
+        keyboard.handle(&[0; 7]),
+        Err(BootReportError::ShortReport(7))
+    );
+    let mut mouse = BootMouse::default();
+    assert_eq!(mouse.handle(&[1, 2]), Err(BootReportError::ShortReport(2)));
+}
+
+#[test]
+fn mouse_reports() {
+    let mut mouse = BootMouse::default();
+    assert_eq!(
+        mouse.handle(&[0x01, 0x05, 0xFB]),
+        Ok(MouseEvent {
+            buttons: 0x01,
+            pressed: 0x01,
+            released: 0,
+            dx: 5,
+            dy: -5,
+            wheel: 0,
+        })
+    );
+    //the vendor bits above button 5 are dropped, the wheel byte is used if present
+    assert_eq!(
+        mouse.handle(&[0xE2, 0x00, 0x00, 0xFF]),
+        Ok(MouseEvent {
+            buttons: 0x02,
+            pressed: 0x02,
+            released: 0x01,
+            dx: 0,
+            dy: 0,
+            wheel: -1,
+        })
+    );
+    assert_eq!(mouse.buttons, 0x02);
+}
@@ -0,0 +1,211 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_hid::Hid,
+    desc_report::{
+        CollectionType, MainItemFlags, ReportDescriptor, ReportDescriptorError, ReportType,
+    },
+    hid_builder::ReportDescriptorBuilder,
+};
+
+//HID 1.11 appendix B.1
+const BOOT_KEYBOARD: &[u8] = &[
+    0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0x05, 0x07, 0x19, 0xE0, 0x29, 0xE7, 0x15, 0x00, 0x25, 0x01,
+    0x75, 0x01, 0x95, 0x08, 0x81, 0x02, 0x95, 0x01, 0x75, 0x08, 0x81, 0x01, 0x95, 0x05, 0x75, 0x01,
+    0x05, 0x08, 0x19, 0x01, 0x29, 0x05, 0x91, 0x02, 0x95, 0x01, 0x75, 0x03, 0x91, 0x01, 0x95, 0x06,
+    0x75, 0x08, 0x15, 0x00, 0x25, 0x65, 0x05, 0x07, 0x19, 0x00, 0x29, 0x65, 0x81, 0x00, 0xC0,
+];
+
+fn boot_keyboard() -> ReportDescriptorBuilder {
+    ReportDescriptorBuilder::new()
+        .usage_page(0x01)
+        .usage(0x06)
+        .collection(CollectionType::Application)
+        .usage_page(0x07)
+        .usage_range(0xE0, 0xE7)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(8)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .report_count(1)
+        .report_size(8)
+        .input(MainItemFlags::CONSTANT)
+        .report_count(5)
+        .report_size(1)
+        .usage_page(0x08)
+        .usage_range(1, 5)
+        .output(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .report_count(1)
+        .report_size(3)
+        .output(MainItemFlags::CONSTANT)
+        .report_count(6)
+        .report_size(8)
+        .logical_range(0, 0x65)
+        .usage_page(0x07)
+        .usage_range(0, 0x65)
+        .input(MainItemFlags::DATA_ARRAY_ABSOLUTE)
+        .end_collection()
+}
+
+#[test]
+fn boot_keyboard_bytes() {
+    let builder = boot_keyboard();
+    assert_eq!(builder.len(), BOOT_KEYBOARD.len());
+    assert_eq!(builder.build().unwrap(), BOOT_KEYBOARD);
+}
+
+#[test]
+fn boot_keyboard_round_trip() {
+    let descriptor = ReportDescriptor::parse(&boot_keyboard().build().unwrap()).unwrap();
+    let input = descriptor.report(ReportType::Input, None).unwrap();
+    assert_eq!(input.byte_length(), 8);
+    assert_eq!(
+        input
+            .fields
+            .iter()
+            .map(|field| (
+                field.bit_offset,
+                field.bit_length,
+                field.flags.is_constant()
+            ))
+            .collect::<Vec<_>>(),
+        [(0, 8, false), (8, 8, true), (16, 48, false)]
+    );
+    let output = descriptor.report(ReportType::Output, None).unwrap();
+    assert_eq!(output.byte_length(), 1);
+    assert_eq!(output.fields[0].usage(4), Some(0x0008_0005));
+    assert!(!descriptor.uses_report_ids());
+
+    //values that need wider items survive the trip too
+    let raw = ReportDescriptorBuilder::new()
+        .usage_page(0xFF00)
+        .usage(0x01)
+        .collection(CollectionType::Application)
+        .report_id(3)
+        .logical_range(-1000, 70000)
+        .report_size(32)
+        .report_count(1)
+        .usage(0x02)
+        .feature(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .end_collection()
+        .build()
+        .unwrap();
+    let descriptor = ReportDescriptor::parse(&raw).unwrap();
+    let field = &descriptor
+        .report(ReportType::Feature, Some(3))
+        .unwrap()
+        .fields[0];
+    assert_eq!(
+        (
+            field.global_state.logical_minimum,
+            field.global_state.logical_maximum
+        ),
+        (-1000, 70000)
+    );
+    assert_eq!(field.usage(0), Some(0xFF00_0002));
+}
+
+#[test]
+fn nesting_errors() {
+    //errors carry the offset the rejected item would have had
+    assert!(matches!(
+        ReportDescriptorBuilder::new()
+            .usage(0x01)
+            .end_collection()
+            .build(),
+        Err(ReportDescriptorError::UnbalancedEndCollection(2))
+    ));
+    assert!(matches!(
+        ReportDescriptorBuilder::new()
+            .collection(CollectionType::Application)
+            .build(),
+        Err(ReportDescriptorError::UnclosedCollection)
+    ));
+    assert!(matches!(
+        ReportDescriptorBuilder::new().push().pop().pop().build(),
+        Err(ReportDescriptorError::PopWithoutPush(2))
+    ));
+    //the first mistake is the one reported
+    assert!(matches!(
+        ReportDescriptorBuilder::new()
+            .pop()
+            .end_collection()
+            .collection(CollectionType::Physical)
+            .build(),
+        Err(ReportDescriptorError::PopWithoutPush(0))
+    ));
+    assert!(matches!(
+        ReportDescriptorBuilder::new().report_id(0).build(),
+        Err(ReportDescriptorError::ReportIdZero(0))
+    ));
+    assert!(matches!(
+        ReportDescriptorBuilder::new().unit_exponent(8).build(),
+        Err(ReportDescriptorError::ValueOutOfRange(0))
+    ));
+}
+
+#[test]
+fn delimiter_errors() {
+    let alternatives = ReportDescriptorBuilder::new()
+        .open_delimiter()
+        .usage(0x30)
+        .usage(0x31)
+        .close_delimiter();
+    assert_eq!(
+        alternatives.build().unwrap(),
+        [0xA9, 0x01, 0x09, 0x30, 0x09, 0x31, 0xA9, 0x00]
+    );
+    assert!(matches!(
+        ReportDescriptorBuilder::new()
+            .open_delimiter()
+            .open_delimiter()
+            .build(),
+        Err(ReportDescriptorError::NestedDelimiter(2))
+    ));
+    assert!(matches!(
+        ReportDescriptorBuilder::new().close_delimiter().build(),
+        Err(ReportDescriptorError::UnbalancedDelimiter(0))
+    ));
+    //left open at the end
+    assert!(matches!(
+        ReportDescriptorBuilder::new()
+            .open_delimiter()
+            .usage(0x30)
+            .build(),
+        Err(ReportDescriptorError::UnbalancedDelimiter(4))
+    ));
+}
+
+#[test]
+fn build_for_updates_the_hid_descriptor() {
+    let mut hid = Hid::new(0x0111.into(), 0, 0);
+    let raw = boot_keyboard().build_for(&mut hid).unwrap();
+    assert_eq!(hid.report_descriptor_len(), Some(raw.len() as u16));
+    assert_eq!(
+        hid.to_u8_array(),
+        [0x09, 0x21, 0x11, 0x01, 0x00, 0x01, 0x22, 0x3F, 0x00]
+    );
+
+    //a descriptor announcing no report descriptor gets an entry and a longer bLength
+    let mut hid = Hid::from_u8_array(&[0x06, 0x21, 0x11, 0x01, 0x00, 0x00]).unwrap();
+    boot_keyboard().build_for(&mut hid).unwrap();
+    assert_eq!((hid.len, hid.num_descriptions), (9, 1));
+    assert_eq!(hid.report_descriptor_len(), Some(63));
+
+    //other entries are kept, only the report descriptor length changes
+    let mut hid = Hid::from_u8_array(&[
+        0x0C, 0x21, 0x11, 0x01, 0x00, 0x02, 0x23, 0x10, 0x00, 0x22, 0x00, 0x01,
+    ])
+    .unwrap();
+    boot_keyboard().build_for(&mut hid).unwrap();
+    assert_eq!(
+        hid.to_u8_array(),
+        [0x0C, 0x21, 0x11, 0x01, 0x00, 0x02, 0x23, 0x10, 0x00, 0x22, 0x3F, 0x00]
+    );
+
+    //nothing changes when the build fails
+    let mut hid = Hid::new(0x0111.into(), 0, 10);
+    assert!(ReportDescriptorBuilder::new()
+        .collection(CollectionType::Application)
+        .build_for(&mut hid)
+        .is_err());
+    assert_eq!(hid.report_descriptor_len(), Some(10));
+}
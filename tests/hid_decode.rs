@@ -0,0 +1,57 @@
+use usb_descriptor_decoder::descriptors::{desc_report::ReportDescriptor, hid_decode::*};
+
+//HID 1.11 appendix B.2
+const BOOT_MOUSE: &[u8] = &[
+    0x05, 0x01, 0x09, 0x02, 0xA1, 0x01, 0x09, 0x01, 0xA1, 0x00, 0x05, 0x09, 0x19, 0x01, 0x29, 0x03,
+    0x15, 0x00, 0x25, 0x01, 0x95, 0x03, 0x75, 0x01, 0x81, 0x02, 0x95, 0x01, 0x75, 0x05, 0x81, 0x01,
+    0x05, 0x01, 0x09, 0x30, 0x09, 0x31, 0x15, 0x81, 0x25, 0x7F, 0x75, 0x08, 0x95, 0x02, 0x81, 0x06,
+    0xC0, 0xC0,
+];
+
+#[test]
+fn boot_mouse_listing() {
+    let descriptor = ReportDescriptor::parse(BOOT_MOUSE).unwrap();
+    assert_eq!(
+        ReportDescriptorDump::new(&descriptor).to_string(),
+        include_str!("data/boot_mouse.hid-decode")
+    );
+    //the listing alone stops at the last End Collection
+    let items = ReportDescriptorDump::new(&descriptor)
+        .items_only()
+        .to_string();
+    assert_eq!(items.lines().count(), 26);
+    assert!(items.ends_with("// End Collection                           49\n"));
+}
+
+#[test]
+fn units_and_vendor_pages() {
+    //a pressure axis in hundredths of a centimeter and a vendor feature
+    let descriptor = ReportDescriptor::parse(&[
+        0x05, 0x0D, 0x09, 0x04, 0xA1, 0x01, 0x09, 0x30, 0x15, 0x00, 0x26, 0xFF, 0x00, 0x35, 0x00,
+        0x46, 0x10, 0x27, 0x65, 0x11, 0x55, 0x0E, 0x75, 0x08, 0x95, 0x01, 0x81, 0x02, 0x06, 0x00,
+        0xFF, 0x09, 0x01, 0xB1, 0x02, 0xC0,
+    ])
+    .unwrap();
+    let dump = ReportDescriptorDump::new(&descriptor).to_string();
+    for line in [
+        "0x26, 0xff, 0x00,               //  Logical Maximum (255)                   10",
+        "0x46, 0x10, 0x27,               //  Physical Maximum (10000)                15",
+        "0x65, 0x11,                     //  Unit (SILinear: cm)                     18",
+        "0x55, 0x0e,                     //  Unit Exponent (-2)                      20",
+        "0x06, 0x00, 0xff,               //  Usage Page (Vendor Defined Page 0xFF00) 28",
+        "0x09, 0x01,                     //  Usage (Vendor Usage 0x0001)             31",
+        "Feature Report (8 bits, 1 bytes)",
+    ] {
+        assert!(dump.contains(line), "missing {:?} in\n{}", line, dump);
+    }
+
+    let unit = UnitText(0x11);
+    assert_eq!(
+        (unit.system(), unit.exponent(0), unit.exponent(1)),
+        (1, 1, 0)
+    );
+    //cm^-2 and cm/s, exponents are signed nibbles
+    assert_eq!(UnitText(0xE1).exponent(0), -2);
+    assert_eq!(UnitText(0xF011).exponent(2), -1);
+    assert_eq!(UnitText(0).to_string(), "None");
+}
@@ -0,0 +1,84 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_hid::{HIDDescriptorTypes, Hid, HidDescriptorRequest},
+    parser::Error,
+};
+
+//bcdHID 1.11, US, one report descriptor of 0x0141 bytes and two physical descriptor sets
+const MULTI: [u8; 15] = [
+    0x0f, 0x21, 0x11, 0x01, 0x21, 0x03, 0x22, 0x41, 0x01, 0x23, 0x30, 0x00, 0x23, 0x12, 0x00,
+];
+
+#[test]
+fn multi_entry_round_trip() {
+    let hid = Hid::from_u8_array(&MULTI).unwrap();
+    assert_eq!(hid.hid_bcd.raw(), 0x0111);
+    assert_eq!(hid.country_name(), Some("US"));
+    assert_eq!(hid.num_descriptions, 3);
+    assert_eq!(
+        hid.descriptors
+            .iter()
+            .map(|desc| (desc.ty(), desc.length))
+            .collect::<Vec<_>>(),
+        [
+            (Some(HIDDescriptorTypes::HIDReport), 0x0141),
+            (Some(HIDDescriptorTypes::HIDPhysical), 0x0030),
+            (Some(HIDDescriptorTypes::HIDPhysical), 0x0012),
+        ]
+    );
+    assert_eq!(hid.report_descriptor_len(), Some(0x0141));
+    assert_eq!(hid.to_u8_array(), MULTI);
+
+    let requests = hid.descriptor_requests();
+    assert_eq!(
+        requests
+            .iter()
+            .map(|request| request.value())
+            .collect::<Vec<_>>(),
+        [0x2200, 0x2300, 0x2301]
+    );
+    assert_eq!(HidDescriptorRequest::REQUEST_TYPE, 0x81);
+}
+
+#[test]
+fn entries_stop_at_blength() {
+    //bNumDescriptors claims two but bLength only has room for one, the rest is the next descriptor
+    let mut raw = MULTI;
+    raw[0] = 9;
+    raw[5] = 2;
+    let hid = Hid::from_u8_array(&raw).unwrap();
+    assert_eq!(hid.descriptors.len(), 1);
+    assert_eq!(hid.report_descriptor_len(), Some(0x0141));
+}
+
+#[test]
+fn short_descriptors_are_rejected() {
+    assert!(matches!(
+        Hid::from_u8_array(&MULTI[..5]),
+        Err(Error::ShortDescriptor(0x21, 5))
+    ));
+    //bLength larger than the buffer
+    assert!(matches!(
+        Hid::from_u8_array(&MULTI[..9]),
+        Err(Error::ShortDescriptor(0x21, 9))
+    ));
+    let mut raw = MULTI;
+    raw[0] = 4;
+    assert!(matches!(
+        Hid::from_u8_array(&raw),
+        Err(Error::ShortDescriptor(0x21, 15))
+    ));
+}
+
+#[test]
+fn new_announces_one_report_descriptor() {
+    let mut hid = Hid::new(0x0111.into(), 0, 63);
+    assert_eq!(hid.to_u8_array(), [9, 0x21, 0x11, 0x01, 0, 1, 0x22, 63, 0]);
+    hid.set_report_descriptor_len(0x0100);
+    assert_eq!(hid.len, 9);
+    assert_eq!(
+        Hid::from_u8_array(&hid.to_u8_array())
+            .unwrap()
+            .report_descriptor_len(),
+        Some(0x0100)
+    );
+}
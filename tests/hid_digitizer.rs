@@ -0,0 +1,275 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_report::{CollectionType, MainItemFlags, ReportDescriptor, ReportType},
+    hid_builder::ReportDescriptorBuilder,
+    hid_digitizer::{Contact, DeviceMode, DigitizerInterpreter},
+};
+
+//precision touchpad with two Finger collections per input report (id 1), Contact Count
+//Maximum in feature report 2 and the Device Mode in a Device Configuration application (id 3)
+fn touchpad() -> ReportDescriptor {
+    let mut builder = ReportDescriptorBuilder::new()
+        .usage_page(0x0D)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .report_id(1);
+    for _ in 0..2 {
+        builder = builder
+            .usage_page(0x0D)
+            .usage(0x22)
+            .collection(CollectionType::Logical)
+            .usage(0x42)
+            .usage(0x47)
+            .logical_range(0, 1)
+            .report_size(1)
+            .report_count(2)
+            .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+            .padding(ReportType::Input, 6)
+            .usage(0x51)
+            .logical_range(0, 15)
+            .report_size(8)
+            .report_count(1)
+            .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+            .usage_page(0x01)
+            .logical_range(0, 4000)
+            .physical_range(0, 1000)
+            .unit(0x11)
+            .unit_exponent(-2)
+            .report_size(16)
+            .usage(0x30)
+            .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+            .usage(0x31)
+            .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+            .physical_range(0, 0)
+            .unit(0)
+            .unit_exponent(0)
+            .end_collection();
+    }
+    let raw = builder
+        .usage_page(0x0D)
+        .usage(0x56)
+        .logical_range(0, 0xFFFF)
+        .report_size(16)
+        .report_count(1)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .usage(0x54)
+        .logical_range(0, 127)
+        .report_size(8)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .usage_page(0x09)
+        .usage(0x01)
+        .logical_range(0, 1)
+        .report_size(1)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .padding(ReportType::Input, 7)
+        .report_id(2)
+        .usage_page(0x0D)
+        .usage(0x55)
+        .logical_range(0, 15)
+        .report_size(4)
+        .report_count(1)
+        .feature(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .padding(ReportType::Feature, 4)
+        .end_collection()
+        .usage(0x0E)
+        .collection(CollectionType::Application)
+        .report_id(3)
+        .usage(0x23)
+        .collection(CollectionType::Logical)
+        .usage(0x52)
+        .logical_range(0, 10)
+        .report_size(8)
+        .report_count(1)
+        .feature(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .end_collection()
+        .end_collection()
+        .build()
+        .unwrap();
+    ReportDescriptor::parse(&raw).unwrap()
+}
+
+//(tip, contact identifier, x, y) for both slots
+type Finger = (bool, u8, u16, u16);
+
+fn report(fingers: [Finger; 2], scan_time: u16, count: u8, button: bool) -> Vec<u8> {
+    let mut data = vec![0x01];
+    for (tip, id, x, y) in fingers {
+        //tip switch and confidence
+        data.push(tip as u8 | 0x02);
+        data.push(id);
+        data.extend_from_slice(&x.to_le_bytes());
+        data.extend_from_slice(&y.to_le_bytes());
+    }
+    data.extend_from_slice(&scan_time.to_le_bytes());
+    data.push(count);
+    data.push(button as u8);
+    data
+}
+
+const NONE: Finger = (false, 0, 0, 0);
+
+fn ids(contacts: &[Contact]) -> Vec<(u32, u32, bool)> {
+    contacts
+        .iter()
+        .map(|contact| (contact.contact_identifier, contact.tracking_id, contact.tip))
+        .collect()
+}
+
+#[test]
+fn slots_and_feature_reports() {
+    let descriptor = touchpad();
+    let interpreter = DigitizerInterpreter::from_descriptor(&descriptor).unwrap();
+    assert_eq!(interpreter.application, 0x000D_0005);
+    assert_eq!(interpreter.slots.len(), 2);
+    let slot = &interpreter.slots[1];
+    assert!(slot.tip_switch.is_some() && slot.confidence.is_some());
+    assert!(slot.x.is_some() && slot.y.is_some() && slot.contact_identifier.is_some());
+    assert!(slot.pressure.is_none() && slot.width.is_none());
+
+    assert_eq!(
+        interpreter.contact_count_maximum(&descriptor, &[0x02, 0x05]),
+        Some(5)
+    );
+    //the padding nibble is ignored
+    assert_eq!(
+        interpreter.contact_count_maximum(&descriptor, &[0x02, 0xF3]),
+        Some(3)
+    );
+    assert_eq!(
+        interpreter.contact_count_maximum(&descriptor, &[0x07, 0x05]),
+        None
+    );
+    assert_eq!(
+        interpreter.device_mode(&descriptor, &[0x03, 0x02]),
+        Some(DeviceMode::MultiInput)
+    );
+    assert_eq!(interpreter.device_mode(&descriptor, &[0x03, 0x09]), None);
+    assert_eq!(
+        interpreter
+            .device_mode_request(&descriptor, DeviceMode::MultiInput)
+            .unwrap(),
+        [0x03, 0x02]
+    );
+    assert_eq!(
+        interpreter
+            .device_mode_request(&descriptor, DeviceMode::Mouse)
+            .unwrap(),
+        [0x03, 0x00]
+    );
+}
+
+#[test]
+fn parallel_mode() {
+    let descriptor = touchpad();
+    let mut interpreter = DigitizerInterpreter::from_descriptor(&descriptor).unwrap();
+
+    let frame = interpreter
+        .handle(
+            &descriptor,
+            &report([(true, 3, 4000, 0), (true, 7, 2000, 400)], 100, 2, true),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame.scan_time, Some(100));
+    assert_eq!(frame.buttons, 1);
+    assert_eq!(ids(&frame.contacts), [(3, 1, true), (7, 2, true)]);
+    let contact = frame.contacts[0];
+    assert_eq!((contact.x, contact.y), (4000, 0));
+    assert!(contact.confident);
+    //4000 logical is 1000 physical, exponent -2
+    assert!((contact.physical_x - 10.0).abs() < 1e-9);
+    assert!((frame.contacts[1].physical_y - 1.0).abs() < 1e-9);
+
+    //only the first slot is valid when the count is 1
+    let frame = interpreter
+        .handle(
+            &descriptor,
+            &report([(true, 7, 2010, 410), (true, 9, 1, 1)], 200, 1, false),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(ids(&frame.contacts), [(7, 2, true)]);
+    assert_eq!(frame.buttons, 0);
+}
+
+#[test]
+fn hybrid_mode() {
+    let descriptor = touchpad();
+    let mut interpreter = DigitizerInterpreter::from_descriptor(&descriptor).unwrap();
+
+    //three contacts over two reports, the second carries a contact count of 0
+    assert_eq!(
+        interpreter
+            .handle(
+                &descriptor,
+                &report([(true, 0, 10, 10), (true, 1, 20, 20)], 500, 3, false),
+            )
+            .unwrap(),
+        None
+    );
+    let frame = interpreter
+        .handle(
+            &descriptor,
+            &report([(true, 2, 30, 30), (true, 15, 99, 99)], 500, 0, false),
+        )
+        .unwrap()
+        .unwrap();
+    assert_eq!(frame.scan_time, Some(500));
+    assert_eq!(
+        frame
+            .contacts
+            .iter()
+            .map(|contact| (contact.contact_identifier, contact.x))
+            .collect::<Vec<_>>(),
+        [(0, 10), (1, 20), (2, 30)]
+    );
+
+    //every finger lifted: a zero count with no frame in progress is an empty frame
+    let frame = interpreter
+        .handle(&descriptor, &report([NONE, NONE], 600, 0, true))
+        .unwrap()
+        .unwrap();
+    assert!(frame.contacts.is_empty());
+    assert_eq!((frame.scan_time, frame.buttons), (Some(600), 1));
+    //and the button release that follows
+    let frame = interpreter
+        .handle(&descriptor, &report([NONE, NONE], 700, 0, false))
+        .unwrap()
+        .unwrap();
+    assert_eq!((frame.scan_time, frame.buttons), (Some(700), 0));
+}
+
+#[test]
+fn tracking_ids_follow_contacts() {
+    let descriptor = touchpad();
+    let mut interpreter = DigitizerInterpreter::from_descriptor(&descriptor).unwrap();
+    let mut frame = |fingers, count| {
+        interpreter
+            .handle(&descriptor, &report(fingers, 0, count, false))
+            .unwrap()
+            .unwrap()
+            .contacts
+    };
+
+    assert_eq!(
+        ids(&frame([(true, 0, 1, 1), (true, 1, 2, 2)], 2)),
+        [(0, 1, true), (1, 2, true)]
+    );
+    //same fingers in the other slots keep their tracking ids
+    assert_eq!(
+        ids(&frame([(true, 1, 2, 2), (true, 0, 1, 1)], 2)),
+        [(1, 2, true), (0, 1, true)]
+    );
+    //contact 0 lifts, the lift is reported with its tracking id
+    assert_eq!(
+        ids(&frame([(false, 0, 1, 1), (true, 1, 2, 2)], 2)),
+        [(0, 1, false), (1, 2, true)]
+    );
+    //the device reuses identifier 0 for a new finger, it gets a new tracking id
+    assert_eq!(
+        ids(&frame([(true, 1, 2, 2), (true, 0, 5, 5)], 2)),
+        [(1, 2, true), (0, 3, true)]
+    );
+    //contact 1 drops out of the frame without a lift and does not keep its id either
+    assert_eq!(ids(&frame([(true, 0, 5, 5), NONE], 1)), [(0, 3, true)]);
+    assert_eq!(ids(&frame([(true, 1, 2, 2), NONE], 1)), [(1, 4, true)]);
+}
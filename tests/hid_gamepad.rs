@@ -0,0 +1,258 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_report::{CollectionType, MainItemFlags, ReportDescriptor, ReportType},
+    hid_builder::ReportDescriptorBuilder,
+    hid_gamepad::{find_override, Axis, AxisInfo, Button, Control, Gamepad},
+};
+
+//report 1 of a DualShock 4 (054c:05c4) descriptor: sticks on X/Y/Z/Rz, an 8 way hat sharing a
+//byte with the face buttons, 14 buttons, a vendor counter and the triggers on Rx/Ry
+const DUALSHOCK4: &[u8] = &[
+    0x05, 0x01, 0x09, 0x05, 0xA1, 0x01, 0x85, 0x01, 0x09, 0x30, 0x09, 0x31, 0x09, 0x32, 0x09, 0x35,
+    0x15, 0x00, 0x26, 0xFF, 0x00, 0x75, 0x08, 0x95, 0x04, 0x81, 0x02, 0x09, 0x39, 0x15, 0x00, 0x25,
+    0x07, 0x35, 0x00, 0x46, 0x3B, 0x01, 0x65, 0x14, 0x75, 0x04, 0x95, 0x01, 0x81, 0x42, 0x65, 0x00,
+    0x05, 0x09, 0x19, 0x01, 0x29, 0x0E, 0x15, 0x00, 0x25, 0x01, 0x75, 0x01, 0x95, 0x0E, 0x81, 0x02,
+    0x06, 0x00, 0xFF, 0x09, 0x20, 0x75, 0x06, 0x95, 0x01, 0x15, 0x00, 0x25, 0x7F, 0x81, 0x02, 0x05,
+    0x01, 0x09, 0x33, 0x09, 0x34, 0x15, 0x00, 0x26, 0xFF, 0x00, 0x75, 0x08, 0x95, 0x02, 0x81, 0x02,
+    0xC0,
+];
+
+//12 buttons, a hat with `hat_positions` directions and 8 bit axes with the given usages
+fn generic_pad(hat_positions: i32, axes: &[u16]) -> ReportDescriptor {
+    let mut builder = ReportDescriptorBuilder::new()
+        .usage_page(0x01)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .usage_page(0x09)
+        .usage_range(1, 12)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(12)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .padding(ReportType::Input, 4)
+        .usage_page(0x01)
+        .usage(0x39)
+        .logical_range(0, hat_positions - 1)
+        .report_size(4)
+        .report_count(1)
+        .input(MainItemFlags(0x42))
+        .padding(ReportType::Input, 4)
+        .logical_range(0, 255)
+        .report_size(8)
+        .report_count(axes.len() as u32);
+    for usage in axes {
+        builder = builder.usage(*usage);
+    }
+    let raw = builder
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .end_collection()
+        .build()
+        .unwrap();
+    ReportDescriptor::parse(&raw).unwrap()
+}
+
+fn dpad(gamepad: &mut Gamepad, descriptor: &ReportDescriptor, hat: u8) -> [bool; 4] {
+    let state = gamepad
+        .update(descriptor, &[0, 0, hat, 128, 128, 128, 128])
+        .unwrap();
+    [
+        state.button(Button::DpadUp),
+        state.button(Button::DpadRight),
+        state.button(Button::DpadDown),
+        state.button(Button::DpadLeft),
+    ]
+}
+
+//(usage, axis) for every axis control, in report order
+fn axes(gamepad: &Gamepad, usages: &[u16]) -> Vec<(u16, Axis)> {
+    gamepad
+        .controls
+        .iter()
+        .filter_map(|control| match control.control {
+            Control::Axis(axis) => Some((usages[control.element], axis)),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn eight_way_hat() {
+    let descriptor = generic_pad(8, &[0x30, 0x31, 0x32, 0x35]);
+    let mut gamepad = Gamepad::from_descriptor(&descriptor, 0, 0).unwrap();
+    assert_eq!(gamepad.application, 0x0001_0005);
+    assert_eq!(gamepad.override_name, None);
+    //clockwise from north, diagonals press two directions
+    let expected = [
+        [true, false, false, false],
+        [true, true, false, false],
+        [false, true, false, false],
+        [false, true, true, false],
+        [false, false, true, false],
+        [false, false, true, true],
+        [false, false, false, true],
+        [true, false, false, true],
+    ];
+    for (hat, expected) in expected.iter().enumerate() {
+        assert_eq!(dpad(&mut gamepad, &descriptor, hat as u8), *expected);
+    }
+    //outside the logical range is the null state
+    assert_eq!(dpad(&mut gamepad, &descriptor, 0x0F), [false; 4]);
+}
+
+#[test]
+fn four_way_hat() {
+    let descriptor = generic_pad(4, &[0x30, 0x31, 0x32, 0x35]);
+    let mut gamepad = Gamepad::from_descriptor(&descriptor, 0, 0).unwrap();
+    assert_eq!(
+        dpad(&mut gamepad, &descriptor, 0),
+        [true, false, false, false]
+    );
+    assert_eq!(
+        dpad(&mut gamepad, &descriptor, 1),
+        [false, true, false, false]
+    );
+    assert_eq!(
+        dpad(&mut gamepad, &descriptor, 2),
+        [false, false, true, false]
+    );
+    assert_eq!(
+        dpad(&mut gamepad, &descriptor, 3),
+        [false, false, false, true]
+    );
+    assert_eq!(dpad(&mut gamepad, &descriptor, 4), [false; 4]);
+}
+
+#[test]
+fn right_stick_on_z_rz_when_rz_exists() {
+    let usages = [0x30, 0x31, 0x32, 0x35, 0x33, 0x34];
+    let gamepad = Gamepad::from_descriptor(&generic_pad(8, &usages), 0, 0).unwrap();
+    assert_eq!(
+        axes(&gamepad, &usages),
+        [
+            (0x30, Axis::LeftX),
+            (0x31, Axis::LeftY),
+            (0x32, Axis::RightX),
+            (0x35, Axis::RightY),
+            (0x33, Axis::LeftTrigger),
+            (0x34, Axis::RightTrigger),
+        ]
+    );
+}
+
+#[test]
+fn right_stick_on_rx_ry_without_rz() {
+    let usages = [0x30, 0x31, 0x33, 0x34, 0x32];
+    let gamepad = Gamepad::from_descriptor(&generic_pad(8, &usages), 0, 0).unwrap();
+    assert_eq!(
+        axes(&gamepad, &usages),
+        [
+            (0x30, Axis::LeftX),
+            (0x31, Axis::LeftY),
+            (0x33, Axis::RightX),
+            (0x34, Axis::RightY),
+            (0x32, Axis::LeftTrigger),
+        ]
+    );
+    //sticks get the default dead zone, triggers none
+    let dead_zones: Vec<_> = gamepad
+        .controls
+        .iter()
+        .filter_map(|control| control.axis_info.map(|info| info.dead_zone))
+        .collect();
+    assert_eq!(dead_zones, [0.0625, 0.0625, 0.0625, 0.0625, 0.0]);
+}
+
+#[test]
+fn normalize_and_dead_zone() {
+    let info = AxisInfo {
+        logical_minimum: 0,
+        logical_maximum: 255,
+        dead_zone: 0.1,
+    };
+    assert_eq!(info.normalize(Axis::LeftX, 0), -1.0);
+    assert_eq!(info.normalize(Axis::LeftX, 255), 1.0);
+    assert!((info.normalize(Axis::LeftX, 128) - 1.0 / 255.0).abs() < 1e-6);
+    assert_eq!(info.normalize(Axis::LeftTrigger, 0), 0.0);
+    assert_eq!(info.normalize(Axis::RightTrigger, 255), 1.0);
+    //out of range values clamp
+    assert_eq!(info.normalize(Axis::LeftY, 300), 1.0);
+    assert_eq!(info.normalize(Axis::LeftY, -5), -1.0);
+
+    let signed = AxisInfo {
+        logical_minimum: -127,
+        logical_maximum: 127,
+        dead_zone: 0.0,
+    };
+    assert_eq!(signed.normalize(Axis::RightX, 0), 0.0);
+    assert_eq!(signed.normalize(Axis::RightX, -127), -1.0);
+
+    assert_eq!(info.apply_dead_zone(0.05), 0.0);
+    assert_eq!(info.apply_dead_zone(-0.1), 0.0);
+    assert!((info.apply_dead_zone(0.55) - 0.5).abs() < 1e-6);
+    assert!((info.apply_dead_zone(-0.55) + 0.5).abs() < 1e-6);
+    assert_eq!(info.apply_dead_zone(1.0), 1.0);
+    assert_eq!(info.apply_dead_zone(-1.0), -1.0);
+    assert_eq!(signed.apply_dead_zone(0.01), 0.01);
+    let everything = AxisInfo {
+        dead_zone: 1.0,
+        ..info
+    };
+    assert_eq!(everything.apply_dead_zone(1.0), 0.0);
+}
+
+#[test]
+fn dualshock4_override() {
+    let descriptor = ReportDescriptor::parse(DUALSHOCK4).unwrap();
+    let entry = find_override(0x054c, 0x05c4).unwrap();
+    assert_eq!(entry.name, "Sony DualShock 4");
+    assert!(find_override(0x054c, 0x0000).is_none());
+
+    let mut gamepad = Gamepad::from_descriptor(&descriptor, 0x054c, 0x05c4).unwrap();
+    assert_eq!(gamepad.override_name, Some("Sony DualShock 4"));
+
+    //left stick full left and down, hat east, cross, options and ps pressed, counter 5,
+    //l2 fully pressed
+    let report = [0x01, 0, 255, 128, 128, 0x22, 0x20, 0x15, 255, 0];
+    let state = *gamepad.update(&descriptor, &report).unwrap();
+    let pressed: Vec<_> = [
+        Button::South,
+        Button::East,
+        Button::West,
+        Button::North,
+        Button::Select,
+        Button::Start,
+        Button::Mode,
+        Button::DpadUp,
+        Button::DpadRight,
+    ]
+    .into_iter()
+    .filter(|button| state.button(*button))
+    .collect();
+    assert_eq!(
+        pressed,
+        [
+            Button::South,
+            Button::Start,
+            Button::Mode,
+            Button::DpadRight
+        ]
+    );
+    assert_eq!(state.axis(Axis::LeftX), -1.0);
+    assert_eq!(state.axis(Axis::LeftY), 1.0);
+    assert!(state.axis(Axis::RightX).abs() < 0.01);
+    assert_eq!(state.axis(Axis::LeftTrigger), 1.0);
+    assert_eq!(state.axis(Axis::RightTrigger), 0.0);
+
+    //hat neutral (8) and everything released
+    let state = *gamepad
+        .update(&descriptor, &[0x01, 128, 128, 128, 128, 0x08, 0, 0, 0, 0])
+        .unwrap();
+    assert!(!state.buttons.iter().any(|pressed| *pressed));
+
+    //without the override button 1 (square) would be south
+    let mut generic = Gamepad::from_descriptor(&descriptor, 0, 0).unwrap();
+    let state = generic
+        .update(&descriptor, &[0x01, 128, 128, 128, 128, 0x18, 0, 0, 0, 0])
+        .unwrap();
+    assert!(state.button(Button::South));
+    assert!(!state.button(Button::East));
+}
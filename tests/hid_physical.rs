@@ -0,0 +1,162 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_physical::{
+        Bias, Designator, PhysicalDescriptorSet, PhysicalDescriptorSet0, PhysicalDescriptors,
+        Qualifier,
+    },
+    desc_report::{CollectionType, MainItemFlags, ReportDescriptor, ReportType},
+    hid_builder::ReportDescriptorBuilder,
+    parser::Error,
+};
+
+const SET0: [u8; 3] = [2, 7, 0];
+//right hand, most preferred: thumb (right, effort 31), index finger (right, effort 16), middle finger
+const RIGHT: [u8; 7] = [0x20, 0x11, 0x3F, 0x12, 0x30, 0x13, 0x05];
+//left hand, preference 1
+const LEFT: [u8; 7] = [0x41, 0x11, 0x5F, 0x12, 0x50, 0x13, 0x45];
+
+fn physical() -> PhysicalDescriptors {
+    PhysicalDescriptors::new(&SET0, &[&RIGHT, &LEFT]).unwrap()
+}
+
+#[test]
+fn set_decoding() {
+    let descriptors = physical();
+    assert_eq!((descriptors.set0.number, descriptors.set0.length), (2, 7));
+    assert_eq!(PhysicalDescriptorSet0::request().value(), 0x2300);
+    assert_eq!(PhysicalDescriptorSet0::request().length, 3);
+    assert_eq!(
+        descriptors
+            .set0
+            .set_requests()
+            .map(|request| (request.value(), request.length))
+            .collect::<Vec<_>>(),
+        [(0x2301, 7), (0x2302, 7)]
+    );
+
+    let right = &descriptors.sets[0];
+    assert_eq!(right.descriptors.len(), 3);
+    assert_eq!(
+        right
+            .descriptors
+            .iter()
+            .map(|desc| desc.designator())
+            .collect::<Vec<_>>(),
+        [
+            Some(Designator::Thumb),
+            Some(Designator::IndexFinger),
+            Some(Designator::MiddleFinger)
+        ]
+    );
+}
+
+#[test]
+fn bias_qualifier_and_effort_bits() {
+    let descriptors = physical();
+    let (right, left) = (&descriptors.sets[0], &descriptors.sets[1]);
+    assert_eq!(
+        (right.bias(), right.preference()),
+        (Some(Bias::RightHand), 0)
+    );
+    assert_eq!((left.bias(), left.preference()), (Some(Bias::LeftHand), 1));
+
+    let flags = |set: &PhysicalDescriptorSet| {
+        set.descriptors
+            .iter()
+            .map(|desc| (desc.qualifier(), desc.effort()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(
+        flags(right),
+        [
+            (Some(Qualifier::Right), 31),
+            (Some(Qualifier::Right), 16),
+            (Some(Qualifier::None), 5)
+        ]
+    );
+    assert_eq!(
+        flags(left),
+        [
+            (Some(Qualifier::Left), 31),
+            (Some(Qualifier::Left), 16),
+            (Some(Qualifier::Left), 5)
+        ]
+    );
+    //qualifier values above Center are reserved
+    let reserved = PhysicalDescriptorSet::from_u8_array(&[0xE0, 0x01, 0xE0]).unwrap();
+    assert_eq!(reserved.bias(), None);
+    assert_eq!(reserved.descriptors[0].qualifier(), None);
+}
+
+#[test]
+fn designator_ranges_are_spread_over_elements() {
+    let raw = ReportDescriptorBuilder::new()
+        .usage_page(0x01)
+        .usage(0x05)
+        .collection(CollectionType::Application)
+        .usage_page(0x09)
+        .usage_range(1, 5)
+        .designator_range(0, 2)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(5)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .padding(ReportType::Input, 3)
+        .usage(6)
+        .designator_index(1)
+        .report_count(2)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .padding(ReportType::Input, 6)
+        .end_collection()
+        .build()
+        .unwrap();
+    let descriptor = ReportDescriptor::parse(&raw).unwrap();
+    let report = descriptor.report(ReportType::Input, None).unwrap();
+    let (buttons, pinch, padding) = (&report.fields[0], &report.fields[2], &report.fields[1]);
+
+    //the last designator repeats past the maximum
+    assert_eq!(
+        (0..5)
+            .map(|element| buttons.designator_index(element))
+            .collect::<Vec<_>>(),
+        [Some(0), Some(1), Some(2), Some(2), Some(2)]
+    );
+    assert_eq!(pinch.designator_index(0), Some(1));
+    assert_eq!(pinch.designator_index(1), Some(1));
+    assert_eq!(padding.designator_index(0), None);
+
+    let descriptors = physical();
+    let designator = |element, bias| {
+        descriptors
+            .for_field(buttons, element, bias)
+            .and_then(|desc| desc.designator())
+    };
+    assert_eq!(designator(0, None), Some(Designator::Thumb));
+    assert_eq!(designator(4, None), Some(Designator::MiddleFinger));
+    assert_eq!(
+        descriptors
+            .for_field(pinch, 0, Some(Bias::LeftHand))
+            .map(|desc| desc.flags),
+        Some(0x50)
+    );
+    assert!(descriptors.designator(3, None).is_none());
+    assert!(descriptors.preferred_set(Some(Bias::BothHands)).is_none());
+}
+
+#[test]
+fn short_sets_are_rejected() {
+    assert!(matches!(
+        PhysicalDescriptorSet0::from_u8_array(&SET0[..2]),
+        Err(Error::ShortDescriptor(0x23, 2))
+    ));
+    assert!(matches!(
+        PhysicalDescriptorSet::from_u8_array(&[]),
+        Err(Error::ShortDescriptor(0x23, 0))
+    ));
+    assert!(matches!(
+        PhysicalDescriptors::new(&SET0, &[&RIGHT, &[]]),
+        Err(Error::ShortDescriptor(0x23, 0))
+    ));
+    //a trailing odd byte is not an entry
+    let set = PhysicalDescriptorSet::from_u8_array(&RIGHT[..6]).unwrap();
+    assert_eq!(set.descriptors.len(), 2);
+}
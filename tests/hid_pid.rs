@@ -0,0 +1,360 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_report::{
+        CollectionType, MainItemFlags, ReportDescriptor, ReportDescriptorError, ReportType,
+    },
+    hid_builder::ReportDescriptorBuilder,
+    hid_pid::{
+        BlockLoad, BlockLoadStatus, Condition, ConditionType, Effect, EffectKind, EffectOperation,
+        PidDevice, PidState, Waveform,
+    },
+    hid_usage::PhysicalInputDevice as Pid,
+};
+
+const OUTPUT: MainItemFlags = MainItemFlags::DATA_VARIABLE_ABSOLUTE;
+
+//force feedback wheel: steering input, PID state (input 2), Set Effect (output 1), Set
+//Constant Force (output 5), Set Condition (output 3), Effect Operation (output 10), Create New
+//Effect (feature 6) and PID Block Load (feature 7)
+fn wheel() -> ReportDescriptor {
+    let effect_types = |builder: ReportDescriptorBuilder| {
+        builder
+            .usage(0x25)
+            .collection(CollectionType::Logical)
+            .usage(0x26)
+            .usage(0x31)
+            .usage(0x40)
+            .logical_range(1, 3)
+            .report_size(8)
+            .report_count(1)
+    };
+    let block_index = |builder: ReportDescriptorBuilder| {
+        builder
+            .usage(0x22)
+            .logical_range(1, 40)
+            .report_size(8)
+            .report_count(1)
+    };
+
+    let builder = ReportDescriptorBuilder::new()
+        .usage_page(0x01)
+        .usage(0x04)
+        .collection(CollectionType::Application)
+        .report_id(1)
+        .usage(0x30)
+        .logical_range(0, 1023)
+        .report_size(16)
+        .report_count(1)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        //PID State
+        .usage_page(0x0F)
+        .usage(0x92)
+        .collection(CollectionType::Logical)
+        .report_id(2);
+    let builder = block_index(builder)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .usage(0x9F)
+        .usage(0xA0)
+        .usage(0xA4)
+        .usage(0xA5)
+        .usage(0xA6)
+        .usage(0x94)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(6)
+        .input(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .padding(ReportType::Input, 2)
+        .end_collection()
+        //Set Effect
+        .usage(0x21)
+        .collection(CollectionType::Logical)
+        .report_id(1);
+    let builder = effect_types(block_index(builder).output(OUTPUT))
+        .output(MainItemFlags::DATA_ARRAY_ABSOLUTE)
+        .end_collection()
+        .usage(0x50)
+        .usage(0x54)
+        .usage(0x51)
+        .usage(0xA7)
+        .logical_range(0, 32767)
+        .physical_range(0, 32767)
+        .unit(0x1003)
+        .unit_exponent(-3)
+        .report_size(16)
+        .report_count(4)
+        .output(OUTPUT)
+        .physical_range(0, 0)
+        .unit(0)
+        .unit_exponent(0)
+        .usage(0x52)
+        .logical_range(0, 255)
+        .report_size(8)
+        .report_count(1)
+        .output(OUTPUT)
+        .usage(0x53)
+        .logical_range(1, 8)
+        .output(OUTPUT)
+        .usage(0x55)
+        .collection(CollectionType::Logical)
+        .usage_page(0x01)
+        .usage(0x30)
+        .logical_range(0, 1)
+        .report_size(1)
+        .report_count(1)
+        .output(OUTPUT)
+        .end_collection()
+        .usage_page(0x0F)
+        .usage(0x56)
+        .output(OUTPUT)
+        .padding(ReportType::Output, 6)
+        .usage(0x57)
+        .collection(CollectionType::Logical)
+        .usage_page(0x0A)
+        .usage(0x01)
+        .logical_range(0, 255)
+        .report_size(8)
+        .report_count(1)
+        .output(OUTPUT)
+        .end_collection()
+        .usage_page(0x0F)
+        .end_collection()
+        //Set Constant Force
+        .usage(0x73)
+        .collection(CollectionType::Logical)
+        .report_id(5);
+    let builder = block_index(builder)
+        .output(OUTPUT)
+        .usage(0x70)
+        .logical_range(-10000, 10000)
+        .report_size(16)
+        .output(OUTPUT)
+        .end_collection()
+        //Set Condition
+        .usage(0x5F)
+        .collection(CollectionType::Logical)
+        .report_id(3);
+    let builder = block_index(builder)
+        .output(OUTPUT)
+        .usage(0x23)
+        .logical_range(0, 1)
+        .output(OUTPUT)
+        .usage(0x60)
+        .usage(0x61)
+        .usage(0x62)
+        .logical_range(-128, 127)
+        .report_count(3)
+        .output(OUTPUT)
+        .usage(0x63)
+        .usage(0x64)
+        .usage(0x65)
+        .logical_range(0, 255)
+        .output(OUTPUT)
+        .end_collection()
+        //Effect Operation
+        .usage(0x77)
+        .collection(CollectionType::Logical)
+        .report_id(10);
+    let builder = block_index(builder)
+        .output(OUTPUT)
+        .usage(0x78)
+        .collection(CollectionType::Logical)
+        .usage(0x79)
+        .usage(0x7A)
+        .usage(0x7B)
+        .logical_range(1, 3)
+        .output(MainItemFlags::DATA_ARRAY_ABSOLUTE)
+        .end_collection()
+        .usage(0x7C)
+        .logical_range(0, 255)
+        .output(OUTPUT)
+        .end_collection()
+        //Create New Effect
+        .usage(0xAB)
+        .collection(CollectionType::Logical)
+        .report_id(6);
+    let builder = effect_types(builder)
+        .feature(MainItemFlags::DATA_ARRAY_ABSOLUTE)
+        .end_collection()
+        .end_collection()
+        //PID Block Load
+        .usage(0x89)
+        .collection(CollectionType::Logical)
+        .report_id(7);
+    let raw = block_index(builder)
+        .feature(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .usage(0x8B)
+        .collection(CollectionType::Logical)
+        .usage(0x8C)
+        .usage(0x8D)
+        .usage(0x8E)
+        .logical_range(1, 3)
+        .feature(MainItemFlags::DATA_ARRAY_ABSOLUTE)
+        .end_collection()
+        .usage(0xAC)
+        .logical_range(0, 0xFFFF)
+        .report_size(16)
+        .feature(MainItemFlags::DATA_VARIABLE_ABSOLUTE)
+        .end_collection()
+        .end_collection()
+        .build()
+        .unwrap();
+    ReportDescriptor::parse(&raw).unwrap()
+}
+
+#[test]
+fn constant_force_until_stopped() {
+    let descriptor = wheel();
+    let device = PidDevice::from_descriptor(&descriptor).unwrap();
+    assert!(device.supports(&descriptor, Pid::EtConstantForce));
+    assert!(device.supports(&descriptor, Pid::EtSpring));
+    assert!(!device.supports(&descriptor, Pid::EtSquare));
+
+    let effect = Effect {
+        direction: 9000,
+        ..Effect::new(EffectKind::Constant { magnitude: 5000 })
+    };
+    let reports = device.encode_effect(&descriptor, 1, &effect).unwrap();
+    assert_eq!(
+        reports,
+        [
+            vec![0x05, 0x01, 0x88, 0x13],
+            //block 1, ET Constant Force, duration and trigger button all ones past their
+            //logical maximum, full gain, X axis and direction enabled, 90 degrees
+            vec![
+                0x01, 0x01, 0x01, 0xFF, 0xFF, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0x03,
+                0x40,
+            ],
+        ]
+    );
+}
+
+#[test]
+fn timed_effect_with_trigger() {
+    let descriptor = wheel();
+    let device = PidDevice::from_descriptor(&descriptor).unwrap();
+    let effect = Effect {
+        duration_ms: Some(1500),
+        trigger_button: Some(2),
+        start_delay_ms: 250,
+        gain: 5000,
+        ..Effect::new(EffectKind::Constant { magnitude: -10000 })
+    };
+    let reports = device.encode_effect(&descriptor, 3, &effect).unwrap();
+    assert_eq!(reports[0], [0x05, 0x03, 0xF0, 0xD8]);
+    assert_eq!(
+        reports[1],
+        [
+            0x01, 0x03, 0x01, 0xDC, 0x05, 0x00, 0x00, 0x00, 0x00, 0xFA, 0x00, 0x7F, 0x02, 0x03,
+            0x00
+        ]
+    );
+}
+
+#[test]
+fn condition_per_axis() {
+    let descriptor = wheel();
+    let device = PidDevice::from_descriptor(&descriptor).unwrap();
+    let spring = Condition {
+        center_point_offset: 5000,
+        positive_coefficient: 10000,
+        negative_coefficient: -10000,
+        positive_saturation: 10000,
+        negative_saturation: 5000,
+        dead_band: 0,
+    };
+    let effect = Effect::new(EffectKind::Condition {
+        condition_type: ConditionType::Spring,
+        axes: vec![spring, Condition::default()],
+    });
+    let reports = device.encode_effect(&descriptor, 2, &effect).unwrap();
+    assert_eq!(reports.len(), 3);
+    assert_eq!(
+        reports[0],
+        [0x03, 0x02, 0x00, 0x3F, 0x7F, 0x81, 0xFF, 0x7F, 0x00]
+    );
+    assert_eq!(
+        reports[1],
+        [0x03, 0x02, 0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]
+    );
+    //ET Spring is the third effect type
+    assert_eq!(reports[2][2], 0x03);
+
+    //the wheel has no Set Periodic report
+    let sine = Effect::new(EffectKind::Periodic {
+        waveform: Waveform::Sine,
+        magnitude: 10000,
+        offset: 0,
+        phase: 0,
+        period_ms: 100,
+    });
+    assert!(matches!(
+        device.encode_effect(&descriptor, 1, &sine),
+        Err(ReportDescriptorError::UndeclaredUsage(0x0F, 0x6E))
+    ));
+}
+
+#[test]
+fn block_allocation_and_operations() {
+    let descriptor = wheel();
+    let device = PidDevice::from_descriptor(&descriptor).unwrap();
+    assert_eq!(
+        device
+            .create_new_effect(&descriptor, &EffectKind::Constant { magnitude: 0 })
+            .unwrap(),
+        [0x06, 0x01]
+    );
+    assert_eq!(
+        device.block_load(&descriptor, &[0x07, 0x02, 0x01, 0x00, 0x10]),
+        Some(BlockLoad {
+            effect_block_index: 2,
+            status: BlockLoadStatus::Success,
+            ram_pool_available: Some(0x1000),
+        })
+    );
+    assert_eq!(
+        device
+            .block_load(&descriptor, &[0x07, 0x00, 0x02, 0x00, 0x00])
+            .map(|load| load.status),
+        Some(BlockLoadStatus::Full)
+    );
+    //no status selected, and a report that is not the block load report
+    assert_eq!(
+        device.block_load(&descriptor, &[0x07, 0x02, 0x00, 0x00, 0x00]),
+        None
+    );
+    assert_eq!(device.block_load(&descriptor, &[0x06, 0x01]), None);
+
+    assert_eq!(
+        device
+            .effect_operation(&descriptor, 2, EffectOperation::StartSolo, 1)
+            .unwrap(),
+        [0x0A, 0x02, 0x02, 0x01]
+    );
+    assert_eq!(
+        device
+            .effect_operation(&descriptor, 2, EffectOperation::Stop, 0)
+            .unwrap(),
+        [0x0A, 0x02, 0x03, 0x00]
+    );
+    //neither a block free nor a device control report is declared
+    assert!(device.block_free(&descriptor, 2).is_err());
+}
+
+#[test]
+fn state() {
+    let descriptor = wheel();
+    let device = PidDevice::from_descriptor(&descriptor).unwrap();
+    assert_eq!(
+        device.state(&descriptor, &[0x02, 0x03, 0b0010_0110]),
+        Some(PidState {
+            effect_block_index: Some(3),
+            effect_playing: true,
+            device_paused: false,
+            actuators_enabled: true,
+            safety_switch: true,
+            actuator_override_switch: false,
+            actuator_power: false,
+        })
+    );
+    //the steering report is not the PID state
+    assert_eq!(device.state(&descriptor, &[0x01, 0x00, 0x02]), None);
+}
@@ -0,0 +1,382 @@
+use usb_descriptor_decoder::descriptors::desc_report::{
+    CollectionType, Item, ItemTag, MainItemTag, ReportDescriptor, ReportDescriptorError,
+    ReportEvent, ReportHandler, ReportItem, ReportType,
+};
+
+//HID 1.11 appendix B.1
+const BOOT_KEYBOARD: &[u8] = &[
+    0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0x05, 0x07, 0x19, 0xE0, 0x29, 0xE7, 0x15, 0x00, 0x25, 0x01,
+    0x75, 0x01, 0x95, 0x08, 0x81, 0x02, 0x95, 0x01, 0x75, 0x08, 0x81, 0x01, 0x95, 0x05, 0x75, 0x01,
+    0x05, 0x08, 0x19, 0x01, 0x29, 0x05, 0x91, 0x02, 0x95, 0x01, 0x75, 0x03, 0x91, 0x01, 0x95, 0x06,
+    0x75, 0x08, 0x15, 0x00, 0x25, 0x65, 0x05, 0x07, 0x19, 0x00, 0x29, 0x65, 0x81, 0x00, 0xC0,
+];
+
+//HID 1.11 appendix B.2
+const BOOT_MOUSE: &[u8] = &[
+    0x05, 0x01, 0x09, 0x02, 0xA1, 0x01, 0x09, 0x01, 0xA1, 0x00, 0x05, 0x09, 0x19, 0x01, 0x29, 0x03,
+    0x15, 0x00, 0x25, 0x01, 0x95, 0x03, 0x75, 0x01, 0x81, 0x02, 0x95, 0x01, 0x75, 0x05, 0x81, 0x01,
+    0x05, 0x01, 0x09, 0x30, 0x09, 0x31, 0x15, 0x81, 0x25, 0x7F, 0x75, 0x08, 0x95, 0x02, 0x81, 0x06,
+    0xC0, 0xC0,
+];
+
+//wireless receiver style composite: a keyboard (id 1), consumer keys (id 2) and a mouse (id 3)
+//whose wheel sits between push and pop, plus a vendor feature report with extended usages
+const COMPOSITE: &[u8] = &[
+    0x05, 0x01, 0x09, 0x06, 0xA1, 0x01, 0x85, 0x01, 0x05, 0x07, 0x19, 0xE0, 0x29, 0xE7, 0x15, 0x00,
+    0x25, 0x01, 0x75, 0x01, 0x95, 0x08, 0x81, 0x02, 0x95, 0x06, 0x75, 0x08, 0x15, 0x00, 0x26, 0xFF,
+    0x00, 0x19, 0x00, 0x2A, 0xFF, 0x00, 0x81, 0x00, 0xC0, //
+    0x05, 0x0C, 0x09, 0x01, 0xA1, 0x01, 0x85, 0x02, 0x15, 0x00, 0x26, 0xFF, 0x03, 0x19, 0x00, 0x2A,
+    0xFF, 0x03, 0x75, 0x10, 0x95, 0x02, 0x81, 0x00, 0xC0, //
+    0x05, 0x01, 0x09, 0x02, 0xA1, 0x01, 0x85, 0x03, 0x09, 0x01, 0xA1, 0x00, 0x05, 0x09, 0x19, 0x01,
+    0x29, 0x05, 0x15, 0x00, 0x25, 0x01, 0x75, 0x01, 0x95, 0x05, 0x81, 0x02, 0x75, 0x03, 0x95, 0x01,
+    0x81, 0x03, 0x05, 0x01, 0x16, 0x01, 0xF8, 0x26, 0xFF, 0x07, 0x75, 0x0C, 0x95, 0x02, 0x09, 0x30,
+    0x09, 0x31, 0x81, 0x06, 0xA4, 0x15, 0x81, 0x25, 0x7F, 0x75, 0x08, 0x95, 0x01, 0x09, 0x38, 0x81,
+    0x06, 0xB4, 0x95, 0x01, 0x0A, 0x38, 0x02, 0x05, 0x0C, 0x81, 0x06, 0xC0, 0xC0, //
+    0x06, 0x00, 0xFF, 0x09, 0x01, 0xA1, 0x01, 0x85, 0x10, 0x0B, 0x02, 0x00, 0x00, 0xFF, 0x0B, 0x03,
+    0x00, 0x00, 0xFF, 0x75, 0x08, 0x95, 0x02, 0xB1, 0x02, 0xC0,
+];
+
+fn fields(
+    descriptor: &ReportDescriptor,
+    report_type: ReportType,
+    id: Option<u8>,
+) -> Vec<(usize, usize)> {
+    descriptor
+        .report(report_type, id)
+        .unwrap()
+        .fields
+        .iter()
+        .map(|field| (field.bit_offset, field.bit_length))
+        .collect()
+}
+
+fn events(events: Vec<ReportEvent>) -> Vec<(u32, u32, i32, bool)> {
+    events
+        .iter()
+        .map(|event| (event.usage_page, event.usage, event.value, event.relative))
+        .collect()
+}
+
+#[test]
+fn items() {
+    let items = ReportItem::parse_all(BOOT_MOUSE).unwrap();
+    assert_eq!(items.len(), 26);
+    assert!(matches!(
+        items[2].item,
+        Item::Short {
+            tag: ItemTag::Main(MainItemTag::Collection),
+            size: 1,
+            data: 1
+        }
+    ));
+    //logical minimum -127 is sign extended from its single byte
+    assert_eq!((items[19].offset, items[19].item.signed()), (38, -127));
+
+    let long = ReportItem::parse_all(&[0xFE, 0x02, 0x10, 0xAA, 0xBB, 0xC0]).unwrap();
+    assert_eq!(long[0].len, 5);
+    assert!(matches!(&long[0].item, Item::Long { tag: 0x10, data } if data == &[0xAA, 0xBB]));
+}
+
+#[test]
+fn boot_keyboard() {
+    let descriptor = ReportDescriptor::parse(BOOT_KEYBOARD).unwrap();
+    assert!(!descriptor.uses_report_ids());
+    assert_eq!(
+        fields(&descriptor, ReportType::Input, None),
+        [(0, 8), (8, 8), (16, 48)]
+    );
+    assert_eq!(
+        fields(&descriptor, ReportType::Output, None),
+        [(0, 5), (5, 3)]
+    );
+
+    let input = descriptor.report(ReportType::Input, None).unwrap();
+    assert_eq!(input.byte_length(), 8);
+    let (modifiers, reserved, keys) = (&input.fields[0], &input.fields[1], &input.fields[2]);
+    assert!(modifiers.flags.is_variable());
+    assert_eq!(modifiers.usage(0), Some(0x0007_00E0));
+    assert_eq!(modifiers.usage(7), Some(0x0007_00E7));
+    assert!(reserved.is_padding());
+    assert!(keys.flags.is_array());
+    assert_eq!(keys.local_state.usage_count(), 0x66);
+    assert_eq!(keys.global_state.logical_maximum, 0x65);
+
+    let leds = &descriptor.report(ReportType::Output, None).unwrap().fields[0];
+    assert_eq!(leds.usage(4), Some(0x0008_0005));
+    assert_eq!(descriptor.collections.len(), 1);
+    assert_eq!(descriptor.collections[0].usage, 0x0001_0006);
+}
+
+#[test]
+fn boot_mouse() {
+    let descriptor = ReportDescriptor::parse(BOOT_MOUSE).unwrap();
+    assert_eq!(
+        fields(&descriptor, ReportType::Input, None),
+        [(0, 3), (3, 5), (8, 16)]
+    );
+
+    let pointer = &descriptor.collections[1];
+    assert_eq!(pointer.collection_type, CollectionType::Physical);
+    assert_eq!((pointer.usage, pointer.parent), (0x0001_0001, Some(0)));
+    assert_eq!(descriptor.collections[0].children, [1]);
+    assert_eq!(descriptor.collection_fields(0).len(), 3);
+
+    let axes = &descriptor.report(ReportType::Input, None).unwrap().fields[2];
+    assert!(axes.flags.is_relative() && axes.is_signed());
+    assert_eq!(
+        (axes.usage(0), axes.usage(1)),
+        (Some(0x0001_0030), Some(0x0001_0031))
+    );
+    assert_eq!(axes.read(&[0x01, 0xFF, 0x05], 0), -1);
+    assert_eq!(axes.read(&[0x01, 0xFF, 0x05], 1), 5);
+}
+
+#[test]
+fn composite_with_report_ids() {
+    let descriptor = ReportDescriptor::parse(COMPOSITE).unwrap();
+    assert!(descriptor.uses_report_ids());
+    assert_eq!(
+        fields(&descriptor, ReportType::Input, Some(1)),
+        [(0, 8), (8, 48)]
+    );
+    assert_eq!(fields(&descriptor, ReportType::Input, Some(2)), [(0, 32)]);
+    assert_eq!(
+        fields(&descriptor, ReportType::Input, Some(3)),
+        [(0, 5), (5, 3), (8, 24), (32, 8), (40, 12)]
+    );
+    assert_eq!(
+        fields(&descriptor, ReportType::Feature, Some(0x10)),
+        [(0, 16)]
+    );
+    assert_eq!(
+        descriptor
+            .report(ReportType::Input, Some(3))
+            .unwrap()
+            .byte_length(),
+        8
+    );
+
+    let mouse = descriptor.report(ReportType::Input, Some(3)).unwrap();
+    //the pop restored the 12 bit signed range of the axes before the consumer AC Pan field
+    let (wheel, pan) = (&mouse.fields[3], &mouse.fields[4]);
+    assert_eq!(wheel.global_state.logical_minimum, -127);
+    assert_eq!(wheel.usage(0), Some(0x0001_0038));
+    assert_eq!(pan.global_state.logical_minimum, -2047);
+    assert_eq!(pan.report_size(), 12);
+    //a 2 byte usage takes the page in effect at the main item, not the one before it
+    assert_eq!(pan.usage(0), Some(0x000C_0238));
+    assert_eq!(mouse.fields[2].read(&[0x00, 0xFF, 0xFF, 0x7F], 0), -1);
+    assert_eq!(mouse.fields[2].read(&[0x00, 0xFF, 0xFF, 0x7F], 1), 0x7FF);
+
+    let vendor = descriptor.report(ReportType::Feature, Some(0x10)).unwrap();
+    assert_eq!(vendor.fields[0].usage(1), Some(0xFF00_0003));
+    assert_eq!(
+        descriptor.split_report(ReportType::Input, &[2, 0xE9, 0x00, 0, 0]),
+        Ok((1, &[0xE9, 0x00, 0, 0][..]))
+    );
+    assert_eq!(
+        descriptor.split_report(ReportType::Input, &[9, 0]),
+        Err(ReportDescriptorError::UnknownReportId(9))
+    );
+}
+
+#[test]
+fn errors() {
+    let parse = |raw: &[u8]| ReportDescriptor::parse(raw).map(|_| ());
+    assert_eq!(
+        parse(&[0x05, 0x01, 0x26, 0xFF]),
+        Err(ReportDescriptorError::UnexpectedEnd(2))
+    );
+    assert_eq!(
+        parse(&[0xFE, 0x04, 0x10, 0x00]),
+        Err(ReportDescriptorError::UnexpectedEnd(0))
+    );
+    assert_eq!(
+        parse(&[0x85, 0x00]),
+        Err(ReportDescriptorError::ReportIdZero(0))
+    );
+    assert_eq!(
+        parse(&[0xA4, 0xB4, 0xB4]),
+        Err(ReportDescriptorError::PopWithoutPush(2))
+    );
+    assert_eq!(
+        parse(&[0xA1, 0x01, 0xC0, 0xC0]),
+        Err(ReportDescriptorError::UnbalancedEndCollection(3))
+    );
+    assert_eq!(
+        parse(&[0xA1, 0x01]),
+        Err(ReportDescriptorError::UnclosedCollection)
+    );
+    assert_eq!(
+        parse(&[0xA9, 0x01, 0xA9, 0x01]),
+        Err(ReportDescriptorError::NestedDelimiter(2))
+    );
+    assert_eq!(
+        parse(&[0xA9, 0x00]),
+        Err(ReportDescriptorError::UnbalancedDelimiter(0))
+    );
+
+    //report size times count must neither wrap nor exceed 64k bytes
+    let overflow = [
+        0x77, 0x00, 0x00, 0x01, 0x00, 0x97, 0x00, 0x00, 0x01, 0x00, 0x81, 0x02,
+    ];
+    assert_eq!(
+        parse(&overflow),
+        Err(ReportDescriptorError::ReportTooLarge(10))
+    );
+    let too_long = [
+        0x75, 0x08, 0x96, 0xFF, 0xFF, 0x81, 0x02, 0x95, 0x01, 0x81, 0x02,
+    ];
+    assert_eq!(
+        parse(&too_long),
+        Err(ReportDescriptorError::ReportTooLarge(9))
+    );
+}
+
+#[test]
+fn delimiters_keep_the_first_alternative() {
+    let raw = [
+        0x05, 0x01, 0xA9, 0x01, 0x09, 0x30, 0x09, 0x40, 0xA9, 0x00, 0x75, 0x08, 0x95, 0x01, 0x81,
+        0x02,
+    ];
+    let descriptor = ReportDescriptor::parse(&raw).unwrap();
+    let field = &descriptor.report(ReportType::Input, None).unwrap().fields[0];
+    assert_eq!(field.local_state.usage_count(), 1);
+    assert_eq!(field.usage(0), Some(0x0001_0030));
+}
+
+#[test]
+fn keyboard_input_reports() {
+    let mut handler = ReportHandler::new(&ReportDescriptor::parse(BOOT_KEYBOARD).unwrap());
+    assert_eq!(handler.total_byte_length, 8);
+
+    //left shift + a, absolute values that stay zero are not reported
+    let pressed = handler.handle(&[0x02, 0, 0x04, 0, 0, 0, 0, 0]).unwrap();
+    assert_eq!(events(pressed), [(7, 0xE1, 1, false), (7, 0x04, 1, false)]);
+    assert!(handler
+        .handle(&[0x02, 0, 0x04, 0, 0, 0, 0, 0])
+        .unwrap()
+        .is_empty());
+
+    //shift released, b joins a
+    let changed = handler.handle(&[0x00, 0, 0x04, 0x05, 0, 0, 0, 0]).unwrap();
+    assert_eq!(events(changed), [(7, 0xE1, 0, false), (7, 0x05, 1, false)]);
+    let released = handler.handle(&[0; 8]).unwrap();
+    assert_eq!(events(released), [(7, 0x04, 0, false), (7, 0x05, 0, false)]);
+
+    //a short report must not read the missing bytes as zero
+    handler.handle(&[0x02, 0, 0x04, 0, 0, 0, 0, 0]).unwrap();
+    assert_eq!(
+        handler.handle(&[0x00, 0, 0x04]).map(events),
+        Err(ReportDescriptorError::ShortReport(3))
+    );
+    assert!(handler
+        .handle(&[0x02, 0, 0x04, 0, 0, 0, 0, 0])
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn mouse_input_reports() {
+    let mut handler = ReportHandler::new(&ReportDescriptor::parse(BOOT_MOUSE).unwrap());
+    let moved = handler.handle(&[0x01, 0xFF, 0x00]).unwrap();
+    assert_eq!(events(moved), [(9, 1, 1, false), (1, 0x30, -1, true)]);
+    //relative values are reported every time they are not zero
+    let moved = handler.handle(&[0x01, 0xFF, 0x00]).unwrap();
+    assert_eq!(events(moved), [(1, 0x30, -1, true)]);
+    assert_eq!(
+        handler.handle(&[0x01, 0xFF]).map(events),
+        Err(ReportDescriptorError::ShortReport(2))
+    );
+}
+
+#[test]
+fn composite_input_reports() {
+    let mut handler = ReportHandler::new(&ReportDescriptor::parse(COMPOSITE).unwrap());
+    assert_eq!(handler.total_byte_length, 8);
+
+    let volume_up = handler.handle(&[2, 0xE9, 0x00, 0, 0]).unwrap();
+    assert_eq!(events(volume_up), [(0x0C, 0xE9, 1, false)]);
+    let scrolled = handler.handle(&[3, 0, 0, 0, 0, 0x01, 0, 0]).unwrap();
+    assert_eq!(events(scrolled), [(1, 0x38, 1, true)]);
+
+    assert_eq!(
+        handler.handle(&[2, 0xE9]).map(events),
+        Err(ReportDescriptorError::ShortReport(1))
+    );
+    assert_eq!(
+        handler.handle(&[9, 0]).map(events),
+        Err(ReportDescriptorError::UnknownReportId(9))
+    );
+    assert_eq!(
+        handler.handle(&[]).map(events),
+        Err(ReportDescriptorError::ShortReport(0))
+    );
+}
+
+#[test]
+fn encode_reports() {
+    let keyboard = ReportDescriptor::parse(BOOT_KEYBOARD).unwrap();
+    //num lock and caps lock, out of range values are clamped to the logical range
+    assert_eq!(
+        keyboard.encode(ReportType::Output, &[(0x08, 0x01, 1), (0x08, 0x02, 5)]),
+        Ok(vec![0x03])
+    );
+    assert_eq!(
+        keyboard.encode(
+            ReportType::Input,
+            &[(0x07, 0xE1, 1), (0x07, 0x04, 1), (0x07, 0x05, 1)]
+        ),
+        Ok(vec![0x02, 0, 0x04, 0x05, 0, 0, 0, 0])
+    );
+    let too_many: Vec<_> = (0x04..0x0B).map(|key| (0x07, key, 1)).collect();
+    assert_eq!(
+        keyboard.encode(ReportType::Input, &too_many),
+        Err(ReportDescriptorError::ArrayOverflow(0x07, 0x0A))
+    );
+    assert_eq!(
+        keyboard.encode(ReportType::Output, &[(0x08, 0x01, 1), (0x08, 0x10, 1)]),
+        Err(ReportDescriptorError::UndeclaredUsage(0x08, 0x10))
+    );
+
+    let composite = ReportDescriptor::parse(COMPOSITE).unwrap();
+    assert_eq!(
+        composite.encode(ReportType::Input, &[(0x0C, 0xE9, 1)]),
+        Ok(vec![0x02, 0xE9, 0x00, 0x00, 0x00])
+    );
+    assert_eq!(
+        composite.encode(ReportType::Feature, &[(0xFF00, 0x03, 7)]),
+        Ok(vec![0x10, 0x00, 0x07])
+    );
+
+    //five led usages but only three elements, led 4 and 5 have nowhere to go
+    let leds = ReportDescriptor::parse(&[
+        0x05, 0x08, 0x19, 0x01, 0x29, 0x05, 0x15, 0x00, 0x25, 0x01, 0x75, 0x01, 0x95, 0x03, 0x91,
+        0x02,
+    ])
+    .unwrap();
+    assert_eq!(
+        leds.encode(ReportType::Output, &[(0x08, 0x03, 1)]),
+        Ok(vec![0x04])
+    );
+    assert_eq!(
+        leds.encode(ReportType::Output, &[(0x08, 0x04, 1)]),
+        Err(ReportDescriptorError::UndeclaredUsage(0x08, 0x04))
+    );
+}
+
+#[test]
+fn repeated_usages_keep_their_own_values() {
+    //two contacts reporting X in their own field, as in every Finger collection of a touch screen
+    let descriptor = ReportDescriptor::parse(&[
+        0x05, 0x01, 0x15, 0x00, 0x26, 0xFF, 0x00, 0x75, 0x08, 0x95, 0x01, 0x09, 0x30, 0x81, 0x02,
+        0x09, 0x30, 0x81, 0x02,
+    ])
+    .unwrap();
+    let mut handler = ReportHandler::new(&descriptor);
+    let first = handler.handle(&[10, 20]).unwrap();
+    assert_eq!(events(first), [(1, 0x30, 10, false), (1, 0x30, 20, false)]);
+    assert!(handler.handle(&[10, 20]).unwrap().is_empty());
+    let second_moved = handler.handle(&[10, 21]).unwrap();
+    assert_eq!(events(second_moved), [(1, 0x30, 21, false)]);
+}
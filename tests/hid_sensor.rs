@@ -0,0 +1,302 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_report::{CollectionType, MainItemFlags, ReportDescriptor, ReportDescriptorError},
+    hid_builder::ReportDescriptorBuilder,
+    hid_sensor::{
+        PowerState, ReportingState, Sensor, SensorModifier, SensorProperty, SensorReading,
+    },
+    hid_usage::Sensors,
+};
+
+const VARIABLE: MainItemFlags = MainItemFlags::DATA_VARIABLE_ABSOLUTE;
+const ARRAY: MainItemFlags = MainItemFlags::DATA_ARRAY_ABSOLUTE;
+
+//named array property or data field: a logical collection of `count` selectors from `first`
+fn named_array(
+    builder: ReportDescriptorBuilder,
+    usage: u16,
+    first: u16,
+    count: u16,
+) -> ReportDescriptorBuilder {
+    builder
+        .usage(usage)
+        .logical_range(0, count as i32 - 1)
+        .report_size(8)
+        .report_count(1)
+        .collection(CollectionType::Logical)
+        .usage_range(first, first + count - 1)
+}
+
+//a sensor collection grouping an accelerometer (id 1) whose report interval is in 100us
+//units and whose sensitivity is declared once for Acceleration, and an ambient light sensor
+//(id 2) with a unitless report interval, a per field illuminance sensitivity and a sensor wide
+//one
+fn sensors() -> ReportDescriptor {
+    let builder = ReportDescriptorBuilder::new()
+        .usage_page(0x20)
+        .usage(0x01)
+        .collection(CollectionType::Application)
+        .usage(0x73)
+        .collection(CollectionType::Physical)
+        .report_id(1);
+    let builder = named_array(builder, 0x316, 0x840, 6)
+        .feature(ARRAY)
+        .end_collection();
+    let builder = named_array(builder, 0x319, 0x850, 6)
+        .feature(ARRAY)
+        .end_collection()
+        .usage(0x30E)
+        .logical_range(0, 0x7FFF)
+        .unit(0x1001)
+        .unit_exponent(-4)
+        .report_size(16)
+        .feature(VARIABLE)
+        .usage(0x1452)
+        .unit(0)
+        .unit_exponent(-2)
+        .feature(VARIABLE);
+    let builder = named_array(builder, 0x201, 0x800, 7)
+        .input(ARRAY)
+        .end_collection();
+    let builder = named_array(builder, 0x202, 0x810, 17)
+        .input(ARRAY)
+        .end_collection()
+        .usage(0x453)
+        .usage(0x454)
+        .usage(0x455)
+        .logical_range(-32767, 32767)
+        .unit_exponent(-2)
+        .report_size(16)
+        .report_count(3)
+        .input(VARIABLE)
+        .end_collection()
+        .usage(0x41)
+        .collection(CollectionType::Physical)
+        .report_id(2)
+        .usage(0x30E)
+        .usage(0x14D1)
+        .usage(0x30F)
+        .logical_range(0, 0x7FFF)
+        .unit_exponent(0)
+        .report_size(16)
+        .report_count(3)
+        .feature(VARIABLE);
+    let raw = named_array(builder, 0x201, 0x800, 7)
+        .input(ARRAY)
+        .end_collection()
+        .usage(0x4D1)
+        .usage(0x4D2)
+        .logical_range(0, 0x7FFF)
+        .report_size(16)
+        .report_count(2)
+        .input(VARIABLE)
+        .end_collection()
+        .end_collection()
+        .build()
+        .unwrap();
+    ReportDescriptor::parse(&raw).unwrap()
+}
+
+fn close(value: f64, expected: f64) -> bool {
+    (value - expected).abs() < 1e-9
+}
+
+#[test]
+fn discover() {
+    let descriptor = sensors();
+    let sensors = Sensor::discover(&descriptor);
+    assert_eq!(
+        sensors
+            .iter()
+            .map(|sensor| sensor.kind())
+            .collect::<Vec<_>>(),
+        [
+            Some(Sensors::MotionAccelerometer3D),
+            Some(Sensors::LightAmbientLight)
+        ]
+    );
+    let (accelerometer, light) = (&sensors[0], &sensors[1]);
+    assert_ne!(accelerometer.input_report, light.input_report);
+    assert_ne!(accelerometer.feature_report, light.feature_report);
+
+    assert_eq!(
+        accelerometer
+            .data_fields
+            .iter()
+            .map(|field| (field.usage, field.unit_exponent))
+            .collect::<Vec<_>>(),
+        [(0x453, -2), (0x454, -2), (0x455, -2)]
+    );
+    assert_eq!(
+        accelerometer.data_fields[0].name(),
+        Some("Data Field: Acceleration Axis X")
+    );
+    //every axis falls back to the Acceleration sensitivity
+    let sensitivity = accelerometer.data_fields[0].sensitivity;
+    assert!(sensitivity.is_some());
+    assert!(accelerometer
+        .data_fields
+        .iter()
+        .all(|field| field.sensitivity == sensitivity));
+
+    //illuminance has its own sensitivity, color temperature only the sensor wide one
+    assert_eq!(
+        light
+            .data_fields
+            .iter()
+            .map(|field| (field.usage, field.sensitivity.is_some()))
+            .collect::<Vec<_>>(),
+        [(0x4D1, true), (0x4D2, false)]
+    );
+}
+
+#[test]
+fn modifiers() {
+    assert_eq!(
+        SensorModifier::split(0x1452),
+        (0x452, SensorModifier::ChangeSensitivityAbsolute)
+    );
+    assert_eq!(
+        SensorModifier::split(0xE4D1),
+        (0x4D1, SensorModifier::ChangeSensitivityPercentRelative)
+    );
+    assert_eq!(SensorModifier::split(0x0453), (0x453, SensorModifier::None));
+    assert_eq!(SensorModifier::Maximum.apply(0x0453), 0x2453);
+    //an existing modifier is replaced
+    assert_eq!(SensorModifier::Minimum.apply(0x2453), 0x3453);
+    assert_eq!(SensorModifier::None.apply(0x3453), 0x0453);
+}
+
+#[test]
+fn accelerometer_sample_and_properties() {
+    let descriptor = sensors();
+    let accelerometer = &Sensor::discover(&descriptor)[0];
+
+    //ready, data updated, x 1g, y -0.5g, z 9.81g
+    let sample = accelerometer
+        .sample(
+            &descriptor,
+            &[0x01, 0x01, 0x03, 0x64, 0x00, 0xCE, 0xFF, 0xD5, 0x03],
+        )
+        .unwrap();
+    assert_eq!(sample.state, Some(Sensors::SensorStateReady));
+    assert_eq!(sample.event, Some(Sensors::SensorEventDataUpdated));
+    let SensorReading::Accelerometer { x, y, z } = sample.reading else {
+        panic!("{:?}", sample.reading);
+    };
+    assert!(close(x, 1.0) && close(y, -0.5) && close(z, 9.81));
+    //the light sensor's report
+    assert!(accelerometer
+        .sample(&descriptor, &[0x02, 0x01, 0x00, 0x00, 0x00, 0x00])
+        .is_none());
+
+    //all events, D0, 1000 * 100us, 0.5g
+    let current = [0x01, 0x01, 0x01, 0xE8, 0x03, 0x32, 0x00];
+    let properties = accelerometer.properties(&descriptor, &current).unwrap();
+    assert_eq!(properties.report_interval_ms, Some(100));
+    assert_eq!(properties.minimum_report_interval_ms, None);
+    assert_eq!(properties.power_state, Some(PowerState::D0FullPower));
+    assert_eq!(properties.reporting_state, Some(ReportingState::AllEvents));
+    assert_eq!(
+        properties
+            .sensitivity
+            .iter()
+            .map(|(usage, value)| (*usage, close(*value, 0.5)))
+            .collect::<Vec<_>>(),
+        [(0x453, true), (0x454, true), (0x455, true)]
+    );
+}
+
+#[test]
+fn set_properties() {
+    let descriptor = sensors();
+    let sensors = Sensor::discover(&descriptor);
+    let (accelerometer, light) = (&sensors[0], &sensors[1]);
+    let current = [0x01, 0x01, 0x01, 0xE8, 0x03, 0x32, 0x00];
+
+    //the interval goes through the exponent, selectors are indices into the named arrays
+    assert_eq!(
+        accelerometer
+            .set_properties(
+                &descriptor,
+                Some(&current),
+                &[
+                    SensorProperty::ReportInterval { milliseconds: 250 },
+                    SensorProperty::PowerState(PowerState::D1LowPower),
+                ],
+            )
+            .unwrap(),
+        [0x01, 0x01, 0x02, 0xC4, 0x09, 0x32, 0x00]
+    );
+    //an axis sensitivity is written to its group
+    assert_eq!(
+        accelerometer
+            .set_properties(
+                &descriptor,
+                None,
+                &[
+                    SensorProperty::ReportingState(ReportingState::WakeOnThresholdEvents),
+                    SensorProperty::Sensitivity {
+                        data_field: 0x454,
+                        value: 0.25,
+                    },
+                ],
+            )
+            .unwrap(),
+        [0x01, 0x05, 0x00, 0x00, 0x00, 0x19, 0x00]
+    );
+
+    //unitless interval, per field and sensor wide sensitivities
+    assert_eq!(
+        light
+            .set_properties(
+                &descriptor,
+                None,
+                &[
+                    SensorProperty::ReportInterval { milliseconds: 500 },
+                    SensorProperty::Sensitivity {
+                        data_field: 0x4D1,
+                        value: 10.0,
+                    },
+                    SensorProperty::Sensitivity {
+                        data_field: 0x4D2,
+                        value: 50.0,
+                    },
+                ],
+            )
+            .unwrap(),
+        [0x02, 0xF4, 0x01, 0x0A, 0x00, 0x32, 0x00]
+    );
+    assert!(matches!(
+        light.set_properties(
+            &descriptor,
+            None,
+            &[SensorProperty::PowerState(PowerState::D0FullPower)]
+        ),
+        Err(ReportDescriptorError::UndeclaredUsage(0x20, 0x851))
+    ));
+}
+
+#[test]
+fn ambient_light() {
+    let descriptor = sensors();
+    let light = &Sensor::discover(&descriptor)[1];
+    let sample = light
+        .sample(&descriptor, &[0x02, 0x01, 0x2C, 0x01, 0x8C, 0x0A])
+        .unwrap();
+    assert_eq!(sample.state, Some(Sensors::SensorStateReady));
+    assert_eq!(sample.event, None);
+    assert_eq!(
+        sample.reading,
+        SensorReading::AmbientLight {
+            illuminance: 300.0,
+            color_temperature: Some(2700.0),
+        }
+    );
+
+    let properties = light
+        .properties(&descriptor, &[0x02, 0x64, 0x00, 0x05, 0x00, 0x00, 0x00])
+        .unwrap();
+    assert_eq!(properties.report_interval_ms, Some(100));
+    assert_eq!(properties.power_state, None);
+    assert_eq!(properties.sensitivity, [(0x4D1, 5.0)]);
+}
@@ -0,0 +1,133 @@
+use usb_descriptor_decoder::descriptors::hid_usage::{
+    Consumer, Digitizer, GenericDesktop, KeyboardUsage, Usage, UsagePage, UsageType,
+};
+
+fn named(page: u16, id: u16) -> (Option<&'static str>, &'static [UsageType]) {
+    let usage = Usage::new(page, id);
+    (usage.name(), usage.usage_types())
+}
+
+#[test]
+fn page_names() {
+    assert_eq!(UsagePage::from(0x01).name(), "Generic Desktop");
+    assert_eq!(UsagePage::from(0x0D).name(), "Digitizers");
+    assert_eq!(UsagePage::from(0x0F).name(), "Physical Input Device");
+    assert_eq!(UsagePage::from(0x20).to_string(), "Sensors");
+    assert_eq!(UsagePage::from(0xF1D0), UsagePage::FidoAlliance);
+
+    //every page id survives the round trip, known or not
+    for page in 0..=u16::MAX {
+        assert_eq!(u16::from(UsagePage::from(page)), page);
+    }
+}
+
+#[test]
+fn vendor_and_reserved_pages() {
+    for page in [0xFF00, 0xFF42, 0xFFFF] {
+        assert_eq!(UsagePage::from(page), UsagePage::Vendor(page));
+        let usage = Usage::new(page, 0x0001);
+        assert_eq!(usage, Usage::Vendor { page, id: 1 });
+        assert_eq!(usage.name(), None);
+        assert!(usage.usage_types().is_empty());
+        assert_eq!(usage.to_string(), "Vendor Usage 0x0001");
+    }
+    assert_eq!(
+        UsagePage::from(0xFF00).to_string(),
+        "Vendor Defined Page 0xFF00"
+    );
+    //0xFEFF is the last id before the vendor range and not assigned
+    assert_eq!(UsagePage::from(0xFEFF), UsagePage::Reserved(0xFEFF));
+    assert_eq!(UsagePage::from(0x13).to_string(), "Reserved Page 0x0013");
+    assert_eq!(
+        Usage::new(0x13, 0x01),
+        Usage::Unknown {
+            page: 0x13,
+            id: 0x01
+        }
+    );
+}
+
+#[test]
+fn usage_names_and_types() {
+    use UsageType::*;
+    assert_eq!(
+        named(0x01, 0x02),
+        (Some("Mouse"), &[CollectionApplication][..])
+    );
+    assert_eq!(named(0x01, 0x30), (Some("X"), &[DynamicValue][..]));
+    assert_eq!(named(0x01, 0x39), (Some("Hat Switch"), &[DynamicValue][..]));
+    assert_eq!(
+        named(0x07, 0x04),
+        (Some("Keyboard a and A"), &[Selector][..])
+    );
+    assert_eq!(
+        named(0x07, 0xE0),
+        (Some("Keyboard LeftControl"), &[DynamicValue][..])
+    );
+    assert_eq!(named(0x08, 0x01), (Some("Num Lock"), &[OnOffControl][..]));
+    assert_eq!(
+        named(0x0C, 0xE9),
+        (Some("Volume Increment"), &[ReTriggerControl][..])
+    );
+    assert_eq!(named(0x0C, 0x238), (Some("AC Pan"), &[LinearControl][..]));
+    assert_eq!(
+        named(0x0D, 0x22),
+        (Some("Finger"), &[CollectionLogical][..])
+    );
+    assert_eq!(
+        named(0x0D, 0x42),
+        (Some("Tip Switch"), &[MomentaryControl][..])
+    );
+    assert_eq!(
+        named(0x0D, 0x55),
+        (Some("Contact Count Maximum"), &[StaticValue][..])
+    );
+    //sensor types are application or physical collections depending on the device
+    assert_eq!(
+        named(0x20, 0x01),
+        (
+            Some("Sensor"),
+            &[CollectionApplication, CollectionPhysical][..]
+        )
+    );
+
+    assert_eq!(
+        Usage::new(0x01, 0x30),
+        Usage::GenericDesktop(GenericDesktop::X)
+    );
+    assert_eq!(Usage::new(0x07, 0x04), Usage::Keyboard(KeyboardUsage::A));
+    assert_eq!(Usage::new(0x0C, 0x238), Usage::Consumer(Consumer::AcPan));
+    assert_eq!(
+        Usage::from_extended(0x000D_0042),
+        Usage::Digitizer(Digitizer::TipSwitch)
+    );
+    assert_eq!(Usage::new(0x0D, 0x42).extended(), 0x000D_0042);
+    assert_eq!(UsageType::NamedArray.abbreviation(), "NAry");
+}
+
+#[test]
+fn numbered_and_unknown_usages() {
+    assert_eq!(Usage::new(0x09, 0).to_string(), "No Button Pressed");
+    assert_eq!(Usage::new(0x09, 3).to_string(), "Button 3");
+    assert_eq!(Usage::new(0x09, 3).usage_types().len(), 4);
+    assert_eq!(Usage::new(0x0A, 2).to_string(), "Instance 2");
+
+    //ids the tables do not list keep their page
+    let unknown = Usage::new(0x01, 0x0300);
+    assert_eq!(
+        unknown,
+        Usage::Unknown {
+            page: 0x01,
+            id: 0x0300
+        }
+    );
+    assert_eq!(unknown.page(), UsagePage::GenericDesktop);
+    assert_eq!(
+        (unknown.name(), unknown.to_string()),
+        (None, "0x0300".into())
+    );
+    assert!(unknown.usage_types().is_empty());
+    //pages without a table resolve to Unknown as well
+    assert_eq!(Usage::new(0x84, 0x01).page(), UsagePage::PowerDevice);
+    assert_eq!(Usage::new(0x84, 0x01).name(), None);
+}
@@ -0,0 +1,118 @@
+use std::collections::BTreeMap;
+
+use usb_descriptor_decoder::descriptors::{
+    lsusb::LsusbDump, parser::RawDescriptorParser, topological_desc::TopologicalUSBDescriptorRoot,
+    PortSpeed,
+};
+
+fn parse(device: &[u8], config: &[u8]) -> TopologicalUSBDescriptorRoot {
+    let mut config = config.to_vec();
+    let total_length = config.len() as u16;
+    config[2..4].copy_from_slice(&total_length.to_le_bytes());
+    //like a transfer buffer, the parser stops at the zeroed tail
+    config.extend_from_slice(&[0, 0]);
+
+    let mut parser = RawDescriptorParser::new(device.to_vec());
+    parser.single_state_cycle();
+    parser.append_config(config);
+    parser.summarize()
+}
+
+//a Logitech K120: boot keyboard on interface 0, media keys on interface 1
+const K120_DEVICE: &[u8] = &[
+    18, 0x01, 0x10, 0x01, 0, 0, 0, 8, 0x6d, 0x04, 0x1c, 0xc3, 0x00, 0x64, 1, 2, 0, 1,
+];
+const K120_CONFIG: &[u8] = &[
+    9, 0x02, 0, 0, 2, 1, 0, 0xa0, 45, //
+    9, 0x04, 0, 0, 1, 3, 1, 1, 0, //
+    9, 0x21, 0x10, 0x01, 0, 1, 0x22, 65, 0, //
+    7, 0x05, 0x81, 0x03, 8, 0, 10, //
+    9, 0x04, 1, 0, 1, 3, 0, 0, 0, //
+    9, 0x21, 0x10, 0x01, 0, 1, 0x22, 159, 0, //
+    7, 0x05, 0x82, 0x03, 4, 0, 255,
+];
+
+#[cfg(feature = "usb-ids")]
+#[test]
+fn golden_k120() {
+    use usb_descriptor_decoder::usb_ids::UsbIds;
+
+    let root = parse(K120_DEVICE, K120_CONFIG);
+    let strings = BTreeMap::from([(1, "Logitech".into()), (2, "USB Keyboard".into())]);
+    let ids = UsbIds::embedded();
+    let dump = LsusbDump::new(&root)
+        .with_strings(&strings)
+        .with_usb_ids(&ids)
+        .with_speed(PortSpeed::LowSpeed)
+        .to_string();
+
+    //lsusb also prints Synch Type and Usage Type for interrupt endpoints and a "Report
+    //Descriptors" block it fetches itself, neither is part of this dump
+    let expected: String = include_str!("data/k120.lsusb")
+        .lines()
+        .filter(|line| !line.contains("Synch Type") && !line.contains("Usage Type"))
+        .filter(|line| !line.contains("Report Descriptors") && !line.contains("UNAVAILABLE"))
+        .flat_map(|line| [line, "\n"])
+        .collect();
+    assert_eq!(dump, expected);
+}
+
+#[test]
+fn max_power_follows_the_port_speed() {
+    //a usb 3.2 device with bMaxPower 112
+    let device = [
+        18, 0x01, 0x20, 0x03, 0, 0, 0, 9, 0x34, 0x12, 0x78, 0x56, 0x00, 0x01, 0, 0, 0, 1,
+    ];
+    let config = [
+        9, 0x02, 0, 0, 1, 1, 0, 0x80, 112, //
+        9, 0x04, 0, 0, 1, 0xff, 0, 0, 0, //
+        7, 0x05, 0x81, 0x02, 0x00, 0x04, 0, //
+        6, 0x30, 0, 0, 0, 0,
+    ];
+    let root = parse(&device, &config);
+    let max_power = |dump: String| {
+        dump.lines()
+            .find(|line| line.contains("MaxPower"))
+            .map(|line| line.trim().to_string())
+            .unwrap()
+    };
+    assert_eq!(
+        max_power(LsusbDump::new(&root).to_string()),
+        "MaxPower              896mA"
+    );
+    assert_eq!(
+        max_power(
+            LsusbDump::new(&root)
+                .with_speed(PortSpeed::SuperSpeed)
+                .to_string()
+        ),
+        "MaxPower              896mA"
+    );
+    //plugged into a usb 2 port it runs at high speed and counts in 2mA
+    assert_eq!(
+        max_power(
+            LsusbDump::new(&root)
+                .with_speed(PortSpeed::HighSpeed)
+                .to_string()
+        ),
+        "MaxPower              224mA"
+    );
+}
+
+#[test]
+fn unrecognized_descriptors_are_dumped_as_bytes() {
+    //a cdc style functional descriptor on a vendor interface, nothing decodes it
+    let config = [
+        9, 0x02, 0, 0, 1, 1, 0, 0x80, 50, //
+        9, 0x04, 0, 0, 1, 0xff, 0, 0, 0, //
+        5, 0x24, 0x00, 0x10, 0x01, //
+        7, 0x05, 0x81, 0x03, 8, 0, 10,
+    ];
+    let root = parse(K120_DEVICE, &config);
+    let dump = root.to_string();
+    assert!(
+        dump.contains("\n      ** UNRECOGNIZED:  05 24 00 10 01\n"),
+        "{}",
+        dump
+    );
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "usb-ids")]
+
+use usb_descriptor_decoder::{descriptors::desc_device::Device, usb_ids::UsbIds};
+
+//the layout of upstream usb.ids, tabs nest products under vendors and subclasses and
+//protocols under classes
+const SAMPLE: &str = "\
+#
+# Version: 2024.07.04
+# Date:    2024-07-04 20:34:02
+#
+046d  Logitech, Inc.
+\t0825  Webcam C270
+\tc52b  Unifying Receiver
+054c  Sony Corp.
+\t05c4  DualShock 4 [CUH-ZCT1x]
+
+C 03  Human Interface Device
+\t00  No Subclass
+\t01  Boot Interface Subclass
+\t\t01  Keyboard
+\t\t02  Mouse
+C 0e  Video
+\t01  Video Control
+
+# List of known device classes, subclasses and protocols
+AT 0001  Audio Terminal
+HID 21  HID
+\t01  not a product
+R 00  Undefined
+";
+
+#[test]
+fn parse_header_and_vendors() {
+    let ids = UsbIds::parse(SAMPLE);
+    assert_eq!(ids.version(), Some("2024.07.04"));
+    assert_eq!(ids.date(), Some("2024-07-04 20:34:02"));
+
+    assert_eq!(ids.vendor_name(0x046d), Some("Logitech, Inc."));
+    assert_eq!(ids.product_name(0x046d, 0xc52b), Some("Unifying Receiver"));
+    assert_eq!(
+        ids.product_name(0x054c, 0x05c4),
+        Some("DualShock 4 [CUH-ZCT1x]")
+    );
+    assert_eq!(ids.vendor(0x046d).unwrap().products.len(), 2);
+    //a product of another vendor is not found under this one
+    assert_eq!(ids.product_name(0x054c, 0x0825), None);
+    assert_eq!(ids.vendor_name(0x1234), None);
+}
+
+#[test]
+fn parse_classes() {
+    let ids = UsbIds::parse(SAMPLE);
+    assert_eq!(
+        ids.class_names(0x03, 0x01, 0x02),
+        (
+            Some("Human Interface Device"),
+            Some("Boot Interface Subclass"),
+            Some("Mouse")
+        )
+    );
+    assert_eq!(ids.subclass_name(0x03, 0x00), Some("No Subclass"));
+    assert_eq!(ids.protocol_name(0x03, 0x00, 0x01), None);
+    assert_eq!(ids.subclass_name(0x0e, 0x01), Some("Video Control"));
+    assert_eq!(ids.class_name(0xff), None);
+}
+
+#[test]
+fn other_lists_are_skipped() {
+    let ids = UsbIds::parse(SAMPLE);
+    //"AT 0001", "HID 21" and "R 00" are neither vendors nor classes, nor are their children
+    assert_eq!(ids.vendor_name(0x0001), None);
+    assert_eq!(ids.vendor_name(0x0021), None);
+    assert_eq!(ids.class_name(0x00), None);
+    assert!(!ids.vendor(0x054c).unwrap().products.contains_key(&0x01));
+
+    let unversioned = UsbIds::parse("0403  Future Technology Devices International, Ltd\n");
+    assert_eq!((unversioned.version(), unversioned.date()), (None, None));
+}
+
+#[test]
+fn device_names() {
+    let ids = UsbIds::parse(SAMPLE);
+    let device = Device {
+        vendor: 0x046d,
+        product_id: 0x0825,
+        ..Default::default()
+    };
+    assert_eq!(
+        ids.device_names(&device),
+        (Some("Logitech, Inc."), Some("Webcam C270"))
+    );
+}
+
+#[test]
+fn compressed_and_embedded() {
+    assert!(UsbIds::from_compressed(b"not zlib").is_err());
+    //latin-1 names do not make the whole file unreadable
+    let lossy = UsbIds::from_bytes(b"1234  Caf\xe9 Ltd\n\t0001  Widget\n");
+    assert_eq!(lossy.product_name(0x1234, 0x0001), Some("Widget"));
+
+    let embedded = UsbIds::embedded();
+    assert_eq!(embedded.vendor_name(0x054c), Some("Sony Corp."));
+    assert_eq!(embedded.class_name(0x03), Some("Human Interface Device"));
+}
@@ -0,0 +1,137 @@
+use usb_descriptor_decoder::descriptors::desc_uvc::{
+    uvc_controls::{CameraControl, EncodingControl, MediaTransportControl},
+    uvc_interfaces::{UVCControlInterface, UVCTerminalType},
+};
+
+#[test]
+fn camera_terminal() {
+    //no optical zoom, auto exposure, exposure time, focus and auto focus
+    let raw = [
+        0x12, 0x24, 0x02, 0x01, 0x01, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03,
+        0x2A, 0x00, 0x02,
+    ];
+    let UVCControlInterface::CameraTerminal(terminal) = UVCControlInterface::from_u8_array(&raw)
+    else {
+        panic!("expected a camera terminal");
+    };
+    assert_eq!((terminal.terminal_id, terminal.terminal_type), (1, 0x0201));
+    assert_eq!(terminal.control_size, 3);
+    assert_eq!(
+        terminal.controls(),
+        [
+            CameraControl::AutoExposureMode,
+            CameraControl::ExposureTimeAbsolute,
+            CameraControl::FocusAbsolute,
+            CameraControl::FocusAuto,
+        ]
+    );
+}
+
+#[test]
+fn connector_terminals() {
+    let composite = [0x08, 0x24, 0x02, 0x03, 0x01, 0x04, 0x00, 0x05];
+    let UVCControlInterface::ConnectorTerminal(terminal) =
+        UVCControlInterface::from_u8_array(&composite)
+    else {
+        panic!("expected a connector terminal");
+    };
+    assert_eq!(terminal.connector(), UVCTerminalType::COMPOSITE_CONNECTOR);
+    assert_eq!(
+        (terminal.terminal_id, terminal.string_index_terminal),
+        (3, 5)
+    );
+
+    let svideo = [0x08, 0x24, 0x02, 0x04, 0x02, 0x04, 0x00, 0x00];
+    let UVCControlInterface::ConnectorTerminal(terminal) =
+        UVCControlInterface::from_u8_array(&svideo)
+    else {
+        panic!("expected a connector terminal");
+    };
+    assert_eq!(terminal.connector(), UVCTerminalType::SVIDEO_CONNECTOR);
+
+    //vendor specific input terminals keep whatever follows iTerminal
+    let vendor = [0x0A, 0x24, 0x02, 0x05, 0x00, 0x02, 0x00, 0x00, 0xAA, 0xBB];
+    let UVCControlInterface::InputTerminal(terminal) = UVCControlInterface::from_u8_array(&vendor)
+    else {
+        panic!("expected a plain input terminal");
+    };
+    assert_eq!(terminal.terminal_type, 0x0200);
+    assert_eq!(terminal.reserved, [0xAA, 0xBB]);
+}
+
+#[test]
+fn selector_unit() {
+    let raw = [0x08, 0x24, 0x04, 0x04, 0x02, 0x01, 0x03, 0x00];
+    let UVCControlInterface::SelectorUnit(unit) = UVCControlInterface::from_u8_array(&raw) else {
+        panic!("expected a selector unit");
+    };
+    assert_eq!(unit.unit_id, 4);
+    assert_eq!(unit.nr_in_pins, 2);
+    assert_eq!(unit.source_ids, [1, 3]);
+    assert_eq!(unit.selector, 0);
+}
+
+#[test]
+fn encoding_unit() {
+    //resolution, rate control and average bit rate, only the bit rate changes while streaming
+    let raw = [
+        0x0D, 0x24, 0x07, 0x06, 0x04, 0x00, 0x03, 0x64, 0x00, 0x00, 0x40, 0x00, 0x00,
+    ];
+    let UVCControlInterface::EncodingUnit(unit) = UVCControlInterface::from_u8_array(&raw) else {
+        panic!("expected an encoding unit");
+    };
+    assert_eq!((unit.unit_id, unit.source_id), (6, 4));
+    assert_eq!(
+        unit.controls(),
+        [
+            EncodingControl::VideoResolution,
+            EncodingControl::RateControlMode,
+            EncodingControl::AverageBitRate,
+        ]
+    );
+    assert_eq!(unit.controls_runtime(), [EncodingControl::AverageBitRate]);
+}
+
+#[test]
+fn media_transport_terminals() {
+    //input: every transport control, a five byte transport mode bitmap
+    let input = [
+        0x10, 0x24, 0x02, 0x02, 0x02, 0x02, 0x00, 0x00, 0x01, 0x0F, 0x05, 0x01, 0x02, 0x03, 0x04,
+        0x05,
+    ];
+    let UVCControlInterface::MediaTransportTerminal(terminal) =
+        UVCControlInterface::from_u8_array(&input)
+    else {
+        panic!("expected a media transport terminal");
+    };
+    assert_eq!(terminal.source_id, None);
+    assert_eq!(terminal.string_index_terminal, 0);
+    assert_eq!(
+        terminal.controls(),
+        [
+            MediaTransportControl::TransportControl,
+            MediaTransportControl::AbsoluteTrackNumber,
+            MediaTransportControl::MediaInformation,
+            MediaTransportControl::TimeCodeInformation,
+        ]
+    );
+    assert_eq!(terminal.transport_modes, [1, 2, 3, 4, 5]);
+
+    //output: bSourceID before iTerminal, no transport modes
+    let output = [
+        0x0B, 0x24, 0x03, 0x07, 0x02, 0x03, 0x00, 0x02, 0x06, 0x01, 0x08,
+    ];
+    let UVCControlInterface::MediaTransportTerminal(terminal) =
+        UVCControlInterface::from_u8_array(&output)
+    else {
+        panic!("expected a media transport terminal");
+    };
+    assert_eq!(terminal.source_id, Some(2));
+    assert_eq!(terminal.string_index_terminal, 6);
+    assert_eq!(
+        terminal.controls(),
+        [MediaTransportControl::TimeCodeInformation]
+    );
+    assert_eq!(terminal.transport_mode_size, 0);
+    assert!(terminal.transport_modes.is_empty());
+}
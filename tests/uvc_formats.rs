@@ -0,0 +1,118 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_uvc::{
+        uvc_formats::{guid_format_name, pixel_format, PlaneLayout, UVC_PIXEL_FORMATS},
+        uvc_interfaces::{UVCInterface, UVCStreamingInterface},
+        uvc_streaming::UvcStreamingInterface,
+    },
+    USBDescriptor,
+};
+
+const GUID_TAIL: [u8; 12] = [
+    0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xaa, 0x00, 0x38, 0x9b, 0x71,
+];
+
+fn guid(fourcc: &[u8; 4]) -> [u8; 16] {
+    let mut guid = [0; 16];
+    guid[..4].copy_from_slice(fourcc);
+    guid[4..].copy_from_slice(&GUID_TAIL);
+    guid
+}
+
+#[test]
+fn common_guids() {
+    let yuy2 = pixel_format(&guid(b"YUY2")).unwrap();
+    assert_eq!(yuy2.fourcc_str(), "YUY2");
+    assert_eq!(
+        (yuy2.bits_per_pixel, yuy2.layout),
+        (16, PlaneLayout::Packed)
+    );
+
+    let nv12 = pixel_format(&guid(b"NV12")).unwrap();
+    assert_eq!((nv12.bits_per_pixel, nv12.layout.planes()), (12, 2));
+    assert_eq!(pixel_format(&guid(b"I420")).unwrap().layout.planes(), 3);
+
+    let h264 = pixel_format(&guid(b"H264")).unwrap();
+    assert_eq!(
+        (h264.bits_per_pixel, h264.layout),
+        (0, PlaneLayout::Compressed)
+    );
+    //HEVC is an alias of H265
+    assert_eq!(
+        pixel_format(&guid(b"HEVC")).unwrap().name,
+        pixel_format(&guid(b"H265")).unwrap().name
+    );
+}
+
+#[test]
+fn directshow_rgb24() {
+    //MEDIASUBTYPE_RGB24 does not follow the fourcc pattern
+    let rgb24 = [
+        0x7d, 0xeb, 0x36, 0xe4, 0x4f, 0x52, 0xce, 0x11, 0x9f, 0x53, 0x00, 0x20, 0xaf, 0x0b, 0xa7,
+        0x70,
+    ];
+    let format = pixel_format(&rgb24).unwrap();
+    assert_eq!(format.fourcc_str(), "BGR3");
+    assert_eq!(format.bits_per_pixel, 24);
+    assert_eq!(guid_format_name(&rgb24), "BGR3");
+}
+
+#[test]
+fn table_is_consistent() {
+    for (i, format) in UVC_PIXEL_FORMATS.iter().enumerate() {
+        if format.guid[4..] == GUID_TAIL {
+            assert_eq!(format.guid[..4], format.fourcc, "{}", format.name);
+        }
+        assert!(
+            UVC_PIXEL_FORMATS[i + 1..]
+                .iter()
+                .all(|other| other.guid != format.guid),
+            "{} is listed twice",
+            format.name
+        );
+        assert_eq!(
+            format.bits_per_pixel == 0,
+            format.layout == PlaneLayout::Compressed,
+            "{}",
+            format.name
+        );
+    }
+}
+
+#[test]
+fn unknown_guids() {
+    let unknown = guid(b"ABCD");
+    assert!(pixel_format(&unknown).is_none());
+    assert_eq!(
+        guid_format_name(&unknown),
+        "{44434241-0000-0010-8000-00aa00389b71}"
+    );
+    assert_eq!(guid_format_name(&guid(b"MJPG")), "MJPG");
+}
+
+#[test]
+fn formats_without_a_guid() {
+    //MJPEG implies its fourcc by descriptor subtype
+    let streaming = |raw: &[u8]| {
+        USBDescriptor::UVCInterface(UVCInterface::Streaming(
+            UVCStreamingInterface::from_u8_array(raw),
+        ))
+    };
+    let mut uncompressed = vec![0x1B, 0x24, 0x04, 0x02, 0x00];
+    uncompressed.extend_from_slice(&guid(b"NV12"));
+    uncompressed.extend_from_slice(&[12, 0x01, 0x00, 0x00, 0x00, 0x00]);
+    let descriptors = [
+        streaming(&[
+            0x0F, 0x24, 0x01, 0x02, 0x00, 0x01, 0x81, 0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00,
+            0x00,
+        ]),
+        streaming(&[
+            0x0B, 0x24, 0x06, 0x01, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00, 0x00,
+        ]),
+        streaming(&uncompressed),
+    ];
+    let interface = UvcStreamingInterface::from_descriptors(&descriptors).unwrap();
+    let mjpeg = interface.format(1).unwrap().pixel_format().unwrap();
+    assert_eq!(mjpeg.fourcc_str(), "MJPG");
+    let nv12 = interface.format(2).unwrap().pixel_format().unwrap();
+    assert_eq!(nv12.fourcc_str(), "NV12");
+}
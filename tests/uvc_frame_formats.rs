@@ -0,0 +1,204 @@
+use usb_descriptor_decoder::descriptors::desc_uvc::uvc_interfaces::{
+    FrameInterval, H264MvcCapabilities, H264Profile, H264SvcCapabilities, UVCStreamingInterface,
+};
+
+const H264_GUID: &[u8; 16] = b"H264\x00\x00\x10\x00\x80\x00\x00\xaa\x00\x38\x9b\x71";
+
+#[test]
+fn frame_based_format() {
+    let mut raw = vec![0x1C, 0x24, 0x10, 0x01, 0x02];
+    raw.extend_from_slice(H264_GUID);
+    raw.extend_from_slice(&[0x10, 0x01, 16, 9, 0x00, 0x00, 0x01]);
+    let UVCStreamingInterface::FormatFrameBased(format) =
+        UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected a frame based format");
+    };
+    assert_eq!((format.format_index, format.num_frame_descriptors), (1, 2));
+    assert_eq!(format.pixel_format().unwrap().fourcc_str(), "H264");
+    assert_eq!((format.bits_per_pixel, format.default_frame_index), (16, 1));
+    assert_eq!((format.aspect_ratio_x, format.aspect_ratio_y), (16, 9));
+    assert_eq!(format.variable_size, 1);
+}
+
+#[test]
+fn frame_based_frame() {
+    //1920x1080, continuous intervals from 30 down to 15 fps
+    let mut raw = vec![0x26, 0x24, 0x11, 0x01, 0x00];
+    raw.extend_from_slice(&1920u16.to_le_bytes());
+    raw.extend_from_slice(&1080u16.to_le_bytes());
+    raw.extend_from_slice(&1_000_000u32.to_le_bytes());
+    raw.extend_from_slice(&8_000_000u32.to_le_bytes());
+    raw.extend_from_slice(&333_333u32.to_le_bytes());
+    raw.push(0);
+    raw.extend_from_slice(&0u32.to_le_bytes());
+    for interval in [333_333u32, 666_666, 333_333] {
+        raw.extend_from_slice(&interval.to_le_bytes());
+    }
+    let UVCStreamingInterface::FrameFrameBased(frame) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected a frame based frame");
+    };
+    assert_eq!((frame.width, frame.height), (1920, 1080));
+    assert_eq!(
+        (frame.min_bit_rate, frame.max_bit_rate),
+        (1_000_000, 8_000_000)
+    );
+    assert_eq!(frame.default_frame_interval, 333_333);
+    assert_eq!(frame.bytes_per_line, 0);
+    let FrameInterval::Continuous((min, max, step)) = frame.frame_interval else {
+        panic!("expected continuous intervals");
+    };
+    assert_eq!((min, max, step), (333_333, 666_666, 333_333));
+}
+
+#[test]
+fn h264_format() {
+    //two simulcast streams without scalability, one with temporal scalability
+    let mut raw = vec![
+        0x34, 0x24, 0x13, 0x01, 0x03, 0x01, 0x02, 0x0F, 0x03, 0x00, 0x00, 0x1F,
+    ];
+    for mb_per_sec in [[245, 120, 0, 0], [245, 0, 0, 0], [0; 4], [0; 4], [0; 4]] {
+        mb_per_sec
+            .iter()
+            .for_each(|value: &u16| raw.extend_from_slice(&value.to_le_bytes()));
+    }
+    let UVCStreamingInterface::FormatH264(format) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected an H.264 format");
+    };
+    assert_eq!((format.format_index, format.num_frame_descriptors), (1, 3));
+    assert_eq!(format.supported_slice_modes, 0x0F);
+    assert_eq!(format.supported_rate_control_modes, 0x1F);
+    assert_eq!(format.max_mb_per_sec_no_scalability, [245, 120, 0, 0]);
+    assert_eq!(format.max_mb_per_sec_temporal_scalability, [245, 0, 0, 0]);
+    assert_eq!(format.max_simulcast_streams(), 2);
+
+    raw[2] = 0x15;
+    assert!(matches!(
+        UVCStreamingInterface::from_u8_array(&raw),
+        UVCStreamingInterface::FormatH264Simulcast(_)
+    ));
+}
+
+#[test]
+fn h264_frame() {
+    //1280x720 constrained high at level 3.1, two discrete intervals
+    let mut raw = vec![0x34, 0x24, 0x14, 0x02];
+    raw.extend_from_slice(&1280u16.to_le_bytes());
+    raw.extend_from_slice(&720u16.to_le_bytes());
+    raw.extend_from_slice(&1u16.to_le_bytes());
+    raw.extend_from_slice(&1u16.to_le_bytes());
+    raw.extend_from_slice(&0x640Cu16.to_le_bytes());
+    raw.push(31);
+    raw.extend_from_slice(&0u16.to_le_bytes());
+    raw.extend_from_slice(&0x0001_0003u32.to_le_bytes());
+    raw.extend_from_slice(&0x0003u16.to_le_bytes());
+    //two temporal layers, three spatial layers
+    raw.extend_from_slice(&((1u32 << 11) * 2 + 1).to_le_bytes());
+    //three views
+    raw.extend_from_slice(&(2u32 << 3).to_le_bytes());
+    raw.extend_from_slice(&2_000_000u32.to_le_bytes());
+    raw.extend_from_slice(&10_000_000u32.to_le_bytes());
+    raw.extend_from_slice(&333_333u32.to_le_bytes());
+    raw.push(2);
+    raw.extend_from_slice(&333_333u32.to_le_bytes());
+    raw.extend_from_slice(&666_666u32.to_le_bytes());
+    let UVCStreamingInterface::FrameH264(frame) = UVCStreamingInterface::from_u8_array(&raw) else {
+        panic!("expected an H.264 frame");
+    };
+    assert_eq!(
+        (frame.frame_index, frame.width, frame.height),
+        (2, 1280, 720)
+    );
+    assert_eq!(frame.h264_profile(), Some(H264Profile::ConstrainedHigh));
+    assert_eq!(frame.level(), (3, 1));
+    assert_eq!(frame.supported_usages, 0x0001_0003);
+    assert_eq!(
+        frame.svc_capabilities(),
+        H264SvcCapabilities {
+            max_temporal_layers: 2,
+            rewrite_support: false,
+            max_cgs_layers: 1,
+            mgs_sublayers: 0,
+            additional_snr_scalability: false,
+            max_spatial_layers: 3,
+        }
+    );
+    assert_eq!(
+        frame.mvc_capabilities(),
+        H264MvcCapabilities {
+            max_temporal_layers: 1,
+            max_views: 3,
+        }
+    );
+    assert_eq!(frame.max_bit_rate, 10_000_000);
+    let FrameInterval::Discrete(intervals) = frame.frame_interval else {
+        panic!("expected discrete intervals");
+    };
+    assert_eq!(intervals, [333_333, 666_666]);
+}
+
+#[test]
+fn vp8_format() {
+    let mut raw = vec![
+        0x0D, 0x24, 0x16, 0x02, 0x01, 0x01, 0x03, 0x04, 0x01, 0x00, 0x03,
+    ];
+    raw.extend_from_slice(&108u16.to_le_bytes());
+    let UVCStreamingInterface::FormatVp8(format) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected a VP8 format");
+    };
+    assert_eq!((format.format_index, format.num_frame_descriptors), (2, 1));
+    assert_eq!(format.max_codec_config_delay, 3);
+    assert_eq!(format.supported_partition_count, 4);
+    assert_eq!(format.supported_rate_control_modes, 0x03);
+    assert_eq!(format.max_mb_per_sec, 108);
+
+    raw[2] = 0x18;
+    assert!(matches!(
+        UVCStreamingInterface::from_u8_array(&raw),
+        UVCStreamingInterface::FormatVp8Simulcast(_)
+    ));
+}
+
+#[test]
+fn vp8_frame() {
+    let mut raw = vec![0x27, 0x24, 0x17, 0x01];
+    raw.extend_from_slice(&640u16.to_le_bytes());
+    raw.extend_from_slice(&480u16.to_le_bytes());
+    raw.extend_from_slice(&0x0000_0001u32.to_le_bytes());
+    raw.extend_from_slice(&0x0001u16.to_le_bytes());
+    raw.extend_from_slice(&0u32.to_le_bytes());
+    raw.extend_from_slice(&500_000u32.to_le_bytes());
+    raw.extend_from_slice(&4_000_000u32.to_le_bytes());
+    raw.extend_from_slice(&333_333u32.to_le_bytes());
+    raw.push(2);
+    raw.extend_from_slice(&333_333u32.to_le_bytes());
+    raw.extend_from_slice(&1_000_000u32.to_le_bytes());
+    let UVCStreamingInterface::FrameVp8(frame) = UVCStreamingInterface::from_u8_array(&raw) else {
+        panic!("expected a VP8 frame");
+    };
+    assert_eq!((frame.width, frame.height), (640, 480));
+    assert_eq!(
+        (frame.min_bit_rate, frame.max_bit_rate),
+        (500_000, 4_000_000)
+    );
+    assert_eq!(frame.num_frame_intervals, 2);
+    let FrameInterval::Discrete(intervals) = frame.frame_interval else {
+        panic!("expected discrete intervals");
+    };
+    assert_eq!(intervals, [333_333, 1_000_000]);
+}
+
+#[test]
+fn unknown_subtypes_are_kept() {
+    for subtype in [0x00, 0x08, 0x20] {
+        let raw = [0x05, 0x24, subtype, 0xAA, 0xBB];
+        let UVCStreamingInterface::Unknown(kept) = UVCStreamingInterface::from_u8_array(&raw)
+        else {
+            panic!("expected subtype {:#04x} to be kept raw", subtype);
+        };
+        assert_eq!(kept, raw);
+    }
+}
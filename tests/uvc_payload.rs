@@ -0,0 +1,196 @@
+use usb_descriptor_decoder::descriptors::desc_uvc::{
+    uvc_payload::{UvcFrameAssembler, UvcPayloadError, UvcPayloadHeader, UvcTransferMode},
+    uvc_probe::UvcProbeCommit,
+};
+
+//a 2 byte header with EOH set in front of `data`
+fn packet(header_info: u8, data: &[u8]) -> Vec<u8> {
+    let mut packet = vec![2, UvcPayloadHeader::EOH | header_info];
+    packet.extend_from_slice(data);
+    packet
+}
+
+fn frames(assembler: &mut UvcFrameAssembler) -> Vec<Vec<u8>> {
+    core::iter::from_fn(|| assembler.take_frame())
+        .map(|frame| frame.data)
+        .collect()
+}
+
+const FID: u8 = UvcPayloadHeader::FID;
+const EOF: u8 = UvcPayloadHeader::EOF;
+const ERR: u8 = UvcPayloadHeader::ERR;
+
+#[test]
+fn payload_header() {
+    let header = UvcPayloadHeader::from_u8_array(&[
+        12, 0x8D, 0x01, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xAA,
+    ])
+    .unwrap();
+    assert_eq!(header.pts, Some(1));
+    //the usb frame number is 11 bits, the rest of the word is reserved
+    assert_eq!(header.scr, Some((2, 0x7FF)));
+    assert!(header.fid() && !header.eof() && !header.error());
+
+    let scr_only = UvcPayloadHeader::from_u8_array(&[8, 0x08, 0x05, 0, 0, 0, 0x07, 0x00]).unwrap();
+    assert_eq!((scr_only.pts, scr_only.scr), (None, Some((5, 7))));
+}
+
+#[test]
+fn malformed_header_length() {
+    assert_eq!(
+        UvcPayloadHeader::from_u8_array(&[9, 0x80]),
+        Err(UvcPayloadError::InvalidHeaderLength(9, 2))
+    );
+    assert_eq!(
+        UvcPayloadHeader::from_u8_array(&[1, 0x80]),
+        Err(UvcPayloadError::InvalidHeaderLength(1, 2))
+    );
+    //PTS and SCR announced, but only room for PTS
+    assert_eq!(
+        UvcPayloadHeader::from_u8_array(&[6, 0x8C, 0, 0, 0, 0]),
+        Err(UvcPayloadError::HeaderTooShort(6))
+    );
+
+    //the frame the bad packet belonged to is incomplete and gets dropped
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Isochronous);
+    assembler.push(&packet(0, b"ab")).unwrap();
+    assert_eq!(
+        assembler.push(&[0x40, 0x80, 0xFF]),
+        Err(UvcPayloadError::InvalidHeaderLength(0x40, 3))
+    );
+    assembler.push(&packet(EOF, b"cd")).unwrap();
+    assembler.push(&packet(FID | EOF, b"ef")).unwrap();
+    assert_eq!(frames(&mut assembler), [b"ef"]);
+    assert_eq!(assembler.dropped_frames(), 1);
+}
+
+#[test]
+fn isochronous_fid_toggle_without_eof() {
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Isochronous);
+    //empty packets carry no header and are skipped
+    for packet in [
+        packet(0, b"ab"),
+        vec![],
+        packet(0, b"cd"),
+        packet(FID, b"ef"),
+    ] {
+        assembler.push(&packet).unwrap();
+    }
+    assert_eq!(frames(&mut assembler), [b"abcd"]);
+    //every toggle ends a frame, in both directions
+    assembler.push(&packet(0, b"gh")).unwrap();
+    assembler.push(&packet(FID, b"ij")).unwrap();
+    assembler.push(&packet(0, b"kl")).unwrap();
+    assert_eq!(frames(&mut assembler), [b"ef", b"gh", b"ij"]);
+}
+
+#[test]
+fn isochronous_error_frame_is_dropped() {
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Isochronous);
+    for packet in [
+        packet(0, b"ab"),
+        packet(ERR, b"c"),
+        packet(EOF, b"d"),
+        packet(FID, b"ef"),
+        packet(FID | EOF, b"g"),
+    ] {
+        assembler.push(&packet).unwrap();
+    }
+    assert_eq!(frames(&mut assembler), [b"efg"]);
+    assert_eq!(assembler.dropped_frames(), 1);
+}
+
+#[test]
+fn isochronous_stale_payloads_after_eof() {
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Isochronous);
+    //some cameras keep sending the closed frame's fid until the next frame starts
+    for packet in [
+        packet(0, b"ab"),
+        packet(EOF, b"c"),
+        packet(0, b"xx"),
+        packet(EOF, b"yy"),
+        packet(FID, b"de"),
+        packet(FID | EOF, b"f"),
+    ] {
+        assembler.push(&packet).unwrap();
+    }
+    assert_eq!(frames(&mut assembler), [&b"abc"[..], b"def"]);
+    assert_eq!(assembler.dropped_frames(), 0);
+}
+
+#[test]
+fn frame_size_limits() {
+    //uncompressed frames of a known size
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Isochronous).frame_size(4);
+    for packet in [
+        packet(0, b"abcd"),
+        packet(FID | EOF, b"abc"),
+        packet(EOF, b"abcd"),
+    ] {
+        assembler.push(&packet).unwrap();
+    }
+    assert_eq!(frames(&mut assembler), [b"abcd", b"abcd"]);
+    assert_eq!(assembler.dropped_frames(), 1);
+
+    //dwMaxVideoFrameSize of the committed probe
+    let probe = UvcProbeCommit {
+        max_video_frame_size: 3,
+        ..Default::default()
+    };
+    let mut assembler = UvcFrameAssembler::from_probe(&probe, true, 0);
+    assembler.push(&packet(EOF, b"abcd")).unwrap();
+    assert!(assembler.take_frame().is_none());
+    assert_eq!(assembler.dropped_frames(), 1);
+}
+
+#[test]
+fn bulk_payloads_split_across_transfers() {
+    //payloads of up to 8 bytes arrive in 4 byte transfers, only the first has a header
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Bulk {
+        max_payload_transfer_size: 8,
+        transfer_size: 4,
+    });
+    let transfers: [&[u8]; 5] = [
+        //no EOF, the payload ends at dwMaxPayloadTransferSize
+        &[2, 0x80, b'a', b'b'],
+        b"cdef",
+        //EOF in the header only counts once the rest of the payload arrived
+        &[2, 0x82, b'g', b'h'],
+        b"ijkl",
+        //next frame in a single short transfer
+        &[2, 0x83, b'm'],
+    ];
+    for (i, transfer) in transfers.iter().enumerate() {
+        assembler.push(transfer).unwrap();
+        if i == 2 {
+            assert!(assembler.take_frame().is_none());
+        }
+    }
+    assert_eq!(frames(&mut assembler), [&b"abcdefghijkl"[..], b"m"]);
+}
+
+#[test]
+fn bulk_short_transfer_ends_payload() {
+    let mut assembler = UvcFrameAssembler::new(UvcTransferMode::Bulk {
+        max_payload_transfer_size: 1024,
+        transfer_size: 4,
+    });
+    let transfers: [&[u8]; 5] = [
+        &[2, 0x80, b'a', b'b'],
+        b"cd",
+        //a new payload with its own header, not data of the previous one
+        &[2, 0x80, b'e', b'f'],
+        b"g",
+        &[2, 0x82],
+    ];
+    for transfer in transfers {
+        assembler.push(transfer).unwrap();
+    }
+    assert_eq!(frames(&mut assembler), [b"abcdefg"]);
+
+    //a zero length transfer ends the payload just the same
+    assembler.push(&[2, 0x81, b'h', b'i']).unwrap();
+    assembler.push(&[]).unwrap();
+    assembler.push(&[2, 0x83, b'j']).unwrap();
+    assert_eq!(frames(&mut assembler), [b"hij"]);
+}
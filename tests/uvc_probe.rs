@@ -0,0 +1,268 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_interface::Interface,
+    desc_uvc::{
+        uvc_interfaces::{UVCInterface, UVCStreamingInterface},
+        uvc_probe::{select_alternate_setting, UvcProbeCommit},
+        uvc_streaming::{UvcStreamingError, UvcStreamingInterface},
+    },
+    parser::RawDescriptorParser,
+    topological_desc::{
+        TopologicalUSBDescriptorEndpoint, TopologicalUSBDescriptorFunction,
+        TopologicalUSBDescriptorRoot,
+    },
+    USBDescriptor,
+};
+
+type Alternates = [(
+    Interface,
+    Vec<USBDescriptor>,
+    Vec<TopologicalUSBDescriptorEndpoint>,
+)];
+
+//a superspeed streaming interface: zero bandwidth setting 0, setting 1 with a 1024 byte
+//isochronous endpoint bursting 16 packets, 24576 bytes per interval per its companion
+fn superspeed_streaming() -> TopologicalUSBDescriptorRoot {
+    let device = vec![
+        18, 0x01, 0x20, 0x03, 0xFF, 0x00, 0x00, 9, 0x34, 0x12, 0x78, 0x56, 0x00, 0x01, 0, 0, 0, 1,
+    ];
+    let mut config = vec![
+        9, 0x02, 0, 0, 1, 1, 0, 0x80, 50, //
+        9, 0x04, 1, 0, 0, 0xFF, 0x02, 0x00, 0, //
+        9, 0x04, 1, 1, 1, 0xFF, 0x02, 0x00, 0, //
+        7, 0x05, 0x81, 0x05, 0x00, 0x04, 1, //
+        6, 0x30, 0x0F, 0x00, 0x00, 0x60,
+    ];
+    let total_length = config.len() as u16;
+    config[2..4].copy_from_slice(&total_length.to_le_bytes());
+    //like a transfer buffer, the parser stops at the zeroed tail
+    config.extend_from_slice(&[0, 0]);
+
+    let mut parser = RawDescriptorParser::new(device);
+    parser.single_state_cycle();
+    parser.append_config(config);
+    parser.summarize()
+}
+
+fn alternates(root: &TopologicalUSBDescriptorRoot) -> &Alternates {
+    let TopologicalUSBDescriptorFunction::Interface(alternates) = &root.device.child[0].child[0]
+    else {
+        panic!("expected a plain interface");
+    };
+    alternates
+}
+
+#[test]
+fn superspeed_alternate_setting() {
+    let root = superspeed_streaming();
+    let alternates = alternates(&root);
+    let TopologicalUSBDescriptorEndpoint::Standard(endpoint) = alternates[1].2[0].clone() else {
+        panic!("expected a standard endpoint");
+    };
+    let companion = endpoint.ssc.unwrap();
+    assert_eq!((companion.kind, companion.max_burst), (0x30, 0x0F));
+    assert_eq!(endpoint.max_bytes_per_interval(), 24576);
+
+    //wMaxPacketSize alone would only allow 1024 bytes
+    let (alternate_setting, _) = select_alternate_setting(alternates, 20000)
+        .unwrap()
+        .unwrap();
+    assert_eq!(alternate_setting, 1);
+}
+
+#[test]
+fn payload_too_large() {
+    let root = superspeed_streaming();
+    //the largest isochronous setting moves 24576 bytes per interval
+    assert_eq!(
+        select_alternate_setting(alternates(&root), 30000).err(),
+        Some(UvcStreamingError::PayloadTooLarge(30000, 24576))
+    );
+    //bulk only interfaces stay on setting 0
+    assert_eq!(
+        select_alternate_setting(&alternates(&root)[..1], 30000)
+            .unwrap()
+            .map(|(alternate_setting, _)| alternate_setting),
+        None
+    );
+}
+
+#[test]
+fn len_for() {
+    assert_eq!(UvcProbeCommit::len_for(0x0100), 26);
+    assert_eq!(UvcProbeCommit::len_for(0x0109), 26);
+    assert_eq!(UvcProbeCommit::len_for(0x010a), 34);
+    assert_eq!(UvcProbeCommit::len_for(0x0110), 34);
+    assert_eq!(UvcProbeCommit::len_for(0x014f), 34);
+    assert_eq!(UvcProbeCommit::len_for(0x0150), 48);
+    assert_eq!(UvcProbeCommit::len_for(0x0200), 48);
+}
+
+fn every_field() -> UvcProbeCommit {
+    UvcProbeCommit {
+        hint: 0x0001,
+        format_index: 2,
+        frame_index: 3,
+        frame_interval: 333_333,
+        key_frame_rate: 4,
+        p_frame_rate: 5,
+        comp_quality: 6,
+        comp_window_size: 7,
+        delay: 8,
+        max_video_frame_size: 614_400,
+        max_payload_transfer_size: 3072,
+        clock_frequency: 48_000_000,
+        framing_info: 0x03,
+        prefered_version: 1,
+        min_version: 1,
+        max_version: 2,
+        usage: 1,
+        bit_depth_luma: 8,
+        settings: 0x0F,
+        max_number_of_ref_frames_plus1: 2,
+        rate_control_modes: 0x0321,
+        layout_per_stream: 0x0001_0002_0003_0004,
+    }
+}
+
+#[test]
+fn uvc_10_probe() {
+    let raw = every_field().to_u8_array(0x0100);
+    assert_eq!(raw.len(), 26);
+    assert_eq!(&raw[..4], [0x01, 0x00, 2, 3]);
+    assert_eq!(raw[4..8], 333_333u32.to_le_bytes());
+    assert_eq!(raw[18..22], 614_400u32.to_le_bytes());
+    assert_eq!(raw[22..26], 3072u32.to_le_bytes());
+
+    //the UVC 1.1 and 1.5 fields were not sent and decode as 0
+    assert_eq!(
+        UvcProbeCommit::from_u8_array(&raw),
+        UvcProbeCommit {
+            clock_frequency: 0,
+            framing_info: 0,
+            prefered_version: 0,
+            min_version: 0,
+            max_version: 0,
+            usage: 0,
+            bit_depth_luma: 0,
+            settings: 0,
+            max_number_of_ref_frames_plus1: 0,
+            rate_control_modes: 0,
+            layout_per_stream: 0,
+            ..every_field()
+        }
+    );
+}
+
+#[test]
+fn uvc_11_probe() {
+    let raw = every_field().to_u8_array(0x0110);
+    assert_eq!(raw.len(), 34);
+    assert_eq!(raw[26..30], 48_000_000u32.to_le_bytes());
+    assert_eq!(&raw[30..34], [0x03, 1, 1, 2]);
+
+    assert_eq!(
+        UvcProbeCommit::from_u8_array(&raw),
+        UvcProbeCommit {
+            usage: 0,
+            bit_depth_luma: 0,
+            settings: 0,
+            max_number_of_ref_frames_plus1: 0,
+            rate_control_modes: 0,
+            layout_per_stream: 0,
+            ..every_field()
+        }
+    );
+}
+
+#[test]
+fn uvc_15_probe() {
+    let raw = every_field().to_u8_array(0x0150);
+    assert_eq!(raw.len(), 48);
+    assert_eq!(&raw[34..38], [1, 8, 0x0F, 2]);
+    assert_eq!(&raw[38..40], [0x21, 0x03]);
+    assert_eq!(raw[40..48], 0x0001_0002_0003_0004u64.to_le_bytes());
+    assert_eq!(UvcProbeCommit::from_u8_array(&raw), every_field());
+}
+
+fn streaming(raw: &[u8]) -> USBDescriptor {
+    USBDescriptor::UVCInterface(UVCInterface::Streaming(
+        UVCStreamingInterface::from_u8_array(raw),
+    ))
+}
+
+fn mjpeg_format(format_index: u8, num_frames: u8) -> USBDescriptor {
+    let mut raw = vec![0x0B, 0x24, 0x06, format_index, num_frames];
+    raw.extend_from_slice(&[0x01, 0x01, 0x00, 0x00, 0x00, 0x00]);
+    streaming(&raw)
+}
+
+fn mjpeg_frame(frame_index: u8, width: u16, height: u16, intervals: &[u32]) -> USBDescriptor {
+    let mut raw = vec![
+        26 + 4 * intervals.len() as u8,
+        0x24,
+        0x07,
+        frame_index,
+        0x00,
+    ];
+    raw.extend_from_slice(&width.to_le_bytes());
+    raw.extend_from_slice(&height.to_le_bytes());
+    let buffer_size = width as u32 * height as u32 * 2;
+    for value in [1_000_000, 2_000_000, buffer_size, intervals[0]] {
+        raw.extend_from_slice(&value.to_le_bytes());
+    }
+    raw.push(intervals.len() as u8);
+    intervals
+        .iter()
+        .for_each(|interval| raw.extend_from_slice(&interval.to_le_bytes()));
+    streaming(&raw)
+}
+
+//two MJPEG formats sharing a 640x480 frame, a 600x800 frame ahead of an exact 800x600 one
+fn two_formats() -> UvcStreamingInterface {
+    let mut header = vec![0x0F, 0x24, 0x01, 0x02, 0x00, 0x01, 0x81];
+    header.extend_from_slice(&[0x00, 0x03, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00]);
+    let descriptors = [
+        streaming(&header),
+        mjpeg_format(1, 2),
+        mjpeg_frame(1, 640, 480, &[333_333, 666_666]),
+        mjpeg_frame(2, 600, 800, &[333_333]),
+        mjpeg_format(2, 2),
+        mjpeg_frame(1, 640, 480, &[333_333]),
+        mjpeg_frame(2, 800, 600, &[666_666]),
+    ];
+    UvcStreamingInterface::from_descriptors(&descriptors).unwrap()
+}
+
+#[test]
+fn negotiate() {
+    let interface = two_formats();
+
+    //both formats have 640x480 at 30 fps, the earlier one wins
+    let probe = interface.negotiate(None, 640, 480, 30).unwrap();
+    assert_eq!((probe.format_index, probe.frame_index), (1, 1));
+    assert_eq!(probe.frame_interval, 333_333);
+    assert_eq!(probe.hint, UvcProbeCommit::HINT_FRAME_INTERVAL);
+    assert_eq!(probe.max_video_frame_size, 640 * 480 * 2);
+
+    //the interval closest to 15 fps
+    let probe = interface.negotiate(None, 640, 480, 15).unwrap();
+    assert_eq!(probe.frame_interval, 666_666);
+
+    //narrowed to the second format
+    let probe = interface.negotiate(Some(2), 640, 480, 30).unwrap();
+    assert_eq!((probe.format_index, probe.frame_index), (2, 1));
+
+    //an exact size beats an earlier frame of the same area and a better interval
+    let probe = interface.negotiate(None, 800, 600, 30).unwrap();
+    assert_eq!((probe.format_index, probe.frame_index), (2, 2));
+    assert_eq!(probe.frame_interval, 666_666);
+
+    //otherwise the closest area
+    let probe = interface.negotiate(None, 700, 500, 30).unwrap();
+    assert_eq!((probe.format_index, probe.frame_index), (1, 1));
+
+    assert_eq!(
+        interface.negotiate(Some(9), 640, 480, 30),
+        Err(UvcStreamingError::NoMatchingFrame)
+    );
+}
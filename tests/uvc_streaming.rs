@@ -0,0 +1,162 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_uvc::{
+        uvc_interfaces::{UVCInterface, UVCStreamingInterface},
+        uvc_streaming::{UvcStreamingError, UvcStreamingHeader, UvcStreamingInterface},
+    },
+    USBDescriptor,
+};
+
+fn streaming(raw: &[u8]) -> USBDescriptor {
+    USBDescriptor::UVCInterface(UVCInterface::Streaming(
+        UVCStreamingInterface::from_u8_array(raw),
+    ))
+}
+
+fn input_header(num_formats: u8) -> USBDescriptor {
+    let mut raw = vec![13 + num_formats, 0x24, 0x01, num_formats, 0x00, 0x01, 0x81];
+    //bmInfo, bTerminalLink, bStillCaptureMethod, the triggers, bControlSize
+    raw.extend_from_slice(&[0x00, 0x03, 0x00, 0x00, 0x00, 0x01]);
+    raw.resize(raw[0] as usize, 0x00);
+    streaming(&raw)
+}
+
+fn mjpeg_format(format_index: u8, num_frames: u8) -> USBDescriptor {
+    let mut raw = vec![0x0B, 0x24, 0x06, format_index, num_frames];
+    raw.extend_from_slice(&[0x01, 0x01, 0x00, 0x00, 0x00, 0x00]);
+    streaming(&raw)
+}
+
+//one discrete interval of 30 fps
+fn frame(sub_type: u8, frame_index: u8, width: u16, height: u16) -> USBDescriptor {
+    let mut raw = vec![0x1E, 0x24, sub_type, frame_index, 0x00];
+    raw.extend_from_slice(&width.to_le_bytes());
+    raw.extend_from_slice(&height.to_le_bytes());
+    for value in [1_000_000u32, 2_000_000, 614_400, 333_333] {
+        raw.extend_from_slice(&value.to_le_bytes());
+    }
+    raw.push(1);
+    raw.extend_from_slice(&333_333u32.to_le_bytes());
+    streaming(&raw)
+}
+
+fn mjpeg_frame(frame_index: u8, width: u16, height: u16) -> USBDescriptor {
+    frame(0x07, frame_index, width, height)
+}
+
+fn still_image_frame() -> USBDescriptor {
+    streaming(&[0x0A, 0x24, 0x03, 0x00, 0x01, 0x80, 0x02, 0xE0, 0x01, 0x00])
+}
+
+fn color_format() -> USBDescriptor {
+    streaming(&[0x06, 0x24, 0x0D, 0x01, 0x01, 0x04])
+}
+
+#[test]
+fn builds_the_tree() {
+    let descriptors = [
+        input_header(1),
+        mjpeg_format(1, 2),
+        mjpeg_frame(1, 640, 480),
+        mjpeg_frame(2, 320, 240),
+        still_image_frame(),
+        color_format(),
+        //anything that is not a videostreaming descriptor is skipped
+        USBDescriptor::Unrecognized(vec![0x05, 0x24, 0x00, 0x10, 0x01]),
+    ];
+    let interface = UvcStreamingInterface::from_descriptors(&descriptors).unwrap();
+    let UvcStreamingHeader::Input(_) = &interface.header else {
+        panic!("expected an input header");
+    };
+    assert_eq!(interface.header.endpoint_address(), 0x81);
+
+    let format = interface.format(1).unwrap();
+    assert_eq!(format.frames.len(), 2);
+    assert_eq!(format.frame(2).map(|frame| frame.width), Some(320));
+    assert_eq!(format.default_frame().map(|frame| frame.height), Some(480));
+    assert_eq!(format.frames[0].max_video_frame_buffer_size, Some(614_400));
+    assert!(format.still_image_frame.is_some());
+    assert!(format.color_matching.is_some());
+    assert!(interface.format(2).is_none());
+}
+
+#[test]
+fn missing_header() {
+    let descriptors = [mjpeg_format(1, 1), mjpeg_frame(1, 640, 480)];
+    assert_eq!(
+        UvcStreamingInterface::from_descriptors(&descriptors).err(),
+        Some(UvcStreamingError::MissingHeader)
+    );
+}
+
+#[test]
+fn orphan_descriptors() {
+    //frames, still image frames and color matching need a format ahead of them
+    for (orphan, sub_type) in [
+        (mjpeg_frame(1, 640, 480), 0x07),
+        (still_image_frame(), 0x03),
+        (color_format(), 0x0D),
+    ] {
+        let descriptors = [input_header(1), orphan, mjpeg_format(1, 0)];
+        assert_eq!(
+            UvcStreamingInterface::from_descriptors(&descriptors).err(),
+            Some(UvcStreamingError::OrphanDescriptor(sub_type))
+        );
+    }
+}
+
+#[test]
+fn duplicate_indices() {
+    let descriptors = [input_header(2), mjpeg_format(1, 0), mjpeg_format(1, 0)];
+    assert_eq!(
+        UvcStreamingInterface::from_descriptors(&descriptors).err(),
+        Some(UvcStreamingError::DuplicateFormatIndex(1))
+    );
+
+    let descriptors = [
+        input_header(1),
+        mjpeg_format(1, 2),
+        mjpeg_frame(1, 640, 480),
+        mjpeg_frame(1, 320, 240),
+    ];
+    assert_eq!(
+        UvcStreamingInterface::from_descriptors(&descriptors).err(),
+        Some(UvcStreamingError::DuplicateFrameIndex(1, 1))
+    );
+}
+
+#[test]
+fn frame_type_mismatch() {
+    //an uncompressed frame under an MJPEG format
+    let descriptors = [
+        input_header(1),
+        mjpeg_format(3, 1),
+        frame(0x05, 1, 640, 480),
+    ];
+    assert_eq!(
+        UvcStreamingInterface::from_descriptors(&descriptors).err(),
+        Some(UvcStreamingError::FrameTypeMismatch(3, 0x05))
+    );
+}
+
+#[test]
+fn count_mismatches() {
+    let descriptors = [
+        input_header(2),
+        mjpeg_format(1, 1),
+        mjpeg_frame(1, 640, 480),
+    ];
+    assert_eq!(
+        UvcStreamingInterface::from_descriptors(&descriptors).err(),
+        Some(UvcStreamingError::NumFormatsMismatch(2, 1))
+    );
+
+    let descriptors = [
+        input_header(1),
+        mjpeg_format(1, 2),
+        mjpeg_frame(1, 640, 480),
+    ];
+    assert_eq!(
+        UvcStreamingInterface::from_descriptors(&descriptors).err(),
+        Some(UvcStreamingError::NumFramesMismatch(1, 2, 1))
+    );
+}
@@ -0,0 +1,131 @@
+use usb_descriptor_decoder::descriptors::{
+    desc_uvc::{
+        uvc_controls::StreamingControl,
+        uvc_interfaces::{DVFormatType, UVCStreamingInterface},
+    },
+    parser::RawDescriptorParser,
+};
+
+#[test]
+fn output_header() {
+    //two formats, two byte bmaControls each
+    let raw = [
+        0x0D, 0x24, 0x02, 0x02, 0x40, 0x00, 0x01, 0x03, 0x02, 0x05, 0x00, 0x04, 0x00,
+    ];
+    let UVCStreamingInterface::OutputHeader(header) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected an output header");
+    };
+    assert_eq!((header.num_formats, header.total_length), (2, 0x40));
+    assert_eq!((header.endpoint_address, header.terminal_link), (0x01, 3));
+    assert_eq!(
+        header.format_controls(),
+        [
+            vec![
+                StreamingControl::KeyFrameRate,
+                StreamingControl::CompressionQuality
+            ],
+            vec![StreamingControl::CompressionQuality],
+        ]
+    );
+
+    //UVC 1.0 output headers stop after bTerminalLink
+    let raw = [0x08, 0x24, 0x02, 0x01, 0x20, 0x00, 0x02, 0x04];
+    let UVCStreamingInterface::OutputHeader(header) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected an output header");
+    };
+    assert_eq!(header.control_size, 0);
+    assert!(header.format_controls().is_empty());
+}
+
+#[test]
+fn output_header_lsusb_controls() {
+    let device = [
+        18, 0x01, 0x00, 0x02, 0xEF, 0x02, 0x01, 64, 0x34, 0x12, 0x78, 0x56, 0x00, 0x01, 0, 0, 0, 1,
+    ];
+    let mut config = vec![
+        9, 0x02, 0, 0, 2, 1, 0, 0x80, 250, //
+        8, 0x0B, 0, 2, 0x0E, 0x03, 0x00, 0, //
+        9, 0x04, 0, 0, 0, 0x0E, 0x01, 0x00, 0, //
+        13, 0x24, 0x01, 0x10, 0x01, 13, 0, 0x80, 0x8D, 0x5B, 0x00, 1, 1, //
+        9, 0x04, 1, 0, 1, 0x0E, 0x02, 0x00, 0, //
+        13, 0x24, 0x02, 2, 13, 0, 0x01, 3, 2, 0x05, 0x01, 0x04, 0x00, //
+        7, 0x05, 0x01, 0x02, 0x00, 0x02, 0,
+    ];
+    let total_length = config.len() as u16;
+    config[2..4].copy_from_slice(&total_length.to_le_bytes());
+    //like a transfer buffer, the parser stops at the zeroed tail
+    config.extend_from_slice(&[0, 0]);
+
+    let mut parser = RawDescriptorParser::new(device.to_vec());
+    parser.single_state_cycle();
+    parser.append_config(config);
+    let dump = parser.summarize().to_string();
+
+    //one entry per format, each bControlSize bytes wide
+    assert!(dump.contains("bmaControls( 0)"), "{}", dump);
+    assert!(dump.contains("bmaControls( 1)"), "{}", dump);
+    assert!(!dump.contains("bmaControls( 2)"), "{}", dump);
+    let first = dump
+        .lines()
+        .find(|line| line.contains("bmaControls( 0)"))
+        .unwrap();
+    assert!(first.trim_end().ends_with(" 261"), "{}", first);
+}
+
+#[test]
+fn mpeg2ts_format() {
+    //UVC 1.1 adds guidStrideFormat
+    let mut raw = vec![0x17, 0x24, 0x0A, 0x01, 0x00, 188, 188];
+    raw.extend_from_slice(b"YUY2\x00\x00\x10\x00\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    let UVCStreamingInterface::FormatMpeg2ts(format) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected an MPEG-2 TS format");
+    };
+    assert_eq!(format.format_index, 1);
+    assert_eq!((format.packet_length, format.stride_length), (188, 188));
+    assert_eq!(&format.guid_stride_format.unwrap()[..4], b"YUY2");
+
+    let raw = [0x07, 0x24, 0x0A, 0x02, 0x04, 192, 192];
+    let UVCStreamingInterface::FormatMpeg2ts(format) = UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected an MPEG-2 TS format");
+    };
+    assert_eq!((format.format_index, format.data_offset), (2, 4));
+    assert_eq!(format.guid_stride_format, None);
+}
+
+#[test]
+fn dv_format() {
+    let raw = [0x09, 0x24, 0x0C, 0x01, 0x00, 0x00, 0x02, 0x00, 0x81];
+    let UVCStreamingInterface::FormatDv(format) = UVCStreamingInterface::from_u8_array(&raw) else {
+        panic!("expected a DV format");
+    };
+    assert_eq!(format.format_index, 1);
+    assert_eq!(format.max_video_frame_buffer_size, 0x20000);
+    assert_eq!(format.dv_format_type(), Some(DVFormatType::SDL_DV));
+    assert!(format.is_60hz());
+
+    let raw = [0x09, 0x24, 0x0C, 0x02, 0x00, 0x00, 0x02, 0x00, 0x02];
+    let UVCStreamingInterface::FormatDv(format) = UVCStreamingInterface::from_u8_array(&raw) else {
+        panic!("expected a DV format");
+    };
+    assert_eq!(format.dv_format_type(), Some(DVFormatType::HD_DV));
+    assert!(!format.is_60hz());
+}
+
+#[test]
+fn stream_based_format() {
+    let mut raw = vec![0x18, 0x24, 0x12, 0x03];
+    raw.extend_from_slice(b"H264\x00\x00\x10\x00\x80\x00\x00\xaa\x00\x38\x9b\x71");
+    raw.extend_from_slice(&4096u32.to_le_bytes());
+    let UVCStreamingInterface::FormatStreamBased(format) =
+        UVCStreamingInterface::from_u8_array(&raw)
+    else {
+        panic!("expected a stream based format");
+    };
+    assert_eq!(format.format_index, 3);
+    assert_eq!(format.packet_length, 4096);
+    assert_eq!(format.pixel_format().unwrap().fourcc_str(), "H264");
+}